@@ -117,6 +117,106 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::IndexArray),
         }),
+        // get : Int -> Array a -> (| None : (), Some : a |)
+        Rc::new(Declaration::Definition {
+            name: String::from("get"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::Int,
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                            Type::mk_variant(
+                                common_kinds,
+                                vec![(Rc::from("None"), Type::Unit), (Rc::from("Some"), a)],
+                                None,
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayGet),
+        }),
+        // head : Array a -> (| None : (), Some : a |)
+        Rc::new(Declaration::Definition {
+            name: String::from("head"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![(Rc::from("None"), Type::Unit), (Rc::from("Some"), a)],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayHead),
+        }),
+        // last : Array a -> (| None : (), Some : a |)
+        Rc::new(Declaration::Definition {
+            name: String::from("last"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![(Rc::from("None"), Type::Unit), (Rc::from("Some"), a)],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayLast),
+        }),
+        // uncons : Array a -> (| None : (), Some : { first : a, rest : Array a } |)
+        Rc::new(Declaration::Definition {
+            name: String::from("uncons"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("None"), Type::Unit),
+                                (
+                                    Rc::from("Some"),
+                                    Type::mk_record(
+                                        common_kinds,
+                                        vec![
+                                            (Rc::from("first"), a.clone()),
+                                            (
+                                                Rc::from("rest"),
+                                                Type::app(Type::mk_array(common_kinds), a),
+                                            ),
+                                        ],
+                                        None,
+                                    ),
+                                ),
+                            ],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayUncons),
+        }),
         // slice : Int -> Int -> Array a -> Array a
         Rc::new(Declaration::Definition {
             name: String::from("slice"),
@@ -328,5 +428,321 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
                 ),
             )),
         }),
+        // filter : (a -> Bool) -> Array a -> Array a
+        Rc::new(Declaration::Definition {
+            name: String::from("filter"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(common_kinds, a.clone(), Type::Bool),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                            Type::app(Type::mk_array(common_kinds), a),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayFilter),
+        }),
+        // sortBy : (a -> a -> (| Less : (), Equal : (), Greater : () |)) -> Array a -> Array a
+        Rc::new(Declaration::Definition {
+            name: String::from("sortBy"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(
+                            common_kinds,
+                            a.clone(),
+                            Type::arrow(
+                                common_kinds,
+                                a.clone(),
+                                Type::mk_variant(
+                                    common_kinds,
+                                    vec![
+                                        (Rc::from("Less"), Type::Unit),
+                                        (Rc::from("Equal"), Type::Unit),
+                                        (Rc::from("Greater"), Type::Unit),
+                                    ],
+                                    None,
+                                ),
+                            ),
+                        ),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                            Type::app(Type::mk_array(common_kinds), a),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArraySortBy),
+        }),
+        // sort : Ord a => Array a -> Array a
+        Rc::new(Declaration::Definition {
+            name: String::from("sort"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::mk_fatarrow(
+                        common_kinds,
+                        Type::app(
+                            Type::Name(
+                                Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                                Rc::from("Ord"),
+                            ),
+                            a.clone(),
+                        ),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                            Type::app(Type::mk_array(common_kinds), a),
+                        ),
+                    ),
+                }
+            },
+            // \ordDict -> sortBy (compare ordDict)
+            body: Rc::new(Expr::mk_lam(
+                true,
+                Expr::mk_app(
+                    Expr::Builtin(Builtin::ArraySortBy),
+                    Expr::mk_app(Expr::Name(Name::definition("compare")), Expr::Var(0)),
+                ),
+            )),
+        }),
+        // reverse : Array a -> Array a
+        Rc::new(Declaration::Definition {
+            name: String::from("reverse"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                        Type::app(Type::mk_array(common_kinds), a),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayReverse),
+        }),
+        // zip : Array a -> Array b -> Array { fst : a, snd : b }
+        Rc::new(Declaration::Definition {
+            name: String::from("zip"),
+            sig: {
+                let a = Type::unsafe_mk_var(1, Kind::Type);
+                let b = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind()), (Rc::from("b"), b.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), b.clone()),
+                            Type::app(
+                                Type::mk_array(common_kinds),
+                                Type::mk_record(
+                                    common_kinds,
+                                    vec![(Rc::from("fst"), a), (Rc::from("snd"), b)],
+                                    None,
+                                ),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayZip),
+        }),
+        // find : (a -> Bool) -> Array a -> (| None : (), Some : a |)
+        Rc::new(Declaration::Definition {
+            name: String::from("find"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(common_kinds, a.clone(), Type::Bool),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                            Type::mk_variant(
+                                common_kinds,
+                                vec![(Rc::from("None"), Type::Unit), (Rc::from("Some"), a)],
+                                None,
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayFind),
+        }),
+        // all : (a -> Bool) -> Array a -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("all"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(common_kinds, a.clone(), Type::Bool),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a),
+                            Type::Bool,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayAll),
+        }),
+        // concat : Array (Array a) -> Array a
+        Rc::new(Declaration::Definition {
+            name: String::from("concat"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::app(
+                            Type::mk_array(common_kinds),
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                        ),
+                        Type::app(Type::mk_array(common_kinds), a),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayConcat),
+        }),
+        // range : Int -> Int -> Array Int
+        Rc::new(Declaration::Definition {
+            name: String::from("range"),
+            sig: TypeSig {
+                ty_vars: vec![],
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(
+                        common_kinds,
+                        Type::Int,
+                        Type::app(Type::mk_array(common_kinds), Type::Int),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayRange),
+        }),
+        // foldr : (a -> b -> b) -> b -> Array a -> b
+        Rc::new(Declaration::Definition {
+            name: String::from("foldr"),
+            sig: {
+                let a = Type::unsafe_mk_var(1, Kind::Type);
+                let b = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind()), (Rc::from("b"), b.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(
+                            common_kinds,
+                            a.clone(),
+                            Type::arrow(common_kinds, b.clone(), b.clone()),
+                        ),
+                        Type::arrow(
+                            common_kinds,
+                            b.clone(),
+                            Type::arrow(
+                                common_kinds,
+                                Type::app(Type::mk_array(common_kinds), a),
+                                b,
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayFoldr),
+        }),
+        // partition : (a -> Bool) -> Array a -> { pass : Array a, fail : Array a }
+        Rc::new(Declaration::Definition {
+            name: String::from("partition"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(common_kinds, a.clone(), Type::Bool),
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(Type::mk_array(common_kinds), a.clone()),
+                            Type::mk_record(
+                                common_kinds,
+                                vec![
+                                    (
+                                        Rc::from("pass"),
+                                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                                    ),
+                                    (Rc::from("fail"), Type::app(Type::mk_array(common_kinds), a)),
+                                ],
+                                None,
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::ArrayPartition),
+        }),
+        // indexOf : Eq a => a -> Array a -> (| None : (), Some : Int |)
+        Rc::new(Declaration::Definition {
+            name: String::from("indexOf"),
+            sig: {
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("a"), a.kind())],
+                    body: Type::mk_fatarrow(
+                        common_kinds,
+                        Type::app(
+                            Type::Name(
+                                Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                                Rc::from("Eq"),
+                            ),
+                            a.clone(),
+                        ),
+                        Type::arrow(
+                            common_kinds,
+                            a.clone(),
+                            Type::arrow(
+                                common_kinds,
+                                Type::app(Type::mk_array(common_kinds), a),
+                                Type::mk_variant(
+                                    common_kinds,
+                                    vec![
+                                        (Rc::from("None"), Type::Unit),
+                                        (Rc::from("Some"), Type::Int),
+                                    ],
+                                    None,
+                                ),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            // \eqDict -> indexOf (eq eqDict)
+            body: Rc::new(Expr::mk_lam(
+                true,
+                Expr::mk_app(
+                    Expr::Builtin(Builtin::ArrayIndexOf),
+                    Expr::mk_app(Expr::Name(Name::definition("eq")), Expr::Var(0)),
+                ),
+            )),
+        }),
     ]
 }
@@ -0,0 +1,160 @@
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
+use std::rc::Rc;
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    vec![
+        // length : Bytes -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("length"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Bytes, Type::Int),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesLength),
+        }),
+        // index : Int -> Bytes -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("index"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Bytes, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesIndex),
+        }),
+        // slice : Int -> Int -> Bytes -> Bytes
+        Rc::new(Declaration::Definition {
+            name: String::from("slice"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(
+                        common_kinds,
+                        Type::Int,
+                        Type::arrow(common_kinds, Type::Bytes, Type::Bytes),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesSlice),
+        }),
+        // append : Bytes -> Bytes -> Bytes
+        Rc::new(Declaration::Definition {
+            name: String::from("append"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Bytes,
+                    Type::arrow(common_kinds, Type::Bytes, Type::Bytes),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesAppend),
+        }),
+        // concat : Array Bytes -> Bytes
+        Rc::new(Declaration::Definition {
+            name: String::from("concat"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::app(Type::mk_array(common_kinds), Type::Bytes),
+                    Type::Bytes,
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesConcat),
+        }),
+        // fromUtf8 : Bytes -> (| Ok : String, Err : { message : String, validUpTo : Int } |)
+        Rc::new(Declaration::Definition {
+            name: String::from("fromUtf8"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Bytes,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("Ok"), Type::String),
+                            (
+                                Rc::from("Err"),
+                                Type::mk_record(
+                                    common_kinds,
+                                    vec![
+                                        (Rc::from("message"), Type::String),
+                                        (Rc::from("validUpTo"), Type::Int),
+                                    ],
+                                    None,
+                                ),
+                            ),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesFromUtf8),
+        }),
+        // toHex : Bytes -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("toHex"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Bytes, Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesToHex),
+        }),
+        // fromHex : String -> (| None : (), Some : Bytes |)
+        Rc::new(Declaration::Definition {
+            name: String::from("fromHex"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("None"), Type::Unit),
+                            (Rc::from("Some"), Type::Bytes),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesFromHex),
+        }),
+        // toBase64 : Bytes -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("toBase64"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Bytes, Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesToBase64),
+        }),
+        // fromBase64 : String -> (| None : (), Some : Bytes |)
+        Rc::new(Declaration::Definition {
+            name: String::from("fromBase64"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("None"), Type::Unit),
+                            (Rc::from("Some"), Type::Bytes),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::BytesFromBase64),
+        }),
+    ]
+}
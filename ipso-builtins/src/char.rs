@@ -25,5 +25,97 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::CharToString),
         }),
+        // isDigit : Char -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isDigit"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Bool),
+            },
+            body: Expr::alloc_builtin(Builtin::CharIsDigit),
+        }),
+        // isAlpha : Char -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isAlpha"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Bool),
+            },
+            body: Expr::alloc_builtin(Builtin::CharIsAlpha),
+        }),
+        // isSpace : Char -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isSpace"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Bool),
+            },
+            body: Expr::alloc_builtin(Builtin::CharIsSpace),
+        }),
+        // isUpper : Char -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isUpper"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Bool),
+            },
+            body: Expr::alloc_builtin(Builtin::CharIsUpper),
+        }),
+        // isLower : Char -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isLower"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Bool),
+            },
+            body: Expr::alloc_builtin(Builtin::CharIsLower),
+        }),
+        // toUpper : Char -> Char
+        Rc::new(Declaration::Definition {
+            name: String::from("toUpper"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Char),
+            },
+            body: Expr::alloc_builtin(Builtin::CharToUpper),
+        }),
+        // toLower : Char -> Char
+        Rc::new(Declaration::Definition {
+            name: String::from("toLower"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Char),
+            },
+            body: Expr::alloc_builtin(Builtin::CharToLower),
+        }),
+        // toCode : Char -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("toCode"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Char, Type::Int),
+            },
+            body: Expr::alloc_builtin(Builtin::CharToCode),
+        }),
+        // fromCode : Int -> (| None : (), Some : Char |)
+        Rc::new(Declaration::Definition {
+            name: String::from("fromCode"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("None"), Type::Unit),
+                            (Rc::from("Some"), Type::Char),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::CharFromCode),
+        }),
     ]
 }
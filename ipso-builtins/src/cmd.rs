@@ -31,6 +31,19 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             ),
             body: Rc::new(Expr::Builtin(Builtin::CmdRead)),
         }),
+        // readBytes : Cmd -> IO Bytes
+        Rc::new(Declaration::Definition {
+            name: String::from("readBytes"),
+            sig: TypeSig::new(
+                vec![],
+                Type::arrow(
+                    common_kinds,
+                    Type::Cmd,
+                    Type::app(Type::mk_io(common_kinds), Type::Bytes),
+                ),
+            ),
+            body: Rc::new(Expr::Builtin(Builtin::CmdReadBytes)),
+        }),
         // lines : Cmd -> IO (Array String)
         Rc::new(Declaration::Definition {
             name: String::from("lines"),
@@ -47,6 +60,107 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             ),
             body: Rc::new(Expr::Builtin(Builtin::Lines)),
         }),
+        // try : Cmd -> IO (| Ok : Int, Err : String |)
+        Rc::new(Declaration::Definition {
+            name: String::from("try"),
+            sig: TypeSig::new(
+                vec![],
+                Type::arrow(
+                    common_kinds,
+                    Type::Cmd,
+                    Type::app(
+                        Type::mk_io(common_kinds),
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![(Rc::from("Ok"), Type::Int), (Rc::from("Err"), Type::String)],
+                            None,
+                        ),
+                    ),
+                ),
+            ),
+            body: Rc::new(Expr::Builtin(Builtin::CmdTry)),
+        }),
+        // output : Cmd -> IO (| Ok : { status : Int, stdout : String, stderr : String }, Err : String |)
+        Rc::new(Declaration::Definition {
+            name: String::from("output"),
+            sig: TypeSig::new(
+                vec![],
+                Type::arrow(
+                    common_kinds,
+                    Type::Cmd,
+                    Type::app(
+                        Type::mk_io(common_kinds),
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (
+                                    Rc::from("Ok"),
+                                    Type::mk_record(
+                                        common_kinds,
+                                        vec![
+                                            (Rc::from("status"), Type::Int),
+                                            (Rc::from("stdout"), Type::String),
+                                            (Rc::from("stderr"), Type::String),
+                                        ],
+                                        None,
+                                    ),
+                                ),
+                                (Rc::from("Err"), Type::String),
+                            ],
+                            None,
+                        ),
+                    ),
+                ),
+            ),
+            body: Rc::new(Expr::Builtin(Builtin::CmdOutput)),
+        }),
+        // outputBytes : Cmd -> IO (| Ok : { status : Int, stdout : Bytes, stderr : Bytes }, Err : String |)
+        Rc::new(Declaration::Definition {
+            name: String::from("outputBytes"),
+            sig: TypeSig::new(
+                vec![],
+                Type::arrow(
+                    common_kinds,
+                    Type::Cmd,
+                    Type::app(
+                        Type::mk_io(common_kinds),
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (
+                                    Rc::from("Ok"),
+                                    Type::mk_record(
+                                        common_kinds,
+                                        vec![
+                                            (Rc::from("status"), Type::Int),
+                                            (Rc::from("stdout"), Type::Bytes),
+                                            (Rc::from("stderr"), Type::Bytes),
+                                        ],
+                                        None,
+                                    ),
+                                ),
+                                (Rc::from("Err"), Type::String),
+                            ],
+                            None,
+                        ),
+                    ),
+                ),
+            ),
+            body: Rc::new(Expr::Builtin(Builtin::CmdOutputBytes)),
+        }),
+        // pipe : Cmd -> Cmd -> Cmd
+        Rc::new(Declaration::Definition {
+            name: String::from("pipe"),
+            sig: TypeSig::new(
+                vec![],
+                Type::arrow(
+                    common_kinds,
+                    Type::Cmd,
+                    Type::arrow(common_kinds, Type::Cmd, Type::Cmd),
+                ),
+            ),
+            body: Rc::new(Expr::Builtin(Builtin::PipeCmd)),
+        }),
         // show : Cmd -> String
         Rc::new(Declaration::Definition {
             name: String::from("show"),
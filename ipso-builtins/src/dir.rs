@@ -0,0 +1,91 @@
+use crate::io;
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
+use std::rc::Rc;
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    vec![
+        // create : String -> IO (| Ok : (), Err : IOError |)
+        //
+        // Parent directories are created as needed.
+        Rc::new(Declaration::Definition {
+            name: String::from("create"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(common_kinds, Type::Unit),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::DirCreate),
+        }),
+        // list : String -> IO (| Ok : Array String, Err : IOError |)
+        //
+        // The names of a directory's entries, sorted.
+        Rc::new(Declaration::Definition {
+            name: String::from("list"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(
+                                common_kinds,
+                                Type::app(Type::mk_array(common_kinds), Type::String),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::DirList),
+        }),
+        // remove : String -> IO (| Ok : (), Err : IOError |)
+        //
+        // Fails when the directory isn't empty.
+        Rc::new(Declaration::Definition {
+            name: String::from("remove"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(common_kinds, Type::Unit),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::DirRemove),
+        }),
+        // removeAll : String -> IO (| Ok : (), Err : IOError |)
+        //
+        // Removes a directory and everything it contains.
+        Rc::new(Declaration::Definition {
+            name: String::from("removeAll"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(common_kinds, Type::Unit),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::DirRemoveAll),
+        }),
+    ]
+}
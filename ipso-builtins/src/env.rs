@@ -1,3 +1,4 @@
+use crate::io;
 use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
 use std::rc::Rc;
 
@@ -61,5 +62,45 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::EnvSetvar),
         }),
+        // tryGetvar : String -> IO (| Ok : String, Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("tryGetvar"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(common_kinds, Type::String),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::EnvTryGetvar),
+        }),
+        // trySetvar : String -> String -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("trySetvar"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::app(
+                                Type::mk_io(common_kinds),
+                                io::mk_result(common_kinds, Type::Unit),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::EnvTrySetvar),
+        }),
     ]
 }
@@ -1,3 +1,4 @@
+use crate::io;
 use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
 use std::rc::Rc;
 
@@ -56,5 +57,222 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::FileAppend),
         }),
+        // readBytes : String -> IO Bytes
+        Rc::new(Declaration::Definition {
+            name: String::from("readBytes"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::app(Type::mk_io(common_kinds), Type::Bytes),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::FileReadBytes),
+        }),
+        // writeBytes : String -> Bytes -> IO ()
+        Rc::new(Declaration::Definition {
+            name: String::from("writeBytes"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::arrow(
+                        common_kinds,
+                        Type::Bytes,
+                        Type::app(Type::mk_io(common_kinds), Type::Unit),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::FileWriteBytes),
+        }),
+        // tryRead : String -> IO (| Ok : String, Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("tryRead"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(common_kinds, Type::String),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileTryRead),
+        }),
+        // tryWrite : String -> String -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("tryWrite"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::app(
+                                Type::mk_io(common_kinds),
+                                io::mk_result(common_kinds, Type::Unit),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileTryWrite),
+        }),
+        // tryAppend : String -> String -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("tryAppend"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::app(
+                                Type::mk_io(common_kinds),
+                                io::mk_result(common_kinds, Type::Unit),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileTryAppend),
+        }),
+        // remove : String -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("remove"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(common_kinds, Type::Unit),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileRemove),
+        }),
+        // copy : String -> String -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("copy"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::app(
+                                Type::mk_io(common_kinds),
+                                io::mk_result(common_kinds, Type::Unit),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileCopy),
+        }),
+        // rename : String -> String -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("rename"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::app(
+                                Type::mk_io(common_kinds),
+                                io::mk_result(common_kinds, Type::Unit),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileRename),
+        }),
+        /*
+        metadata :
+          String ->
+          IO
+            (|
+              Ok : { isDir : Bool, isFile : Bool, modified : Int, permissions : Int, size : Int },
+              Err : IOError
+            |)
+
+        `modified` is in seconds since the Unix epoch, and `permissions` is the Unix file mode.
+        */
+        Rc::new(Declaration::Definition {
+            name: String::from("metadata"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            io::mk_result(
+                                common_kinds,
+                                Type::mk_record(
+                                    common_kinds,
+                                    vec![
+                                        (Rc::from("isDir"), Type::Bool),
+                                        (Rc::from("isFile"), Type::Bool),
+                                        (Rc::from("modified"), Type::Int),
+                                        (Rc::from("permissions"), Type::Int),
+                                        (Rc::from("size"), Type::Int),
+                                    ],
+                                    None,
+                                ),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileMetadata),
+        }),
+        // chmod : String -> Int -> IO (| Ok : (), Err : IOError |)
+        Rc::new(Declaration::Definition {
+            name: String::from("chmod"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::Int,
+                            Type::app(
+                                Type::mk_io(common_kinds),
+                                io::mk_result(common_kinds, Type::Unit),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::FileChmod),
+        }),
     ]
 }
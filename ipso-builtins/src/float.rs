@@ -0,0 +1,67 @@
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
+use std::rc::Rc;
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    vec![
+        // eq : Float -> Float -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("eq"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Float,
+                    Type::arrow(common_kinds, Type::Float, Type::Bool),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::EqFloat),
+        }),
+        // toString : Float -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("toString"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Float, Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::FloatToString),
+        }),
+        // fromInt : Int -> Float
+        Rc::new(Declaration::Definition {
+            name: String::from("fromInt"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Int, Type::Float),
+            },
+            body: Expr::alloc_builtin(Builtin::FloatFromInt),
+        }),
+        // toInt : Float -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("toInt"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Float, Type::Int),
+            },
+            body: Expr::alloc_builtin(Builtin::FloatToInt),
+        }),
+        // parse : String -> (| None : (), Some : Float |)
+        Rc::new(Declaration::Definition {
+            name: String::from("parse"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("None"), Type::Unit),
+                            (Rc::from("Some"), Type::Float),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::FloatParse),
+        }),
+    ]
+}
@@ -25,7 +25,11 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::IntToString),
         }),
-        // mod : Int -> Int -> Bool
+        /*
+        mod : Int -> Int -> Int
+
+        The remainder of `div`, which has the same sign as the divisor.
+        */
         Rc::new(Declaration::Definition {
             name: String::from("mod"),
             sig: TypeSig {
@@ -38,5 +42,148 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::IntMod),
         }),
+        /*
+        div : Int -> Int -> Int
+
+        Division that rounds towards negative infinity, so that `div a b * b + mod a b == a`.
+        */
+        Rc::new(Declaration::Definition {
+            name: String::from("div"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Int, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntDiv),
+        }),
+        /*
+        rem : Int -> Int -> Int
+
+        The remainder of `quot`, which has the same sign as the dividend.
+        */
+        Rc::new(Declaration::Definition {
+            name: String::from("rem"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Int, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntRem),
+        }),
+        /*
+        quot : Int -> Int -> Int
+
+        Division that rounds towards zero, so that `quot a b * b + rem a b == a`.
+        */
+        Rc::new(Declaration::Definition {
+            name: String::from("quot"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Int, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntQuot),
+        }),
+        // abs : Int -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("abs"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Int, Type::Int),
+            },
+            body: Expr::alloc_builtin(Builtin::IntAbs),
+        }),
+        // pow : Int -> Int -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("pow"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Int, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntPow),
+        }),
+        // min : Int -> Int -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("min"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Int, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntMin),
+        }),
+        // max : Int -> Int -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("max"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(common_kinds, Type::Int, Type::Int),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntMax),
+        }),
+        // safeDiv : Int -> Int -> (| None : (), Some : Int |)
+        Rc::new(Declaration::Definition {
+            name: String::from("safeDiv"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(
+                        common_kinds,
+                        Type::Int,
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("None"), Type::Unit),
+                                (Rc::from("Some"), Type::Int),
+                            ],
+                            None,
+                        ),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntSafeDiv),
+        }),
+        // parse : String -> (| None : (), Some : Int |)
+        Rc::new(Declaration::Definition {
+            name: String::from("parse"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("None"), Type::Unit),
+                            (Rc::from("Some"), Type::Int),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::IntParse),
+        }),
     ]
 }
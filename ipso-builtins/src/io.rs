@@ -2,6 +2,60 @@ use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
 use ipso_syntax::kind::Kind;
 use std::rc::Rc;
 
+/**
+The errors reported by builtin IO actions.
+
+```text
+IOError =
+  {
+    kind : (| AlreadyExists : (), InvalidData : (), InvalidInput : (), NotFound : (), Other : (), PermissionDenied : () |),
+    message : String
+  }
+```
+*/
+pub fn mk_error(common_kinds: &CommonKinds) -> Type {
+    Type::mk_record(
+        common_kinds,
+        vec![
+            (
+                Rc::from("kind"),
+                Type::mk_variant(
+                    common_kinds,
+                    vec![
+                        (Rc::from("AlreadyExists"), Type::Unit),
+                        (Rc::from("InvalidData"), Type::Unit),
+                        (Rc::from("InvalidInput"), Type::Unit),
+                        (Rc::from("NotFound"), Type::Unit),
+                        (Rc::from("Other"), Type::Unit),
+                        (Rc::from("PermissionDenied"), Type::Unit),
+                    ],
+                    None,
+                ),
+            ),
+            (Rc::from("message"), Type::String),
+        ],
+        None,
+    )
+}
+
+/**
+The result of a builtin IO action that can fail.
+
+```text
+(| Ok : a, Err : IOError |)
+```
+*/
+pub fn mk_result(common_kinds: &CommonKinds, a: Type) -> Type {
+    Type::mk_variant(
+        common_kinds,
+        vec![
+            (Rc::from("Ok"), a),
+            (Rc::from("Err"), mk_error(common_kinds)),
+        ],
+        None,
+    )
+}
+
 pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
     vec![
         // map : (a -> b) -> IO a -> IO b
@@ -0,0 +1,256 @@
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
+use ipso_syntax::kind::Kind;
+use std::rc::Rc;
+
+// Json
+fn json_type() -> Type {
+    Type::Name(Kind::Type, Rc::from("Json"))
+}
+
+// (| None : (), Some : a |)
+fn option_type(common_kinds: &CommonKinds, a: Type) -> Type {
+    Type::mk_variant(
+        common_kinds,
+        vec![(Rc::from("None"), Type::Unit), (Rc::from("Some"), a)],
+        None,
+    )
+}
+
+// Array { key : String, value : Json }
+fn members_type(common_kinds: &CommonKinds) -> Type {
+    Type::app(
+        Type::mk_array(common_kinds),
+        Type::mk_record(
+            common_kinds,
+            vec![
+                (Rc::from("key"), Type::String),
+                (Rc::from("value"), json_type()),
+            ],
+            None,
+        ),
+    )
+}
+
+/*
+The constructor of `json.view`'s result that has the given tag.
+
+`Json` values are represented by the variant that `json.view` returns, so `Json`'s
+constructors are variant constructors.
+*/
+fn constructor(tag: i64) -> Expr {
+    Expr::mk_variant(Expr::Int(tag))
+}
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    let accessor = |name: &str, builtin: Builtin, a: Type| {
+        Rc::new(Declaration::Definition {
+            name: String::from(name),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, json_type(), option_type(common_kinds, a)),
+            },
+            body: Expr::alloc_builtin(builtin),
+        })
+    };
+
+    vec![
+        // parse : String -> (| Err : String, Ok : Json |)
+        Rc::new(Declaration::Definition {
+            name: String::from("parse"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (Rc::from("Err"), Type::String),
+                            (Rc::from("Ok"), json_type()),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::JsonParse),
+        }),
+        // render : Json -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("render"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, json_type(), Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::JsonRender),
+        }),
+        /*
+        view :
+          Json ->
+          (|
+            Array : Array Json,
+            Bool : Bool,
+            Float : Float,
+            Int : Int,
+            Null : (),
+            Object : Array { key : String, value : Json },
+            String : String
+          |)
+        */
+        Rc::new(Declaration::Definition {
+            name: String::from("view"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    json_type(),
+                    Type::mk_variant(
+                        common_kinds,
+                        vec![
+                            (
+                                Rc::from("Array"),
+                                Type::app(Type::mk_array(common_kinds), json_type()),
+                            ),
+                            (Rc::from("Bool"), Type::Bool),
+                            (Rc::from("Float"), Type::Float),
+                            (Rc::from("Int"), Type::Int),
+                            (Rc::from("Null"), Type::Unit),
+                            (Rc::from("Object"), members_type(common_kinds)),
+                            (Rc::from("String"), Type::String),
+                        ],
+                        None,
+                    ),
+                ),
+            },
+            // \x -> x
+            body: Rc::new(Expr::mk_lam(true, Expr::Var(0))),
+        }),
+        // null : Json
+        Rc::new(Declaration::Definition {
+            name: String::from("null"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: json_type(),
+            },
+            // Null ()
+            body: Rc::new(Expr::mk_app(constructor(4), Expr::Unit)),
+        }),
+        // bool : Bool -> Json
+        Rc::new(Declaration::Definition {
+            name: String::from("bool"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Bool, json_type()),
+            },
+            // Bool
+            body: Rc::new(constructor(1)),
+        }),
+        // int : Int -> Json
+        Rc::new(Declaration::Definition {
+            name: String::from("int"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Int, json_type()),
+            },
+            // Int
+            body: Rc::new(constructor(3)),
+        }),
+        // float : Float -> Json
+        Rc::new(Declaration::Definition {
+            name: String::from("float"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::Float, json_type()),
+            },
+            // Float
+            body: Rc::new(constructor(2)),
+        }),
+        // string : String -> Json
+        Rc::new(Declaration::Definition {
+            name: String::from("string"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::String, json_type()),
+            },
+            // String
+            body: Rc::new(constructor(6)),
+        }),
+        // array : Array Json -> Json
+        Rc::new(Declaration::Definition {
+            name: String::from("array"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::app(Type::mk_array(common_kinds), json_type()),
+                    json_type(),
+                ),
+            },
+            // Array
+            body: Rc::new(constructor(0)),
+        }),
+        // object : Array { key : String, value : Json } -> Json
+        Rc::new(Declaration::Definition {
+            name: String::from("object"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, members_type(common_kinds), json_type()),
+            },
+            // Object
+            body: Rc::new(constructor(5)),
+        }),
+        // field : String -> Json -> (| None : (), Some : Json |)
+        Rc::new(Declaration::Definition {
+            name: String::from("field"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::arrow(
+                        common_kinds,
+                        json_type(),
+                        option_type(common_kinds, json_type()),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::JsonField),
+        }),
+        // index : Int -> Json -> (| None : (), Some : Json |)
+        Rc::new(Declaration::Definition {
+            name: String::from("index"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(
+                        common_kinds,
+                        json_type(),
+                        option_type(common_kinds, json_type()),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::JsonIndex),
+        }),
+        // asBool : Json -> (| None : (), Some : Bool |)
+        accessor("asBool", Builtin::JsonAsBool, Type::Bool),
+        // asInt : Json -> (| None : (), Some : Int |)
+        accessor("asInt", Builtin::JsonAsInt, Type::Int),
+        // asFloat : Json -> (| None : (), Some : Float |)
+        accessor("asFloat", Builtin::JsonAsFloat, Type::Float),
+        // asString : Json -> (| None : (), Some : String |)
+        accessor("asString", Builtin::JsonAsString, Type::String),
+        // asArray : Json -> (| None : (), Some : Array Json |)
+        accessor(
+            "asArray",
+            Builtin::JsonAsArray,
+            Type::app(Type::mk_array(common_kinds), json_type()),
+        ),
+        // asObject : Json -> (| None : (), Some : Array { key : String, value : Json } |)
+        accessor(
+            "asObject",
+            Builtin::JsonAsObject,
+            members_type(common_kinds),
+        ),
+    ]
+}
@@ -1,27 +1,71 @@
 pub mod array;
+pub mod bytes;
 pub mod file;
+pub mod float;
 pub mod path;
 pub mod exit;
 pub mod env;
 pub mod char;
 pub mod cmd;
+pub mod dir;
 pub mod int;
 pub mod io;
+pub mod json;
+pub mod map;
+pub mod regex;
+pub mod set;
 pub mod string;
 
 use ipso_core::{
     Branch, Builtin, ClassDeclaration, ClassMember, CommonKinds, Declaration, Expr, Module, Name,
     Pattern, StringPart, Type, TypeSig,
 };
-use ipso_syntax::{kind::Kind, ModuleRef};
+use ipso_syntax::{desugar, kind::Kind, ModuleRef};
 use std::rc::Rc;
 
 pub fn builtins(common_kinds: &CommonKinds) -> Module {
     let io_ty = Type::mk_io(common_kinds);
     let array_ty = Type::mk_array(common_kinds);
+    let json_ty = Type::Name(Kind::Type, Rc::from("Json"));
+    let to_json = Type::Name(
+        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+        Rc::from("ToJson"),
+    );
+    let from_json = Type::Name(
+        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+        Rc::from("FromJson"),
+    );
+    let functor_ty = Type::Name(
+        Kind::mk_arrow(&common_kinds.type_to_type, &Kind::Constraint),
+        Rc::from("Functor"),
+    );
+    let applicative_ty = Type::Name(
+        Kind::mk_arrow(&common_kinds.type_to_type, &Kind::Constraint),
+        Rc::from("Applicative"),
+    );
+    let monad_ty = Type::Name(
+        Kind::mk_arrow(&common_kinds.type_to_type, &Kind::Constraint),
+        Rc::from("Monad"),
+    );
 
     Module {
         decls: vec![
+            Declaration::BuiltinType {
+                name: String::from("Map"),
+                kind: common_kinds.type_to_type_to_type.clone(),
+            },
+            Declaration::BuiltinType {
+                name: String::from("Set"),
+                kind: common_kinds.type_to_type.clone(),
+            },
+            Declaration::BuiltinType {
+                name: String::from("Json"),
+                kind: Kind::Type,
+            },
+            Declaration::BuiltinType {
+                name: String::from("Regex"),
+                kind: Kind::Type,
+            },
             Declaration::Module {
                 name: String::from("io"),
                 decls: io::decls(common_kinds),
@@ -34,10 +78,18 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 name: String::from("int"),
                 decls: int::decls(common_kinds),
             },
+            Declaration::Module {
+                name: String::from("float"),
+                decls: float::decls(common_kinds),
+            },
             Declaration::Module {
                 name: String::from("array"),
                 decls: array::decls(common_kinds),
             },
+            Declaration::Module {
+                name: String::from("bytes"),
+                decls: bytes::decls(common_kinds),
+            },
             Declaration::Module {
                 name: String::from("char"),
                 decls: char::decls(common_kinds),
@@ -62,6 +114,26 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 name: String::from("file"),
                 decls: file::decls(common_kinds),
             },
+            Declaration::Module {
+                name: String::from("dir"),
+                decls: dir::decls(common_kinds),
+            },
+            Declaration::Module {
+                name: String::from("map"),
+                decls: map::decls(common_kinds),
+            },
+            Declaration::Module {
+                name: String::from("set"),
+                decls: set::decls(common_kinds),
+            },
+            Declaration::Module {
+                name: String::from("json"),
+                decls: json::decls(common_kinds),
+            },
+            Declaration::Module {
+                name: String::from("regex"),
+                decls: regex::decls(common_kinds),
+            },
             // trace : a -> b -> b
             Declaration::Definition {
                 name: String::from("trace"),
@@ -111,7 +183,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 name: String::from("readln"),
                 sig: TypeSig {
                     ty_vars: vec![],
-                    body: Type::app(io_ty, Type::String),
+                    body: Type::app(io_ty.clone(), Type::String),
                 },
                 body: Expr::alloc_builtin(Builtin::Readln),
             },
@@ -133,6 +205,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                             Type::arrow(common_kinds, Type::Var(Kind::Type, 0), Type::Bool),
                         ),
                     },
+                    default: None,
                 }],
             }),
             /*
@@ -159,6 +232,29 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 )),
             },
             /*
+            instance Eq Float where
+              eq = eqFloat
+             */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Eq"),
+                    ),
+                    Type::Float,
+                ),
+                evidence: Rc::from("Eq Float"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Eq Float"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(Expr::Int(0), Expr::Builtin(Builtin::EqFloat))],
+                    None,
+                )),
+            },
+            /*
             instance Eq Char where
               eq = eqChar
              */
@@ -278,7 +374,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                         Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
                         Rc::from("Eq"),
                     ),
-                    Type::Name(Kind::Type, Rc::from("a")),
+                    Type::Var(Kind::Type, 0),
                 )],
                 name: Rc::from("Ord"),
                 args: vec![(Rc::from("a"), Kind::Type)],
@@ -304,6 +400,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                             ),
                         ),
                     },
+                    default: None,
                 }],
             }),
             /*
@@ -479,6 +576,39 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 )),
             },
             /*
+            instance Ord Float where
+              compare = compareFloat
+             */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Ord"),
+                    ),
+                    Type::Float,
+                ),
+                evidence: Rc::from("Ord Float"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Ord Float"),
+                body: Rc::new(Expr::mk_record(
+                    vec![
+                        (
+                            Expr::Int(0),
+                            // dict : Eq Float
+                            Expr::mk_record(
+                                vec![(Expr::Int(0), Expr::Builtin(Builtin::EqFloat))],
+                                None,
+                            ),
+                        ),
+                        (Expr::Int(1), Expr::Builtin(Builtin::CompareFloat)),
+                    ],
+                    None,
+                )),
+            },
+            /*
             instance Ord a => Ord (Array a) where
               compare = compareArray compare
              */
@@ -545,6 +675,291 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                     ),
                 )),
             },
+            /*
+            instance Eq () where
+              eq _ _ = true
+             */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Eq"),
+                    ),
+                    Type::Unit,
+                ),
+                evidence: Rc::from("Eq ()"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Eq ()"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // eq
+                        Expr::Int(0),
+                        // \_ _ -> true
+                        Expr::mk_lam(true, Expr::mk_lam(true, Expr::True)),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance Ord () where
+              compare _ _ = Equal ()
+             */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Ord"),
+                    ),
+                    Type::Unit,
+                ),
+                evidence: Rc::from("Ord ()"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Ord ()"),
+                body: Rc::new(Expr::mk_record(
+                    vec![
+                        (
+                            Expr::Int(0),
+                            // dict : Eq ()
+                            Expr::Name(Name::evidence("Eq ()")),
+                        ),
+                        (
+                            // compare
+                            Expr::Int(1),
+                            // \_ _ -> Equal ()
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_lam(
+                                    true,
+                                    Expr::mk_app(Expr::mk_variant(Expr::Int(0)), Expr::Unit),
+                                ),
+                            ),
+                        ),
+                    ],
+                    None,
+                )),
+            },
+            /*
+            instance EqRecordFields a => Eq { a } where
+              eq = eqRecordFields
+
+            The evidence for EqRecordFields is a function
+            of type `Record a -> Record a -> Bool`.
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Row)],
+                assumes: vec![Type::app(Type::EqRecordFields, Type::Var(Kind::Row, 0))],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Eq"),
+                    ),
+                    // { a }
+                    Type::app(Type::mk_record_ctor(common_kinds), Type::Var(Kind::Row, 0)),
+                ),
+                evidence: Rc::from("Eq Record"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Eq Record"),
+                // \eqRecordFields -> { eq = eqRecordFields }
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(vec![(Expr::Int(0), Expr::Var(0))], None),
+                )),
+            },
+            /*
+            instance OrdRecordFields a => Ord { a } where
+              compare = ordRecordFields
+
+            The evidence for OrdRecordFields is a function of type
+            `Record a -> Record a -> (| Equal : (), Greater : (), Less : () |)`.
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Row)],
+                assumes: vec![Type::app(Type::OrdRecordFields, Type::Var(Kind::Row, 0))],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Ord"),
+                    ),
+                    // { a }
+                    Type::app(Type::mk_record_ctor(common_kinds), Type::Var(Kind::Row, 0)),
+                ),
+                evidence: Rc::from("Ord Record"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Ord Record"),
+                // \ordRecordFields -> ...
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![
+                            (
+                                // eqDict
+                                Expr::Int(0),
+                                /*
+                                {
+                                  eq = \a b ->
+                                    case ordRecordFields a b of
+                                      Equal _ -> true
+                                      _ -> false
+                                }
+                                */
+                                Expr::mk_record(
+                                    vec![(
+                                        Expr::Int(0),
+                                        Expr::mk_lam(
+                                            true,
+                                            Expr::mk_lam(
+                                                true,
+                                                Expr::mk_case(
+                                                    Expr::mk_app(
+                                                        Expr::mk_app(Expr::Var(2), Expr::Var(1)),
+                                                        Expr::Var(0),
+                                                    ),
+                                                    vec![
+                                                        Branch {
+                                                            pattern: Pattern::mk_variant(
+                                                                Expr::Int(0),
+                                                                Pattern::Wildcard,
+                                                            ),
+                                                            body: Expr::True,
+                                                        },
+                                                        Branch {
+                                                            pattern: Pattern::Wildcard,
+                                                            body: Expr::False,
+                                                        },
+                                                    ],
+                                                ),
+                                            ),
+                                        ),
+                                    )],
+                                    None,
+                                ),
+                            ),
+                            (
+                                // compare
+                                Expr::Int(1),
+                                Expr::Var(0),
+                            ),
+                        ],
+                        None,
+                    ),
+                )),
+            },
+            /*
+            instance EqVariantCtor a => Eq (| a |) where
+              eq = eqVariantCtor
+
+            The evidence for EqVariantCtor is a function
+            of type `Variant a -> Variant a -> Bool`.
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Row)],
+                assumes: vec![Type::app(Type::EqVariantCtor, Type::Var(Kind::Row, 0))],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Eq"),
+                    ),
+                    // (| a |)
+                    Type::app(Type::mk_variant_ctor(common_kinds), Type::Var(Kind::Row, 0)),
+                ),
+                evidence: Rc::from("Eq Variant"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Eq Variant"),
+                // \eqVariantCtor -> { eq = eqVariantCtor }
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(vec![(Expr::Int(0), Expr::Var(0))], None),
+                )),
+            },
+            /*
+            instance OrdVariantCtor a => Ord (| a |) where
+              compare = ordVariantCtor
+
+            The evidence for OrdVariantCtor is a function of type
+            `Variant a -> Variant a -> (| Equal : (), Greater : (), Less : () |)`.
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Row)],
+                assumes: vec![Type::app(Type::OrdVariantCtor, Type::Var(Kind::Row, 0))],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Ord"),
+                    ),
+                    // (| a |)
+                    Type::app(Type::mk_variant_ctor(common_kinds), Type::Var(Kind::Row, 0)),
+                ),
+                evidence: Rc::from("Ord Variant"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Ord Variant"),
+                // \ordVariantCtor -> ...
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![
+                            (
+                                // eqDict
+                                Expr::Int(0),
+                                /*
+                                {
+                                  eq = \a b ->
+                                    case ordVariantCtor a b of
+                                      Equal _ -> true
+                                      _ -> false
+                                }
+                                */
+                                Expr::mk_record(
+                                    vec![(
+                                        Expr::Int(0),
+                                        Expr::mk_lam(
+                                            true,
+                                            Expr::mk_lam(
+                                                true,
+                                                Expr::mk_case(
+                                                    Expr::mk_app(
+                                                        Expr::mk_app(Expr::Var(2), Expr::Var(1)),
+                                                        Expr::Var(0),
+                                                    ),
+                                                    vec![
+                                                        Branch {
+                                                            pattern: Pattern::mk_variant(
+                                                                Expr::Int(0),
+                                                                Pattern::Wildcard,
+                                                            ),
+                                                            body: Expr::True,
+                                                        },
+                                                        Branch {
+                                                            pattern: Pattern::Wildcard,
+                                                            body: Expr::False,
+                                                        },
+                                                    ],
+                                                ),
+                                            ),
+                                        ),
+                                    )],
+                                    None,
+                                ),
+                            ),
+                            (
+                                // compare
+                                Expr::Int(1),
+                                Expr::Var(0),
+                            ),
+                        ],
+                        None,
+                    ),
+                )),
+            },
             {
                 let a = Type::Var(Kind::Type, 0);
                 Declaration::Definition {
@@ -644,6 +1059,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                                             // Less () : (| Equal : (), Greater : (), Less : () |)
                                             pattern: Pattern::Variant {
                                                 tag: Rc::new(Expr::Int(2)),
+                                                arg: Rc::new(Pattern::Wildcard),
                                             },
                                             body: Expr::True,
                                         },
@@ -709,6 +1125,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                                             // Greater () : (| Equal : (), Greater : (), Less : () |)
                                             pattern: Pattern::Variant {
                                                 tag: Rc::new(Expr::Int(1)),
+                                                arg: Rc::new(Pattern::Wildcard),
                                             },
                                             body: Expr::False,
                                         },
@@ -774,6 +1191,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                                             // Greater () : (| Equal : (), Greater : (), Less : () |)
                                             pattern: Pattern::Variant {
                                                 tag: Rc::new(Expr::Int(1)),
+                                                arg: Rc::new(Pattern::Wildcard),
                                             },
                                             body: Expr::True,
                                         },
@@ -839,6 +1257,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                                             // Less () : (| Equal : (), Greater : (), Less : () |)
                                             pattern: Pattern::Variant {
                                                 tag: Rc::new(Expr::Int(2)),
+                                                arg: Rc::new(Pattern::Wildcard),
                                             },
                                             body: Expr::False,
                                         },
@@ -871,6 +1290,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                             Type::app(array_ty.clone(), Type::String),
                         ),
                     ),
+                    default: None,
                 }],
             }),
             /*
@@ -913,7 +1333,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 Declaration::Instance {
                     ty_vars: vec![(Rc::from("a"), Kind::Type)],
                     assumes: vec![Type::app(to_args_ty.clone(), Type::Var(Kind::Type, 0))],
-                    head: Type::app(to_args_ty, Type::app(array_ty, Type::Var(Kind::Type, 0))),
+                    head: Type::app(to_args_ty, Type::app(array_ty.clone(), Type::Var(Kind::Type, 0))),
                     evidence: Rc::from("ToArgs a => ToArgs (Array a)"),
                 }
             },
@@ -954,6 +1374,7 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                         vec![],
                         Type::arrow(common_kinds, Type::Var(Kind::Type, 0), Type::String),
                     ),
+                    default: None,
                 }],
             }),
             /*
@@ -1017,8 +1438,8 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 )),
             },
             /*
-            instance Debug Char where
-              debug = char.toString
+            instance Debug Float where
+              debug = float.toString
             */
             Declaration::Instance {
                 ty_vars: vec![],
@@ -1028,20 +1449,20 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                         Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
                         Rc::from("Debug"),
                     ),
-                    Type::Char,
+                    Type::Float,
                 ),
-                evidence: Rc::from("Debug Char"),
+                evidence: Rc::from("Debug Float"),
             },
             Declaration::Evidence {
-                name: Rc::from("Debug Char"),
+                name: Rc::from("Debug Float"),
                 body: Rc::new(Expr::mk_record(
                     vec![(
                         // debug
                         Expr::Int(0),
-                        // char.toString
+                        // float.toString
                         Expr::Module {
                             id: ModuleRef::This,
-                            path: vec![String::from("char")],
+                            path: vec![String::from("float")],
                             item: Name::definition("toString"),
                         },
                     )],
@@ -1049,7 +1470,39 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                 )),
             },
             /*
-            instance Debug String where
+            instance Debug Char where
+              debug = char.toString
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(
+                    Type::Name(
+                        Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                        Rc::from("Debug"),
+                    ),
+                    Type::Char,
+                ),
+                evidence: Rc::from("Debug Char"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Debug Char"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // debug
+                        Expr::Int(0),
+                        // char.toString
+                        Expr::Module {
+                            id: ModuleRef::This,
+                            path: vec![String::from("char")],
+                            item: Name::definition("toString"),
+                        },
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance Debug String where
               debug = <debugString>
             */
             Declaration::Instance {
@@ -1437,6 +1890,1081 @@ pub fn builtins(common_kinds: &CommonKinds) -> Module {
                     None,
                 ))),
             },
-        ],
-    }
+            /*
+            instance (Debug k, Debug v) => Debug (Map k v) where
+              debug = debugMap debug debug
+            */
+            {
+                let debug = Type::Name(
+                    Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                    Rc::from("Debug"),
+                );
+                let k = Type::Var(Kind::Type, 1);
+                let v = Type::Var(Kind::Type, 0);
+                Declaration::Instance {
+                    ty_vars: vec![(Rc::from("k"), Kind::Type), (Rc::from("v"), Kind::Type)],
+                    assumes: vec![
+                        // Debug k
+                        Type::app(debug.clone(), k.clone()),
+                        // Debug v
+                        Type::app(debug.clone(), v.clone()),
+                    ],
+                    // Debug (Map k v)
+                    head: Type::app(
+                        debug,
+                        Type::app(
+                            Type::app(
+                                Type::Name(
+                                    common_kinds.type_to_type_to_type.clone(),
+                                    Rc::from("Map"),
+                                ),
+                                k,
+                            ),
+                            v,
+                        ),
+                    ),
+                    evidence: Rc::from("Debug Map"),
+                }
+            },
+            Declaration::Evidence {
+                name: Rc::from("Debug Map"),
+                // \debugKeyDict debugValueDict -> { debug = ... }
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_lam(
+                        true,
+                        Expr::mk_record(
+                            vec![(
+                                // debug
+                                Expr::Int(0),
+                                // debugMap (debug debugKeyDict) (debug debugValueDict)
+                                Expr::mk_app(
+                                    Expr::mk_app(
+                                        Expr::Builtin(Builtin::DebugMap),
+                                        Expr::mk_app(
+                                            Expr::Name(Name::definition("debug")),
+                                            Expr::Var(1),
+                                        ),
+                                    ),
+                                    Expr::mk_app(
+                                        Expr::Name(Name::definition("debug")),
+                                        Expr::Var(0),
+                                    ),
+                                ),
+                            )],
+                            None,
+                        ),
+                    ),
+                )),
+            },
+            /*
+            instance Debug a => Debug (Set a) where
+              debug = debugSet debug
+            */
+            {
+                let debug = Type::Name(
+                    Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                    Rc::from("Debug"),
+                );
+                let a = Type::Var(Kind::Type, 0);
+                Declaration::Instance {
+                    ty_vars: vec![(Rc::from("a"), Kind::Type)],
+                    assumes: vec![
+                        // Debug a
+                        Type::app(debug.clone(), a.clone()),
+                    ],
+                    // Debug (Set a)
+                    head: Type::app(
+                        debug,
+                        Type::app(
+                            Type::Name(common_kinds.type_to_type.clone(), Rc::from("Set")),
+                            a,
+                        ),
+                    ),
+                    evidence: Rc::from("Debug Set"),
+                }
+            },
+            Declaration::Evidence {
+                name: Rc::from("Debug Set"),
+                // \debugDict -> { debug = debugSet (debug debugDict) }
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![(
+                            // debug
+                            Expr::Int(0),
+                            Expr::mk_app(
+                                Expr::Builtin(Builtin::DebugSet),
+                                Expr::mk_app(Expr::Name(Name::definition("debug")), Expr::Var(0)),
+                            ),
+                        )],
+                        None,
+                    ),
+                )),
+            },
+            /*
+            class Functor f where
+              fmap : (a -> b) -> f a -> f b
+            */
+            Declaration::Class(ClassDeclaration {
+                supers: vec![],
+                name: Rc::from("Functor"),
+                args: vec![(Rc::from("f"), common_kinds.type_to_type.clone())],
+                members: vec![ClassMember {
+                    name: String::from("fmap"),
+                    sig: TypeSig::new(
+                        vec![(Rc::from("a"), Kind::Type), (Rc::from("b"), Kind::Type)],
+                        Type::arrow(
+                            common_kinds,
+                            Type::arrow(
+                                common_kinds,
+                                Type::Var(Kind::Type, 1),
+                                Type::Var(Kind::Type, 0),
+                            ),
+                            Type::arrow(
+                                common_kinds,
+                                Type::app(
+                                    Type::Var(common_kinds.type_to_type.clone(), 2),
+                                    Type::Var(Kind::Type, 1),
+                                ),
+                                Type::app(
+                                    Type::Var(common_kinds.type_to_type.clone(), 2),
+                                    Type::Var(Kind::Type, 0),
+                                ),
+                            ),
+                        ),
+                    ),
+                    default: None,
+                }],
+            }),
+            /*
+            class Functor f => Applicative f where
+              pure : a -> f a
+              apply : f (a -> b) -> f a -> f b
+            */
+            Declaration::Class(ClassDeclaration {
+                supers: vec![Type::app(
+                    functor_ty.clone(),
+                    Type::Var(common_kinds.type_to_type.clone(), 0),
+                )],
+                name: Rc::from("Applicative"),
+                args: vec![(Rc::from("f"), common_kinds.type_to_type.clone())],
+                members: vec![
+                    ClassMember {
+                        name: String::from("pure"),
+                        sig: TypeSig::new(
+                            vec![(Rc::from("a"), Kind::Type)],
+                            Type::arrow(
+                                common_kinds,
+                                Type::Var(Kind::Type, 0),
+                                Type::app(
+                                    Type::Var(common_kinds.type_to_type.clone(), 1),
+                                    Type::Var(Kind::Type, 0),
+                                ),
+                            ),
+                        ),
+                        default: None,
+                    },
+                    ClassMember {
+                        name: String::from("apply"),
+                        sig: TypeSig::new(
+                            vec![(Rc::from("a"), Kind::Type), (Rc::from("b"), Kind::Type)],
+                            Type::arrow(
+                                common_kinds,
+                                Type::app(
+                                    Type::Var(common_kinds.type_to_type.clone(), 2),
+                                    Type::arrow(
+                                        common_kinds,
+                                        Type::Var(Kind::Type, 1),
+                                        Type::Var(Kind::Type, 0),
+                                    ),
+                                ),
+                                Type::arrow(
+                                    common_kinds,
+                                    Type::app(
+                                        Type::Var(common_kinds.type_to_type.clone(), 2),
+                                        Type::Var(Kind::Type, 1),
+                                    ),
+                                    Type::app(
+                                        Type::Var(common_kinds.type_to_type.clone(), 2),
+                                        Type::Var(Kind::Type, 0),
+                                    ),
+                                ),
+                            ),
+                        ),
+                        default: None,
+                    },
+                ],
+            }),
+            /*
+            class Applicative m => Monad m where
+              andThen : m a -> (a -> m b) -> m b
+
+            Computation expressions (`comp`) are desugared into `andThen`, through `comp.bind`.
+            */
+            Declaration::Class(ClassDeclaration {
+                supers: vec![Type::app(
+                    applicative_ty.clone(),
+                    Type::Var(common_kinds.type_to_type.clone(), 0),
+                )],
+                name: Rc::from("Monad"),
+                args: vec![(Rc::from("m"), common_kinds.type_to_type.clone())],
+                members: vec![ClassMember {
+                    name: String::from("andThen"),
+                    sig: TypeSig::new(
+                        vec![(Rc::from("a"), Kind::Type), (Rc::from("b"), Kind::Type)],
+                        Type::arrow(
+                            common_kinds,
+                            Type::app(
+                                Type::Var(common_kinds.type_to_type.clone(), 2),
+                                Type::Var(Kind::Type, 1),
+                            ),
+                            Type::arrow(
+                                common_kinds,
+                                Type::arrow(
+                                    common_kinds,
+                                    Type::Var(Kind::Type, 1),
+                                    Type::app(
+                                        Type::Var(common_kinds.type_to_type.clone(), 2),
+                                        Type::Var(Kind::Type, 0),
+                                    ),
+                                ),
+                                Type::app(
+                                    Type::Var(common_kinds.type_to_type.clone(), 2),
+                                    Type::Var(Kind::Type, 0),
+                                ),
+                            ),
+                        ),
+                    ),
+                    default: None,
+                }],
+            }),
+            /*
+            comp.bind : Monad m => (a -> m b) -> m a -> m b
+            comp.bind f m = andThen m f
+            */
+            Declaration::Definition {
+                name: String::from(desugar::COMP_BIND),
+                sig: {
+                    let a = Type::Var(Kind::Type, 2);
+                    let m = Type::Var(common_kinds.type_to_type.clone(), 1);
+                    let b = Type::Var(Kind::Type, 0);
+                    TypeSig::new(
+                        vec![
+                            (Rc::from("a"), Kind::Type),
+                            (Rc::from("m"), common_kinds.type_to_type.clone()),
+                            (Rc::from("b"), Kind::Type),
+                        ],
+                        Type::mk_fatarrow(
+                            common_kinds,
+                            Type::app(monad_ty.clone(), m.clone()),
+                            Type::arrow(
+                                common_kinds,
+                                Type::arrow(
+                                    common_kinds,
+                                    a.clone(),
+                                    Type::app(m.clone(), b.clone()),
+                                ),
+                                Type::arrow(
+                                    common_kinds,
+                                    Type::app(m.clone(), a),
+                                    Type::app(m, b),
+                                ),
+                            ),
+                        ),
+                    )
+                },
+                // \dict f m -> dict.andThen m f
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_lam(
+                        true,
+                        Expr::mk_lam(
+                            true,
+                            Expr::mk_app(
+                                Expr::mk_app(
+                                    Expr::mk_project(Expr::Var(2), Expr::Int(1)),
+                                    Expr::Var(0),
+                                ),
+                                Expr::Var(1),
+                            ),
+                        ),
+                    ),
+                )),
+            },
+            /*
+            instance Functor IO where
+              fmap = io.map
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(functor_ty.clone(), io_ty.clone()),
+                evidence: Rc::from("Functor IO"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Functor IO"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fmap
+                        Expr::Int(0),
+                        Expr::Builtin(Builtin::MapIO),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance Applicative IO where
+              pure = io.pure
+              apply fs xs = io.andThen fs (\f -> io.map f xs)
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(applicative_ty.clone(), io_ty.clone()),
+                evidence: Rc::from("Applicative IO"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Applicative IO"),
+                body: Rc::new(Expr::mk_record(
+                    vec![
+                        // dict : Functor IO
+                        (
+                            Expr::Int(0),
+                            Expr::Name(Name::evidence("Functor IO")),
+                        ),
+                        // pure
+                        (Expr::Int(1), Expr::Builtin(Builtin::Pure)),
+                        // apply
+                        (
+                            Expr::Int(2),
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_lam(
+                                    true,
+                                    Expr::mk_app(
+                                        Expr::mk_app(Expr::Builtin(Builtin::BindIO), Expr::Var(1)),
+                                        Expr::mk_lam(
+                                            true,
+                                            Expr::mk_app(
+                                                Expr::mk_app(
+                                                    Expr::Builtin(Builtin::MapIO),
+                                                    Expr::Var(0),
+                                                ),
+                                                Expr::Var(1),
+                                            ),
+                                        ),
+                                    ),
+                                ),
+                            ),
+                        ),
+                    ],
+                    None,
+                )),
+            },
+            /*
+            instance Monad IO where
+              andThen = io.andThen
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(monad_ty.clone(), io_ty),
+                evidence: Rc::from("Monad IO"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Monad IO"),
+                body: Rc::new(Expr::mk_record(
+                    vec![
+                        // dict : Applicative IO
+                        (
+                            Expr::Int(0),
+                            Expr::Name(Name::evidence("Applicative IO")),
+                        ),
+                        // andThen
+                        (Expr::Int(1), Expr::Builtin(Builtin::BindIO)),
+                    ],
+                    None,
+                )),
+            },
+            /*
+            instance Functor Array where
+              fmap = array.map
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(functor_ty.clone(), array_ty.clone()),
+                evidence: Rc::from("Functor Array"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Functor Array"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fmap
+                        Expr::Int(0),
+                        Expr::Builtin(Builtin::MapArray),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance Applicative Array where
+              pure x = [x]
+              apply fs xs = array.flatMap (\f -> array.map f xs) fs
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(applicative_ty.clone(), array_ty.clone()),
+                evidence: Rc::from("Applicative Array"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Applicative Array"),
+                body: Rc::new(Expr::mk_record(
+                    vec![
+                        // dict : Functor Array
+                        (
+                            Expr::Int(0),
+                            Expr::Name(Name::evidence("Functor Array")),
+                        ),
+                        // pure
+                        (
+                            Expr::Int(1),
+                            Expr::mk_lam(true, Expr::Array(vec![Expr::Var(0)])),
+                        ),
+                        // apply
+                        (
+                            Expr::Int(2),
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_lam(
+                                    true,
+                                    Expr::mk_app(
+                                        Expr::mk_app(
+                                            Expr::Builtin(Builtin::FlatMap),
+                                            Expr::mk_lam(
+                                                true,
+                                                Expr::mk_app(
+                                                    Expr::mk_app(
+                                                        Expr::Builtin(Builtin::MapArray),
+                                                        Expr::Var(0),
+                                                    ),
+                                                    Expr::Var(1),
+                                                ),
+                                            ),
+                                        ),
+                                        Expr::Var(1),
+                                    ),
+                                ),
+                            ),
+                        ),
+                    ],
+                    None,
+                )),
+            },
+            /*
+            instance Monad Array where
+              andThen xs f = array.flatMap f xs
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(monad_ty, array_ty.clone()),
+                evidence: Rc::from("Monad Array"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("Monad Array"),
+                body: Rc::new(Expr::mk_record(
+                    vec![
+                        // dict : Applicative Array
+                        (
+                            Expr::Int(0),
+                            Expr::Name(Name::evidence("Applicative Array")),
+                        ),
+                        // andThen
+                        (
+                            Expr::Int(1),
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_lam(
+                                    true,
+                                    Expr::mk_app(
+                                        Expr::mk_app(Expr::Builtin(Builtin::FlatMap), Expr::Var(0)),
+                                        Expr::Var(1),
+                                    ),
+                                ),
+                            ),
+                        ),
+                    ],
+                    None,
+                )),
+            },
+            /*
+            class ToJson a where
+              toJson : a -> Json
+            */
+            Declaration::Class(ClassDeclaration {
+                supers: vec![],
+                name: Rc::from("ToJson"),
+                args: vec![(Rc::from("a"), Kind::Type)],
+                members: vec![ClassMember {
+                    name: String::from("toJson"),
+                    sig: TypeSig::new(
+                        vec![],
+                        Type::arrow(common_kinds, Type::Var(Kind::Type, 0), json_ty.clone()),
+                    ),
+                    default: None,
+                }],
+            }),
+            /*
+            class FromJson a where
+              fromJson : Json -> (| Err : String, Ok : a |)
+            */
+            Declaration::Class(ClassDeclaration {
+                supers: vec![],
+                name: Rc::from("FromJson"),
+                args: vec![(Rc::from("a"), Kind::Type)],
+                members: vec![ClassMember {
+                    name: String::from("fromJson"),
+                    sig: TypeSig::new(
+                        vec![],
+                        Type::arrow(
+                            common_kinds,
+                            json_ty.clone(),
+                            Type::mk_variant(
+                                common_kinds,
+                                vec![
+                                    (Rc::from("Err"), Type::String),
+                                    (Rc::from("Ok"), Type::Var(Kind::Type, 0)),
+                                ],
+                                None,
+                            ),
+                        ),
+                    ),
+                    default: None,
+                }],
+            }),
+            /*
+            instance ToJson Json where
+              toJson x = x
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(to_json.clone(), json_ty.clone()),
+                evidence: Rc::from("ToJson Json"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson Json"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // toJson
+                        Expr::Int(0),
+                        // \x -> x
+                        Expr::mk_lam(true, Expr::Var(0)),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance FromJson Json where
+              fromJson = Ok
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(from_json.clone(), json_ty.clone()),
+                evidence: Rc::from("FromJson Json"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson Json"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fromJson
+                        Expr::Int(0),
+                        // Ok
+                        Expr::mk_variant(Expr::Int(1)),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance ToJson Bool where
+              toJson = json.bool
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(to_json.clone(), Type::Bool),
+                evidence: Rc::from("ToJson Bool"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson Bool"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // toJson
+                        Expr::Int(0),
+                        // json.bool
+                        Expr::Module {
+                            id: ModuleRef::This,
+                            path: vec![String::from("json")],
+                            item: Name::definition("bool"),
+                        },
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance FromJson Bool where
+              fromJson x =
+                case json.view x of
+                  Bool b -> Ok b
+                  _ -> Err "expected a boolean"
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(from_json.clone(), Type::Bool),
+                evidence: Rc::from("FromJson Bool"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson Bool"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fromJson
+                        Expr::Int(0),
+                        decode_json_ctor(JSON_BOOL, "expected a boolean"),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance ToJson Int where
+              toJson = json.int
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(to_json.clone(), Type::Int),
+                evidence: Rc::from("ToJson Int"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson Int"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // toJson
+                        Expr::Int(0),
+                        // json.int
+                        Expr::Module {
+                            id: ModuleRef::This,
+                            path: vec![String::from("json")],
+                            item: Name::definition("int"),
+                        },
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance FromJson Int where
+              fromJson x =
+                case json.view x of
+                  Int n -> Ok n
+                  _ -> Err "expected an integer"
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(from_json.clone(), Type::Int),
+                evidence: Rc::from("FromJson Int"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson Int"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fromJson
+                        Expr::Int(0),
+                        decode_json_ctor(JSON_INT, "expected an integer"),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance ToJson Float where
+              toJson = json.float
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(to_json.clone(), Type::Float),
+                evidence: Rc::from("ToJson Float"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson Float"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // toJson
+                        Expr::Int(0),
+                        // json.float
+                        Expr::Module {
+                            id: ModuleRef::This,
+                            path: vec![String::from("json")],
+                            item: Name::definition("float"),
+                        },
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance FromJson Float where
+              fromJson x =
+                case json.view x of
+                  Int n -> Ok (float.fromInt n)
+                  Float f -> Ok f
+                  _ -> Err "expected a number"
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(from_json.clone(), Type::Float),
+                evidence: Rc::from("FromJson Float"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson Float"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fromJson
+                        Expr::Int(0),
+                        Expr::mk_lam(
+                            true,
+                            Expr::mk_case(
+                                Expr::Var(0),
+                                vec![
+                                    /*
+                                    `Int` is matched first because its tag is greater than `Float`'s,
+                                    so matching it doesn't change `Float`'s tag.
+
+                                    See [note: peeling constructors when matching on variants].
+                                    */
+                                    Branch {
+                                        pattern: Pattern::mk_variant(
+                                            Expr::Int(JSON_INT),
+                                            Pattern::Name,
+                                        ),
+                                        // Ok (float.fromInt n)
+                                        body: Expr::mk_app(
+                                            Expr::mk_variant(Expr::Int(1)),
+                                            Expr::mk_app(
+                                                Expr::Module {
+                                                    id: ModuleRef::This,
+                                                    path: vec![String::from("float")],
+                                                    item: Name::definition("fromInt"),
+                                                },
+                                                Expr::Var(0),
+                                            ),
+                                        ),
+                                    },
+                                    Branch {
+                                        pattern: Pattern::mk_variant(
+                                            Expr::Int(JSON_FLOAT),
+                                            Pattern::Name,
+                                        ),
+                                        // Ok f
+                                        body: Expr::mk_app(
+                                            Expr::mk_variant(Expr::Int(1)),
+                                            Expr::Var(0),
+                                        ),
+                                    },
+                                    Branch {
+                                        pattern: Pattern::Wildcard,
+                                        // Err "expected a number"
+                                        body: Expr::mk_app(
+                                            Expr::mk_variant(Expr::Int(0)),
+                                            Expr::String(vec![StringPart::from("expected a number")]),
+                                        ),
+                                    },
+                                ],
+                            ),
+                        ),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance ToJson String where
+              toJson = json.string
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(to_json.clone(), Type::String),
+                evidence: Rc::from("ToJson String"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson String"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // toJson
+                        Expr::Int(0),
+                        // json.string
+                        Expr::Module {
+                            id: ModuleRef::This,
+                            path: vec![String::from("json")],
+                            item: Name::definition("string"),
+                        },
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance FromJson String where
+              fromJson x =
+                case json.view x of
+                  String s -> Ok s
+                  _ -> Err "expected a string"
+            */
+            Declaration::Instance {
+                ty_vars: vec![],
+                assumes: vec![],
+                head: Type::app(from_json.clone(), Type::String),
+                evidence: Rc::from("FromJson String"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson String"),
+                body: Rc::new(Expr::mk_record(
+                    vec![(
+                        // fromJson
+                        Expr::Int(0),
+                        decode_json_ctor(JSON_STRING, "expected a string"),
+                    )],
+                    None,
+                )),
+            },
+            /*
+            instance ToJson a => ToJson (Array a) where
+              toJson x = json.array (array.map toJson x)
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Type)],
+                assumes: vec![Type::app(to_json.clone(), Type::Var(Kind::Type, 0))],
+                head: Type::app(
+                    to_json.clone(),
+                    Type::app(array_ty.clone(), Type::Var(Kind::Type, 0)),
+                ),
+                evidence: Rc::from("ToJson Array"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson Array"),
+                // \toJsonDict -> { toJson = ... }
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![(
+                            // toJson
+                            Expr::Int(0),
+                            // \x -> json.array (array.map (toJson toJsonDict) x)
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_app(
+                                    Expr::Module {
+                                        id: ModuleRef::This,
+                                        path: vec![String::from("json")],
+                                        item: Name::definition("array"),
+                                    },
+                                    Expr::mk_app(
+                                        Expr::mk_app(
+                                            Expr::Module {
+                                                id: ModuleRef::This,
+                                                path: vec![String::from("array")],
+                                                item: Name::definition("map"),
+                                            },
+                                            Expr::mk_app(
+                                                Expr::Name(Name::definition("toJson")),
+                                                Expr::Var(1),
+                                            ),
+                                        ),
+                                        Expr::Var(0),
+                                    ),
+                                ),
+                            ),
+                        )],
+                        None,
+                    ),
+                )),
+            },
+            /*
+            instance FromJson a => FromJson (Array a) where
+              fromJson = decodeJsonArray fromJson
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Type)],
+                assumes: vec![Type::app(from_json.clone(), Type::Var(Kind::Type, 0))],
+                head: Type::app(
+                    from_json.clone(),
+                    Type::app(array_ty, Type::Var(Kind::Type, 0)),
+                ),
+                evidence: Rc::from("FromJson Array"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson Array"),
+                // \fromJsonDict -> { fromJson = decodeJsonArray (fromJson fromJsonDict) }
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![(
+                            // fromJson
+                            Expr::Int(0),
+                            Expr::mk_app(
+                                Expr::Builtin(Builtin::JsonDecodeArray),
+                                Expr::mk_app(Expr::Name(Name::definition("fromJson")), Expr::Var(0)),
+                            ),
+                        )],
+                        None,
+                    ),
+                )),
+            },
+            /*
+            instance ToJsonRecordFields a => ToJson { a } where
+              toJson record = json.object (toJsonRecordFields record)
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Row)],
+                assumes: vec![Type::app(Type::ToJsonRecordFields, Type::Var(Kind::Row, 0))],
+                head: Type::app(
+                    to_json,
+                    // { a }
+                    Type::app(Type::mk_record_ctor(common_kinds), Type::Var(Kind::Row, 0)),
+                ),
+                evidence: Rc::from("ToJson Record"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("ToJson Record"),
+                /*
+                The evidence for ToJsonRecordFields is a function
+                of type `Record a -> Array { key : String, value : Json }`
+
+                \toJsonRecordFields -> { toJson = \record -> json.object (toJsonRecordFields record) }
+                */
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![(
+                            // toJson
+                            Expr::Int(0),
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_app(
+                                    Expr::Module {
+                                        id: ModuleRef::This,
+                                        path: vec![String::from("json")],
+                                        item: Name::definition("object"),
+                                    },
+                                    Expr::mk_app(Expr::Var(1), Expr::Var(0)),
+                                ),
+                            ),
+                        )],
+                        None,
+                    ),
+                )),
+            },
+            /*
+            instance FromJsonRecordFields a => FromJson { a } where
+              fromJson x =
+                case json.view x of
+                  Object fields -> fromJsonRecordFields fields
+                  _ -> Err "expected an object"
+            */
+            Declaration::Instance {
+                ty_vars: vec![(Rc::from("a"), Kind::Row)],
+                assumes: vec![Type::app(Type::FromJsonRecordFields, Type::Var(Kind::Row, 0))],
+                head: Type::app(
+                    from_json,
+                    // { a }
+                    Type::app(Type::mk_record_ctor(common_kinds), Type::Var(Kind::Row, 0)),
+                ),
+                evidence: Rc::from("FromJson Record"),
+            },
+            Declaration::Evidence {
+                name: Rc::from("FromJson Record"),
+                /*
+                The evidence for FromJsonRecordFields is a function of type
+                `Array { key : String, value : Json } -> (| Err : String, Ok : Record a |)`
+
+                \fromJsonRecordFields -> { fromJson = ... }
+                */
+                body: Rc::new(Expr::mk_lam(
+                    true,
+                    Expr::mk_record(
+                        vec![(
+                            // fromJson
+                            Expr::Int(0),
+                            Expr::mk_lam(
+                                true,
+                                Expr::mk_case(
+                                    Expr::Var(0),
+                                    vec![
+                                        Branch {
+                                            pattern: Pattern::mk_variant(
+                                                Expr::Int(JSON_OBJECT),
+                                                Pattern::Name,
+                                            ),
+                                            // fromJsonRecordFields fields
+                                            body: Expr::mk_app(Expr::Var(2), Expr::Var(0)),
+                                        },
+                                        Branch {
+                                            pattern: Pattern::Wildcard,
+                                            // Err "expected an object"
+                                            body: Expr::mk_app(
+                                                Expr::mk_variant(Expr::Int(0)),
+                                                Expr::String(vec![StringPart::from("expected an object")]),
+                                            ),
+                                        },
+                                    ],
+                                ),
+                            ),
+                        )],
+                        None,
+                    ),
+                )),
+            },
+        ],
+        exports: None,
+    }
+}
+
+// Tags of `Json`'s constructors, which are ordered like the constructors of `json.view`'s result.
+const JSON_BOOL: i64 = 1;
+const JSON_FLOAT: i64 = 2;
+const JSON_INT: i64 = 3;
+const JSON_OBJECT: i64 = 5;
+const JSON_STRING: i64 = 6;
+
+/*
+\x ->
+  case json.view x of
+    <ctor> value -> Ok value
+    _ -> Err <error>
+*/
+fn decode_json_ctor(tag: i64, error: &str) -> Expr {
+    Expr::mk_lam(
+        true,
+        Expr::mk_case(
+            Expr::Var(0),
+            vec![
+                Branch {
+                    pattern: Pattern::mk_variant(Expr::Int(tag), Pattern::Name),
+                    // Ok value
+                    body: Expr::mk_app(Expr::mk_variant(Expr::Int(1)), Expr::Var(0)),
+                },
+                Branch {
+                    pattern: Pattern::Wildcard,
+                    // Err <error>
+                    body: Expr::mk_app(
+                        Expr::mk_variant(Expr::Int(0)),
+                        Expr::String(vec![StringPart::from(error)]),
+                    ),
+                },
+            ],
+        ),
+    )
 }
@@ -0,0 +1,210 @@
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Name, Type, TypeSig};
+use ipso_syntax::kind::Kind;
+use std::rc::Rc;
+
+// Map k v
+fn map_type(common_kinds: &CommonKinds, k: Type, v: Type) -> Type {
+    Type::app(
+        Type::app(
+            Type::Name(common_kinds.type_to_type_to_type.clone(), Rc::from("Map")),
+            k,
+        ),
+        v,
+    )
+}
+
+// Ord k
+fn ord(k: Type) -> Type {
+    Type::app(
+        Type::Name(
+            Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+            Rc::from("Ord"),
+        ),
+        k,
+    )
+}
+
+// \ordDict -> builtin (compare ordDict)
+fn with_compare(builtin: Builtin) -> Rc<Expr> {
+    Rc::new(Expr::mk_lam(
+        true,
+        Expr::mk_app(
+            Expr::Builtin(builtin),
+            Expr::mk_app(Expr::Name(Name::definition("compare")), Expr::Var(0)),
+        ),
+    ))
+}
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    let k = Type::unsafe_mk_var(1, Kind::Type);
+    let v = Type::unsafe_mk_var(0, Kind::Type);
+    let ty_vars = vec![(Rc::from("k"), k.kind()), (Rc::from("v"), v.kind())];
+    let entry_type = Type::mk_record(
+        common_kinds,
+        vec![(Rc::from("key"), k.clone()), (Rc::from("value"), v.clone())],
+        None,
+    );
+
+    vec![
+        // empty : Map k v
+        Rc::new(Declaration::Definition {
+            name: String::from("empty"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: map_type(common_kinds, k.clone(), v.clone()),
+            },
+            body: Expr::alloc_builtin(Builtin::MapEmpty),
+        }),
+        // size : Map k v -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("size"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::arrow(
+                    common_kinds,
+                    map_type(common_kinds, k.clone(), v.clone()),
+                    Type::Int,
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::MapSize),
+        }),
+        // insert : Ord k => k -> v -> Map k v -> Map k v
+        Rc::new(Declaration::Definition {
+            name: String::from("insert"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(k.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        k.clone(),
+                        Type::arrow(
+                            common_kinds,
+                            v.clone(),
+                            Type::arrow(
+                                common_kinds,
+                                map_type(common_kinds, k.clone(), v.clone()),
+                                map_type(common_kinds, k.clone(), v.clone()),
+                            ),
+                        ),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::MapInsert),
+        }),
+        // lookup : Ord k => k -> Map k v -> (| None : (), Some : v |)
+        Rc::new(Declaration::Definition {
+            name: String::from("lookup"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(k.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        k.clone(),
+                        Type::arrow(
+                            common_kinds,
+                            map_type(common_kinds, k.clone(), v.clone()),
+                            Type::mk_variant(
+                                common_kinds,
+                                vec![
+                                    (Rc::from("None"), Type::Unit),
+                                    (Rc::from("Some"), v.clone()),
+                                ],
+                                None,
+                            ),
+                        ),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::MapLookup),
+        }),
+        // delete : Ord k => k -> Map k v -> Map k v
+        Rc::new(Declaration::Definition {
+            name: String::from("delete"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(k.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        k.clone(),
+                        Type::arrow(
+                            common_kinds,
+                            map_type(common_kinds, k.clone(), v.clone()),
+                            map_type(common_kinds, k.clone(), v.clone()),
+                        ),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::MapDelete),
+        }),
+        // toArray : Map k v -> Array { key : k, value : v }
+        Rc::new(Declaration::Definition {
+            name: String::from("toArray"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::arrow(
+                    common_kinds,
+                    map_type(common_kinds, k.clone(), v.clone()),
+                    Type::app(Type::mk_array(common_kinds), entry_type.clone()),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::MapToArray),
+        }),
+        // fromArray : Ord k => Array { key : k, value : v } -> Map k v
+        Rc::new(Declaration::Definition {
+            name: String::from("fromArray"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(k.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), entry_type),
+                        map_type(common_kinds, k.clone(), v.clone()),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::MapFromArray),
+        }),
+        // fold : (a -> k -> v -> a) -> a -> Map k v -> a
+        Rc::new(Declaration::Definition {
+            name: String::from("fold"),
+            sig: {
+                let a = Type::unsafe_mk_var(2, Kind::Type);
+                let k = Type::unsafe_mk_var(1, Kind::Type);
+                let v = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![
+                        (Rc::from("a"), a.kind()),
+                        (Rc::from("k"), k.kind()),
+                        (Rc::from("v"), v.kind()),
+                    ],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(
+                            common_kinds,
+                            a.clone(),
+                            Type::arrow(
+                                common_kinds,
+                                k.clone(),
+                                Type::arrow(common_kinds, v.clone(), a.clone()),
+                            ),
+                        ),
+                        Type::arrow(
+                            common_kinds,
+                            a.clone(),
+                            Type::arrow(common_kinds, map_type(common_kinds, k, v), a),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::MapFold),
+        }),
+    ]
+}
@@ -18,5 +18,213 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::PathExists),
         }),
+        // isFile : String -> IO Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isFile"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(Type::mk_io(common_kinds), Type::Bool),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathIsFile),
+        }),
+        // isDir : String -> IO Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isDir"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(Type::mk_io(common_kinds), Type::Bool),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathIsDir),
+        }),
+        // join : String -> String -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("join"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(common_kinds, Type::String, Type::String),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathJoin),
+        }),
+        // parent : String -> (| None : (), Some : String |)
+        //
+        // `None` when the path is a root, or a relative path with a single component.
+        Rc::new(Declaration::Definition {
+            name: String::from("parent"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("None"), Type::Unit),
+                                (Rc::from("Some"), Type::String),
+                            ],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathParent),
+        }),
+        // fileName : String -> (| None : (), Some : String |)
+        Rc::new(Declaration::Definition {
+            name: String::from("fileName"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("None"), Type::Unit),
+                                (Rc::from("Some"), Type::String),
+                            ],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathFileName),
+        }),
+        // extension : String -> (| None : (), Some : String |)
+        Rc::new(Declaration::Definition {
+            name: String::from("extension"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("None"), Type::Unit),
+                                (Rc::from("Some"), Type::String),
+                            ],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathExtension),
+        }),
+        // withExtension : String -> String -> String
+        //
+        // `withExtension ext path` replaces `path`'s extension with `ext`, or adds it.
+        Rc::new(Declaration::Definition {
+            name: String::from("withExtension"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(common_kinds, Type::String, Type::String),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathWithExtension),
+        }),
+        // normalize : String -> String
+        //
+        // Lexically removes `.` components and resolves `..` components.
+        Rc::new(Declaration::Definition {
+            name: String::from("normalize"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(common_kinds, Type::String, Type::String),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathNormalize),
+        }),
+        // absolute : String -> IO String
+        //
+        // Resolves a path against the current directory, and normalizes it.
+        Rc::new(Declaration::Definition {
+            name: String::from("absolute"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(Type::mk_io(common_kinds), Type::String),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathAbsolute),
+        }),
+        // relativeTo : String -> String -> (| None : (), Some : String |)
+        //
+        // `relativeTo base path` is a relative path that leads from `base` to `path`.
+        Rc::new(Declaration::Definition {
+            name: String::from("relativeTo"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::mk_variant(
+                                common_kinds,
+                                vec![
+                                    (Rc::from("None"), Type::Unit),
+                                    (Rc::from("Some"), Type::String),
+                                ],
+                                None,
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathRelativeTo),
+        }),
+        // glob : String -> IO (Array String)
+        //
+        // The paths that match a pattern containing `*`, `?`, `[...]` or `**`, sorted.
+        Rc::new(Declaration::Definition {
+            name: String::from("glob"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::app(
+                            Type::mk_io(common_kinds),
+                            Type::app(Type::mk_array(common_kinds), Type::String),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::PathGlob),
+        }),
     ]
 }
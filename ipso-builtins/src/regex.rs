@@ -0,0 +1,185 @@
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Type, TypeSig};
+use ipso_syntax::kind::Kind;
+use std::rc::Rc;
+
+// Regex
+fn regex_type() -> Type {
+    Type::Name(Kind::Type, Rc::from("Regex"))
+}
+
+// (| None : (), Some : a |)
+fn option_type(common_kinds: &CommonKinds, a: Type) -> Type {
+    Type::mk_variant(
+        common_kinds,
+        vec![(Rc::from("None"), Type::Unit), (Rc::from("Some"), a)],
+        None,
+    )
+}
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    vec![
+        // compile : String -> (| Err : String, Ok : Regex |)
+        Rc::new(Declaration::Definition {
+            name: String::from("compile"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("Err"), Type::String),
+                                (Rc::from("Ok"), regex_type()),
+                            ],
+                            None,
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexCompile),
+        }),
+        // isMatch : Regex -> String -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("isMatch"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        regex_type(),
+                        Type::arrow(common_kinds, Type::String, Type::Bool),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexIsMatch),
+        }),
+        // find : Regex -> String -> (| None : (), Some : String |)
+        //
+        // The leftmost match.
+        Rc::new(Declaration::Definition {
+            name: String::from("find"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        regex_type(),
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            option_type(common_kinds, Type::String),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexFind),
+        }),
+        // findAll : Regex -> String -> Array String
+        //
+        // All the non-overlapping matches.
+        Rc::new(Declaration::Definition {
+            name: String::from("findAll"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        regex_type(),
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::app(Type::mk_array(common_kinds), Type::String),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexFindAll),
+        }),
+        // captures : Regex -> String -> (| None : (), Some : Array (| None : (), Some : String |) |)
+        //
+        // The capture groups of the leftmost match, by index. Group 0 is the whole match, and
+        // groups that didn't participate in the match are `None`.
+        Rc::new(Declaration::Definition {
+            name: String::from("captures"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        regex_type(),
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            option_type(
+                                common_kinds,
+                                Type::app(
+                                    Type::mk_array(common_kinds),
+                                    option_type(common_kinds, Type::String),
+                                ),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexCaptures),
+        }),
+        // namedCaptures : Regex -> String -> (| None : (), Some : Map String String |)
+        //
+        // The named capture groups of the leftmost match, by name. Groups that didn't participate
+        // in the match are omitted.
+        Rc::new(Declaration::Definition {
+            name: String::from("namedCaptures"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        regex_type(),
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            option_type(
+                                common_kinds,
+                                Type::app(
+                                    Type::app(
+                                        Type::Name(
+                                            common_kinds.type_to_type_to_type.clone(),
+                                            Rc::from("Map"),
+                                        ),
+                                        Type::String,
+                                    ),
+                                    Type::String,
+                                ),
+                            ),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexNamedCaptures),
+        }),
+        // replace : Regex -> String -> String -> String
+        //
+        // `replace regex replacement string` replaces every match in `string`. `$1` or `${name}` in
+        // the replacement is replaced by a capture group.
+        Rc::new(Declaration::Definition {
+            name: String::from("replace"),
+            sig: {
+                TypeSig {
+                    ty_vars: vec![],
+                    body: Type::arrow(
+                        common_kinds,
+                        regex_type(),
+                        Type::arrow(
+                            common_kinds,
+                            Type::String,
+                            Type::arrow(common_kinds, Type::String, Type::String),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::RegexReplace),
+        }),
+    ]
+}
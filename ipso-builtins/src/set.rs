@@ -0,0 +1,173 @@
+use ipso_core::{Builtin, CommonKinds, Declaration, Expr, Name, Type, TypeSig};
+use ipso_syntax::kind::Kind;
+use std::rc::Rc;
+
+// Set a
+fn set_type(common_kinds: &CommonKinds, a: Type) -> Type {
+    Type::app(
+        Type::Name(common_kinds.type_to_type.clone(), Rc::from("Set")),
+        a,
+    )
+}
+
+// Ord a
+fn ord(a: Type) -> Type {
+    Type::app(
+        Type::Name(
+            Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+            Rc::from("Ord"),
+        ),
+        a,
+    )
+}
+
+// \ordDict -> builtin (compare ordDict)
+fn with_compare(builtin: Builtin) -> Rc<Expr> {
+    Rc::new(Expr::mk_lam(
+        true,
+        Expr::mk_app(
+            Expr::Builtin(builtin),
+            Expr::mk_app(Expr::Name(Name::definition("compare")), Expr::Var(0)),
+        ),
+    ))
+}
+
+pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
+    let a = Type::unsafe_mk_var(0, Kind::Type);
+    let ty_vars = vec![(Rc::from("a"), a.kind())];
+
+    vec![
+        // empty : Set a
+        Rc::new(Declaration::Definition {
+            name: String::from("empty"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: set_type(common_kinds, a.clone()),
+            },
+            body: Expr::alloc_builtin(Builtin::SetEmpty),
+        }),
+        // size : Set a -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("size"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::arrow(common_kinds, set_type(common_kinds, a.clone()), Type::Int),
+            },
+            body: Expr::alloc_builtin(Builtin::SetSize),
+        }),
+        // insert : Ord a => a -> Set a -> Set a
+        Rc::new(Declaration::Definition {
+            name: String::from("insert"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(a.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        a.clone(),
+                        Type::arrow(
+                            common_kinds,
+                            set_type(common_kinds, a.clone()),
+                            set_type(common_kinds, a.clone()),
+                        ),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::SetInsert),
+        }),
+        // member : Ord a => a -> Set a -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("member"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(a.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        a.clone(),
+                        Type::arrow(common_kinds, set_type(common_kinds, a.clone()), Type::Bool),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::SetMember),
+        }),
+        // delete : Ord a => a -> Set a -> Set a
+        Rc::new(Declaration::Definition {
+            name: String::from("delete"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(a.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        a.clone(),
+                        Type::arrow(
+                            common_kinds,
+                            set_type(common_kinds, a.clone()),
+                            set_type(common_kinds, a.clone()),
+                        ),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::SetDelete),
+        }),
+        // toArray : Set a -> Array a
+        Rc::new(Declaration::Definition {
+            name: String::from("toArray"),
+            sig: TypeSig {
+                ty_vars: ty_vars.clone(),
+                body: Type::arrow(
+                    common_kinds,
+                    set_type(common_kinds, a.clone()),
+                    Type::app(Type::mk_array(common_kinds), a.clone()),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::SetToArray),
+        }),
+        // fromArray : Ord a => Array a -> Set a
+        Rc::new(Declaration::Definition {
+            name: String::from("fromArray"),
+            sig: TypeSig {
+                ty_vars,
+                body: Type::mk_fatarrow(
+                    common_kinds,
+                    ord(a.clone()),
+                    Type::arrow(
+                        common_kinds,
+                        Type::app(Type::mk_array(common_kinds), a.clone()),
+                        set_type(common_kinds, a),
+                    ),
+                ),
+            },
+            body: with_compare(Builtin::SetFromArray),
+        }),
+        // fold : (b -> a -> b) -> b -> Set a -> b
+        Rc::new(Declaration::Definition {
+            name: String::from("fold"),
+            sig: {
+                let b = Type::unsafe_mk_var(1, Kind::Type);
+                let a = Type::unsafe_mk_var(0, Kind::Type);
+                TypeSig {
+                    ty_vars: vec![(Rc::from("b"), b.kind()), (Rc::from("a"), a.kind())],
+                    body: Type::arrow(
+                        common_kinds,
+                        Type::arrow(
+                            common_kinds,
+                            b.clone(),
+                            Type::arrow(common_kinds, a.clone(), b.clone()),
+                        ),
+                        Type::arrow(
+                            common_kinds,
+                            b.clone(),
+                            Type::arrow(common_kinds, set_type(common_kinds, a), b),
+                        ),
+                    ),
+                }
+            },
+            body: Expr::alloc_builtin(Builtin::SetFold),
+        }),
+    ]
+}
@@ -97,5 +97,151 @@ pub fn decls(common_kinds: &CommonKinds) -> Vec<Rc<Declaration>> {
             },
             body: Expr::alloc_builtin(Builtin::FoldlString),
         }),
+        // length : String -> Int
+        Rc::new(Declaration::Definition {
+            name: String::from("length"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::String, Type::Int),
+            },
+            body: Expr::alloc_builtin(Builtin::StringLength),
+        }),
+        // slice : Int -> Int -> String -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("slice"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::Int,
+                    Type::arrow(
+                        common_kinds,
+                        Type::Int,
+                        Type::arrow(common_kinds, Type::String, Type::String),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringSlice),
+        }),
+        // indexOf : String -> String -> (| None : (), Some : Int |)
+        Rc::new(Declaration::Definition {
+            name: String::from("indexOf"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::mk_variant(
+                            common_kinds,
+                            vec![
+                                (Rc::from("None"), Type::Unit),
+                                (Rc::from("Some"), Type::Int),
+                            ],
+                            None,
+                        ),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringIndexOf),
+        }),
+        // startsWith : String -> String -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("startsWith"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::arrow(common_kinds, Type::String, Type::Bool),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringStartsWith),
+        }),
+        // endsWith : String -> String -> Bool
+        Rc::new(Declaration::Definition {
+            name: String::from("endsWith"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::arrow(common_kinds, Type::String, Type::Bool),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringEndsWith),
+        }),
+        // trim : String -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("trim"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::String, Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::StringTrim),
+        }),
+        // replace : String -> String -> String -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("replace"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::arrow(
+                        common_kinds,
+                        Type::String,
+                        Type::arrow(common_kinds, Type::String, Type::String),
+                    ),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringReplace),
+        }),
+        // toChars : String -> Array Char
+        Rc::new(Declaration::Definition {
+            name: String::from("toChars"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::String,
+                    Type::app(Type::mk_array(common_kinds), Type::Char),
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringToChars),
+        }),
+        // fromChars : Array Char -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("fromChars"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(
+                    common_kinds,
+                    Type::app(Type::mk_array(common_kinds), Type::Char),
+                    Type::String,
+                ),
+            },
+            body: Expr::alloc_builtin(Builtin::StringFromChars),
+        }),
+        // toUpper : String -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("toUpper"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::String, Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::StringToUpper),
+        }),
+        // toLower : String -> String
+        Rc::new(Declaration::Definition {
+            name: String::from("toLower"),
+            sig: TypeSig {
+                ty_vars: Vec::new(),
+                body: Type::arrow(common_kinds, Type::String, Type::String),
+            },
+            body: Expr::alloc_builtin(Builtin::StringToLower),
+        }),
     ]
 }
@@ -4,6 +4,7 @@ use ipso_cli::{
     version::VERSION,
 };
 use ipso_diagnostic::{Diagnostic, Location, Message, Source};
+use ipso_eval::{RuntimeError, RuntimeErrorInfo};
 use std::{io, path::PathBuf};
 
 fn report_interpreter_error(filename: String, err: InterpreterError) -> io::Result<()> {
@@ -31,6 +32,7 @@ fn report_interpreter_error(filename: String, err: InterpreterError) -> io::Resu
                 addendum: None,
             },
         ),
+        InterpreterError::RuntimeError(err) => err.report(&mut diagnostic),
     }
     diagnostic.report_all()
 }
@@ -45,6 +47,11 @@ struct Cli {
     #[clap(long = "run")]
     entrypoint: Option<String>,
 
+    /// Add a directory to search for imported modules. Searched before the directories in
+    /// IPSO_PATH.
+    #[clap(long = "include", value_name = "DIR")]
+    include: Vec<PathBuf>,
+
     /// Print the current version.
     #[clap(long = "version")]
     version: bool,
@@ -63,12 +70,23 @@ fn main() -> io::Result<()> {
             let config = ipso_cli::run::Config {
                 filename: filename.clone(),
                 entrypoint: cli.entrypoint,
+                search_path: {
+                    let mut search_path = cli.include;
+                    if let Some(ipso_path) = std::env::var_os("IPSO_PATH") {
+                        search_path.extend(std::env::split_paths(&ipso_path));
+                    }
+                    search_path
+                },
                 stdin: None,
                 stdout: None,
             };
 
             match run_interpreter(config) {
                 Ok(()) => Ok(()),
+                Err(InterpreterError::RuntimeError(RuntimeError {
+                    info: RuntimeErrorInfo::Exit { code },
+                    ..
+                })) => std::process::exit(code),
                 Err(err) => {
                     report_interpreter_error(filename, err)?;
                     std::process::exit(1)
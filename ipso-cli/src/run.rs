@@ -16,6 +16,8 @@ use std::{
 pub struct Config {
     pub filename: String,
     pub entrypoint: Option<String>,
+    /// Directories to search for library modules, in order.
+    pub search_path: Vec<PathBuf>,
     pub stdin: Option<Box<dyn BufRead>>,
     pub stdout: Option<Box<dyn Write>>,
 }
@@ -27,6 +29,7 @@ pub enum InterpreterError {
     ImportError(import::Error),
     MissingEntrypoint(String),
     FileDoesNotExist(PathBuf),
+    RuntimeError(eval::RuntimeError),
 }
 
 impl From<parse::Error> for InterpreterError {
@@ -53,6 +56,12 @@ impl From<import::Error> for InterpreterError {
     }
 }
 
+impl From<eval::RuntimeError> for InterpreterError {
+    fn from(err: eval::RuntimeError) -> Self {
+        InterpreterError::RuntimeError(err)
+    }
+}
+
 fn find_entrypoint_signature(
     entrypoint: &str,
     module: &core::Module,
@@ -88,6 +97,7 @@ pub fn run_interpreter(config: Config) -> Result<(), InterpreterError> {
     let module_id = import::import(
         &mut modules,
         builtins_module_id,
+        &config.search_path,
         &source,
         0,
         &target_path,
@@ -131,7 +141,7 @@ pub fn run_interpreter(config: Config) -> Result<(), InterpreterError> {
     }
 
     let mut env = Env::new();
-    let _result = {
+    {
         let mut stdout = config.stdout.unwrap_or_else(|| Box::new(io::stdout()));
         let mut stdin = config
             .stdin
@@ -141,15 +151,21 @@ pub fn run_interpreter(config: Config) -> Result<(), InterpreterError> {
             .iter()
             .flat_map(|decl| decl.get_bindings(&common_kinds).into_iter())
             .collect();
-        let mut interpreter =
-            Interpreter::new(&mut stdin, &mut stdout, &common_kinds, &modules, &context);
+        let mut interpreter = Interpreter::new(
+            &mut stdin,
+            &mut stdout,
+            &common_kinds,
+            &modules,
+            &Source::File { path: target_path },
+            &context,
+        );
         let action = interpreter.eval_from_module(
             &mut env,
             &ModuleRef::from(module_id),
             &[],
             &Name::definition(entrypoint),
-        );
-        action.perform_io(&mut interpreter)
-    };
+        )?;
+        action.perform_io(&mut interpreter)?;
+    }
     Ok(())
 }
@@ -2,6 +2,7 @@
 mod test;
 
 use ipso_syntax::{self as syntax, kind::Kind, r#type, ModuleRef};
+pub use ipso_syntax::{Float, Redirect};
 use ipso_util::iter::Step;
 use std::{
     cmp,
@@ -22,6 +23,7 @@ just well-formedness that needs to be checked.
 pub enum Type {
     Bool,
     Int,
+    Float,
     Char,
     String,
     Bytes,
@@ -43,6 +45,12 @@ pub enum Type {
     Cmd,
     DebugRecordFields,
     DebugVariantCtor,
+    ToJsonRecordFields,
+    FromJsonRecordFields,
+    EqRecordFields,
+    EqVariantCtor,
+    OrdRecordFields,
+    OrdVariantCtor,
 }
 
 pub struct CommonKinds {
@@ -74,6 +82,7 @@ impl Type {
         match self {
             Type::Bool => r#type::Type::Bool,
             Type::Int => r#type::Type::Int,
+            Type::Float => r#type::Type::Float,
             Type::Char => r#type::Type::Char,
             Type::String => r#type::Type::String,
             Type::Bytes => r#type::Type::Bytes,
@@ -99,6 +108,12 @@ impl Type {
             Type::Cmd => r#type::Type::Cmd,
             Type::DebugRecordFields => r#type::Type::Name(Rc::from("DebugRecordFields")),
             Type::DebugVariantCtor => r#type::Type::Name(Rc::from("DebugVariantCtor")),
+            Type::ToJsonRecordFields => r#type::Type::Name(Rc::from("ToJsonRecordFields")),
+            Type::FromJsonRecordFields => r#type::Type::Name(Rc::from("FromJsonRecordFields")),
+            Type::EqRecordFields => r#type::Type::Name(Rc::from("EqRecordFields")),
+            Type::EqVariantCtor => r#type::Type::Name(Rc::from("EqVariantCtor")),
+            Type::OrdRecordFields => r#type::Type::Name(Rc::from("OrdRecordFields")),
+            Type::OrdVariantCtor => r#type::Type::Name(Rc::from("OrdVariantCtor")),
         }
     }
 
@@ -106,6 +121,7 @@ impl Type {
         match self {
             Type::Bool => Kind::Type,
             Type::Int => Kind::Type,
+            Type::Float => Kind::Type,
             Type::Char => Kind::Type,
             Type::String => Kind::Type,
             Type::Bytes => Kind::Type,
@@ -127,6 +143,12 @@ impl Type {
             Type::Meta(k, _) => k.clone(),
             Type::DebugRecordFields => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
             Type::DebugVariantCtor => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            Type::ToJsonRecordFields => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            Type::FromJsonRecordFields => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            Type::EqRecordFields => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            Type::EqVariantCtor => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            Type::OrdRecordFields => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            Type::OrdVariantCtor => Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
         }
     }
 
@@ -240,6 +262,7 @@ impl Type {
             Type::Var(k, n) => Type::Var(k.clone(), *n),
             Type::Bool => Type::Bool,
             Type::Int => Type::Int,
+            Type::Float => Type::Float,
             Type::Char => Type::Char,
             Type::String => Type::String,
             Type::Bytes => Type::Bytes,
@@ -267,6 +290,12 @@ impl Type {
             Type::Cmd => Type::Cmd,
             Type::DebugRecordFields => Type::DebugRecordFields,
             Type::DebugVariantCtor => Type::DebugVariantCtor,
+            Type::ToJsonRecordFields => Type::ToJsonRecordFields,
+            Type::FromJsonRecordFields => Type::FromJsonRecordFields,
+            Type::EqRecordFields => Type::EqRecordFields,
+            Type::EqVariantCtor => Type::EqVariantCtor,
+            Type::OrdRecordFields => Type::OrdRecordFields,
+            Type::OrdVariantCtor => Type::OrdVariantCtor,
         }
     }
 
@@ -276,6 +305,7 @@ impl Type {
             Type::Var(_, n) => f(n),
             Type::Bool => Type::Bool,
             Type::Int => Type::Int,
+            Type::Float => Type::Float,
             Type::Char => Type::Char,
             Type::String => Type::String,
             Type::Bytes => Type::Bytes,
@@ -297,6 +327,12 @@ impl Type {
             Type::Cmd => Type::Cmd,
             Type::DebugRecordFields => Type::DebugRecordFields,
             Type::DebugVariantCtor => Type::DebugVariantCtor,
+            Type::ToJsonRecordFields => Type::ToJsonRecordFields,
+            Type::FromJsonRecordFields => Type::FromJsonRecordFields,
+            Type::EqRecordFields => Type::EqRecordFields,
+            Type::EqVariantCtor => Type::EqVariantCtor,
+            Type::OrdRecordFields => Type::OrdRecordFields,
+            Type::OrdVariantCtor => Type::OrdVariantCtor,
         }
     }
 
@@ -313,6 +349,7 @@ impl Type {
             }
             Type::Bool => Type::Bool,
             Type::Int => Type::Int,
+            Type::Float => Type::Float,
             Type::Char => Type::Char,
             Type::String => Type::String,
             Type::Bytes => Type::Bytes,
@@ -340,6 +377,12 @@ impl Type {
             Type::Cmd => Type::Cmd,
             Type::DebugRecordFields => Type::DebugRecordFields,
             Type::DebugVariantCtor => Type::DebugVariantCtor,
+            Type::ToJsonRecordFields => Type::ToJsonRecordFields,
+            Type::FromJsonRecordFields => Type::FromJsonRecordFields,
+            Type::EqRecordFields => Type::EqRecordFields,
+            Type::EqVariantCtor => Type::EqVariantCtor,
+            Type::OrdRecordFields => Type::OrdRecordFields,
+            Type::OrdVariantCtor => Type::OrdVariantCtor,
         }
     }
 
@@ -484,6 +527,7 @@ impl<'a> Iterator for TypeIterMetas<'a> {
                 Type::Var(_, _) => Step::Skip,
                 Type::Bool => Step::Skip,
                 Type::Int => Step::Skip,
+                Type::Float => Step::Skip,
                 Type::Char => Step::Skip,
                 Type::String => Step::Skip,
                 Type::Bytes => Step::Skip,
@@ -503,6 +547,12 @@ impl<'a> Iterator for TypeIterMetas<'a> {
                 Type::Cmd => Step::Skip,
                 Type::DebugRecordFields => Step::Skip,
                 Type::DebugVariantCtor => Step::Skip,
+                Type::ToJsonRecordFields => Step::Skip,
+                Type::FromJsonRecordFields => Step::Skip,
+                Type::EqRecordFields => Step::Skip,
+                Type::EqVariantCtor => Step::Skip,
+                Type::OrdRecordFields => Step::Skip,
+                Type::OrdVariantCtor => Step::Skip,
             }
         }
 
@@ -597,6 +647,7 @@ impl<'a> Iterator for TypeIterVars<'a> {
                 Type::Var(_, v) => Step::Yield(*v),
                 Type::Bool => Step::Skip,
                 Type::Int => Step::Skip,
+                Type::Float => Step::Skip,
                 Type::Char => Step::Skip,
                 Type::String => Step::Skip,
                 Type::Bytes => Step::Skip,
@@ -616,6 +667,12 @@ impl<'a> Iterator for TypeIterVars<'a> {
                 Type::Cmd => Step::Skip,
                 Type::DebugRecordFields => Step::Skip,
                 Type::DebugVariantCtor => Step::Skip,
+                Type::ToJsonRecordFields => Step::Skip,
+                Type::FromJsonRecordFields => Step::Skip,
+                Type::EqRecordFields => Step::Skip,
+                Type::EqVariantCtor => Step::Skip,
+                Type::OrdRecordFields => Step::Skip,
+                Type::OrdVariantCtor => Step::Skip,
             }
         }
 
@@ -661,35 +718,112 @@ impl<'a> Iterator for TypeIterVars<'a> {
     }
 }
 
+/**
+A pattern.
+
+Patterns can be nested. The variables bound by a pattern are ordered from left to right, so
+that the rightmost variable is the innermost (i.e. `Var(0)` in a branch's body).
+
+Expressions in a pattern (tags and field indices) are in the scope of the `case` expression,
+and can't refer to any of the pattern's variables.
+*/
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Pattern<E> {
     Name,
-    Record { names: Vec<E>, rest: bool },
-    Variant { tag: Rc<E> },
+    Record {
+        fields: Vec<(E, Pattern<E>)>,
+        rest: bool,
+    },
+    Variant {
+        tag: Rc<E>,
+        arg: Rc<Pattern<E>>,
+    },
+    Array(Vec<Pattern<E>>),
     Char(char),
-    Int(i32),
+    Int(i64),
     String(Rc<str>),
     Wildcard,
 }
 
-impl Pattern<Expr> {
+impl<E> Pattern<E> {
     pub fn bound_vars(&self) -> usize {
         match self {
             Pattern::Name => 1,
-            Pattern::Record { names, rest } => names.len() + if *rest { 1 } else { 0 },
-            Pattern::Variant { tag: _ } => 1,
+            Pattern::Record { fields, rest } => {
+                fields
+                    .iter()
+                    .map(|(_, pattern)| pattern.bound_vars())
+                    .sum::<usize>()
+                    + if *rest { 1 } else { 0 }
+            }
+            Pattern::Variant { arg, .. } => arg.bound_vars(),
+            Pattern::Array(items) => items.iter().map(|item| item.bound_vars()).sum(),
             Pattern::Char(_) | Pattern::Int(_) | Pattern::String(_) | Pattern::Wildcard => 0,
         }
     }
 
-    pub fn map_expr<F: Fn(&Expr) -> Expr>(&self, f: F) -> Self {
+    /**
+    Check whether a pattern matches every value of its type.
+
+    A variant pattern with an irrefutable argument 'peels' its constructor when it's matched
+    at the top of a `case` branch (see [note: peeling constructors when matching on variants]).
+    */
+    pub fn is_irrefutable(&self) -> bool {
+        match self {
+            Pattern::Name | Pattern::Wildcard => true,
+            Pattern::Record { fields, .. } => {
+                fields.iter().all(|(_, pattern)| pattern.is_irrefutable())
+            }
+            Pattern::Variant { .. }
+            | Pattern::Array(_)
+            | Pattern::Char(_)
+            | Pattern::Int(_)
+            | Pattern::String(_) => false,
+        }
+    }
+
+    /// Get the expressions in a pattern, from left to right.
+    pub fn get_exprs(&self) -> Vec<&E> {
+        fn go<'a, E>(pattern: &'a Pattern<E>, exprs: &mut Vec<&'a E>) {
+            match pattern {
+                Pattern::Record { fields, .. } => fields.iter().for_each(|(index, pattern)| {
+                    exprs.push(index);
+                    go(pattern, exprs);
+                }),
+                Pattern::Variant { tag, arg } => {
+                    exprs.push(tag);
+                    go(arg, exprs);
+                }
+                Pattern::Array(items) => items.iter().for_each(|item| go(item, exprs)),
+                Pattern::Name
+                | Pattern::Char(_)
+                | Pattern::Int(_)
+                | Pattern::String(_)
+                | Pattern::Wildcard => {}
+            }
+        }
+
+        let mut exprs = Vec::new();
+        go(self, &mut exprs);
+        exprs
+    }
+}
+
+impl Pattern<Expr> {
+    pub fn map_expr<F: Fn(&Expr) -> Expr>(&self, f: &F) -> Self {
         match self {
             Pattern::Name => Pattern::Name,
-            Pattern::Record { names, rest } => Pattern::Record {
-                names: names.iter().map(f).collect(),
+            Pattern::Record { fields, rest } => Pattern::Record {
+                fields: fields
+                    .iter()
+                    .map(|(index, pattern)| (f(index), pattern.map_expr(f)))
+                    .collect(),
                 rest: *rest,
             },
-            Pattern::Variant { tag } => Pattern::mk_variant(f(tag)),
+            Pattern::Variant { tag, arg } => Pattern::mk_variant(f(tag), arg.map_expr(f)),
+            Pattern::Array(items) => {
+                Pattern::Array(items.iter().map(|item| item.map_expr(f)).collect())
+            }
             Pattern::Char(c) => Pattern::Char(*c),
             Pattern::Int(n) => Pattern::Int(*n),
             Pattern::String(s) => Pattern::String(s.clone()),
@@ -697,8 +831,11 @@ impl Pattern<Expr> {
         }
     }
 
-    pub fn mk_variant(tag: Expr) -> Pattern<Expr> {
-        Pattern::Variant { tag: Rc::new(tag) }
+    pub fn mk_variant(tag: Expr, arg: Pattern<Expr>) -> Pattern<Expr> {
+        Pattern::Variant {
+            tag: Rc::new(tag),
+            arg: Rc::new(arg),
+        }
     }
 
     pub fn subst_placeholder<E, F: FnMut(&Placeholder) -> Result<Expr, E>>(
@@ -707,10 +844,17 @@ impl Pattern<Expr> {
     ) -> Result<(), E> {
         match self {
             Pattern::Name => Ok(()),
-            Pattern::Record { names, .. } => names
+            Pattern::Record { fields, .. } => fields.iter_mut().try_for_each(|(index, pattern)| {
+                index.subst_placeholder(f)?;
+                pattern.subst_placeholder(f)
+            }),
+            Pattern::Variant { tag, arg } => {
+                Rc::make_mut(tag).subst_placeholder(f)?;
+                Rc::make_mut(arg).subst_placeholder(f)
+            }
+            Pattern::Array(items) => items
                 .iter_mut()
-                .try_for_each(|name| name.subst_placeholder(f)),
-            Pattern::Variant { tag } => Rc::make_mut(tag).subst_placeholder(f),
+                .try_for_each(|item| item.subst_placeholder(f)),
             Pattern::Char(_) => Ok(()),
             Pattern::Int(_) => Ok(()),
             Pattern::String(_) => Ok(()),
@@ -721,14 +865,27 @@ impl Pattern<Expr> {
     pub fn __instantiate(&self, depth: usize, val: &Expr) -> Self {
         match self {
             Pattern::Name => Pattern::Name,
-            Pattern::Record { names, rest } => Pattern::Record {
-                names: names
+            Pattern::Record { fields, rest } => Pattern::Record {
+                fields: fields
                     .iter()
-                    .map(|name| name.__instantiate(depth, val))
+                    .map(|(index, pattern)| {
+                        (
+                            index.__instantiate(depth, val),
+                            pattern.__instantiate(depth, val),
+                        )
+                    })
                     .collect(),
                 rest: *rest,
             },
-            Pattern::Variant { tag } => Pattern::mk_variant(tag.__instantiate(depth, val)),
+            Pattern::Variant { tag, arg } => {
+                Pattern::mk_variant(tag.__instantiate(depth, val), arg.__instantiate(depth, val))
+            }
+            Pattern::Array(items) => Pattern::Array(
+                items
+                    .iter()
+                    .map(|item| item.__instantiate(depth, val))
+                    .collect(),
+            ),
             Pattern::Char(c) => Pattern::Char(*c),
             Pattern::Int(n) => Pattern::Int(*n),
             Pattern::String(s) => Pattern::String(s.clone()),
@@ -739,14 +896,28 @@ impl Pattern<Expr> {
     pub fn __abstract_evar(&self, depth: usize, ev: EVar) -> Self {
         match self {
             Pattern::Name => Pattern::Name,
-            Pattern::Record { names, rest } => Pattern::Record {
-                names: names
+            Pattern::Record { fields, rest } => Pattern::Record {
+                fields: fields
                     .iter()
-                    .map(|name| name.__abstract_evar(depth, ev))
+                    .map(|(index, pattern)| {
+                        (
+                            index.__abstract_evar(depth, ev),
+                            pattern.__abstract_evar(depth, ev),
+                        )
+                    })
                     .collect(),
                 rest: *rest,
             },
-            Pattern::Variant { tag } => Pattern::mk_variant(tag.__abstract_evar(depth, ev)),
+            Pattern::Variant { tag, arg } => Pattern::mk_variant(
+                tag.__abstract_evar(depth, ev),
+                arg.__abstract_evar(depth, ev),
+            ),
+            Pattern::Array(items) => Pattern::Array(
+                items
+                    .iter()
+                    .map(|item| item.__abstract_evar(depth, ev))
+                    .collect(),
+            ),
             Pattern::Char(c) => Pattern::Char(*c),
             Pattern::Int(n) => Pattern::Int(*n),
             Pattern::String(s) => Pattern::String(s.clone()),
@@ -779,39 +950,18 @@ impl Branch<Expr> {
     pub fn __instantiate(&self, depth: usize, val: &Expr) -> Self {
         Branch {
             pattern: self.pattern.__instantiate(depth, val),
-            body: self.body.__instantiate(
-                depth
-                    + match &self.pattern {
-                        Pattern::Name => 1,
-                        Pattern::Record { names, rest } => names.len() + if *rest { 1 } else { 0 },
-                        Pattern::Variant { tag: _ } => 1,
-                        Pattern::Char(_) => 0,
-                        Pattern::Int(_) => 0,
-                        Pattern::String(_) => 0,
-                        // TODO: should this be 0?
-                        Pattern::Wildcard => 1,
-                    },
-                val,
-            ),
+            body: self
+                .body
+                .__instantiate(depth + self.pattern.bound_vars(), val),
         }
     }
 
     pub fn __abstract_evar(&self, depth: usize, ev: EVar) -> Self {
         Branch {
             pattern: self.pattern.__abstract_evar(depth, ev),
-            body: self.body.__abstract_evar(
-                depth
-                    + match &self.pattern {
-                        Pattern::Name => 1,
-                        Pattern::Record { names, rest } => names.len() + if *rest { 1 } else { 0 },
-                        Pattern::Variant { tag: _ } => 1,
-                        Pattern::Char(_) => 0,
-                        Pattern::Int(_) => 0,
-                        Pattern::String(_) => 0,
-                        Pattern::Wildcard => 0,
-                    },
-                ev,
-            ),
+            body: self
+                .body
+                .__abstract_evar(depth + self.pattern.bound_vars(), ev),
         }
     }
 }
@@ -916,6 +1066,135 @@ pub enum Builtin {
     FileRead,
     FileWrite,
     FileAppend,
+    PipeCmd,
+    CmdTry,
+    CmdOutput,
+    FileTryRead,
+    FileTryWrite,
+    FileTryAppend,
+    EnvTryGetvar,
+    EnvTrySetvar,
+    EqFloat,
+    CompareFloat,
+    FloatToString,
+    FloatFromInt,
+    FloatToInt,
+    FloatParse,
+    IntDiv,
+    IntRem,
+    IntQuot,
+    IntAbs,
+    IntPow,
+    IntMin,
+    IntMax,
+    IntSafeDiv,
+    IntParse,
+    StringLength,
+    StringSlice,
+    StringIndexOf,
+    StringStartsWith,
+    StringEndsWith,
+    StringTrim,
+    StringReplace,
+    StringToChars,
+    StringFromChars,
+    StringToUpper,
+    StringToLower,
+    BytesLength,
+    BytesIndex,
+    BytesSlice,
+    BytesAppend,
+    BytesConcat,
+    BytesFromUtf8,
+    BytesToHex,
+    BytesFromHex,
+    BytesToBase64,
+    BytesFromBase64,
+    FileReadBytes,
+    FileWriteBytes,
+    CmdReadBytes,
+    CmdOutputBytes,
+    MapEmpty,
+    MapSize,
+    MapInsert,
+    MapLookup,
+    MapDelete,
+    MapToArray,
+    MapFromArray,
+    MapFold,
+    DebugMap,
+    SetEmpty,
+    SetSize,
+    SetInsert,
+    SetMember,
+    SetDelete,
+    SetToArray,
+    SetFromArray,
+    SetFold,
+    DebugSet,
+    ArrayFilter,
+    ArraySortBy,
+    ArrayReverse,
+    ArrayZip,
+    ArrayFind,
+    ArrayAll,
+    ArrayConcat,
+    ArrayRange,
+    ArrayFoldr,
+    ArrayPartition,
+    ArrayIndexOf,
+    ArrayGet,
+    ArrayHead,
+    ArrayLast,
+    ArrayUncons,
+    CharIsDigit,
+    CharIsAlpha,
+    CharIsSpace,
+    CharIsUpper,
+    CharIsLower,
+    CharToUpper,
+    CharToLower,
+    CharToCode,
+    CharFromCode,
+    JsonParse,
+    JsonRender,
+    JsonField,
+    JsonIndex,
+    JsonAsBool,
+    JsonAsInt,
+    JsonAsFloat,
+    JsonAsString,
+    JsonAsArray,
+    JsonAsObject,
+    JsonDecodeField,
+    JsonDecodeArray,
+    FileRemove,
+    FileCopy,
+    FileRename,
+    FileMetadata,
+    FileChmod,
+    DirCreate,
+    DirList,
+    DirRemove,
+    DirRemoveAll,
+    PathIsFile,
+    PathIsDir,
+    PathJoin,
+    PathParent,
+    PathFileName,
+    PathExtension,
+    PathWithExtension,
+    PathNormalize,
+    PathAbsolute,
+    PathRelativeTo,
+    PathGlob,
+    RegexCompile,
+    RegexIsMatch,
+    RegexFind,
+    RegexFindAll,
+    RegexCaptures,
+    RegexNamedCaptures,
+    RegexReplace,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
@@ -944,6 +1223,8 @@ pub enum Binop {
 pub enum CmdPart<E> {
     Literal(Rc<str>),
     Expr(E),
+    Pipe,
+    Redirect(Redirect),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -1003,7 +1284,8 @@ pub enum Expr {
     False,
     IfThenElse(Rc<Expr>, Rc<Expr>, Rc<Expr>),
 
-    Int(i32),
+    Int(i64),
+    Float(Float),
 
     Binop(Binop, Rc<Expr>, Rc<Expr>),
 
@@ -1027,6 +1309,16 @@ pub enum Expr {
     Unit,
 
     Cmd(Vec<CmdPart<Expr>>),
+
+    /**
+    An expression annotated with its position in the source file.
+
+    Runtime errors that occur while evaluating `expr` are reported at `pos`.
+    */
+    Located {
+        pos: usize,
+        expr: Rc<Expr>,
+    },
 }
 
 impl Expr {
@@ -1123,7 +1415,10 @@ impl Expr {
     pub fn mk_binop(op: Binop, a: Expr, b: Expr) -> Expr {
         if op == Binop::Add {
             if let (Expr::Int(a), Expr::Int(b)) = (&a, &b) {
-                return Expr::Int(*a + *b);
+                // Overflowing additions are left for the evaluator to report.
+                if let Some(n) = a.checked_add(*b) {
+                    return Expr::Int(n);
+                }
             }
         }
         Expr::Binop(op, Rc::new(a), Rc::new(b))
@@ -1132,7 +1427,10 @@ impl Expr {
     pub fn mk_binop_l(op: Binop, a: Expr, b: Rc<Expr>) -> Expr {
         if op == Binop::Add {
             if let (Expr::Int(a), Expr::Int(b)) = (&a, b.as_ref()) {
-                return Expr::Int(*a + *b);
+                // Overflowing additions are left for the evaluator to report.
+                if let Some(n) = a.checked_add(*b) {
+                    return Expr::Int(n);
+                }
             }
         }
         Expr::Binop(op, Rc::new(a), b)
@@ -1142,6 +1440,13 @@ impl Expr {
         Expr::Case(Rc::new(expr), branches)
     }
 
+    pub fn mk_located(pos: usize, expr: Expr) -> Expr {
+        Expr::Located {
+            pos,
+            expr: Rc::new(expr),
+        }
+    }
+
     pub fn mk_placeholder(p: usize) -> Expr {
         Expr::Placeholder(Placeholder(p))
     }
@@ -1195,6 +1500,7 @@ impl Expr {
                 Expr::False => Expr::False,
                 Expr::IfThenElse(a, b, c) => Expr::mk_ifthenelse(go(a, f), go(b, f), go(c, f)),
                 Expr::Int(n) => Expr::Int(*n),
+                Expr::Float(n) => Expr::Float(*n),
                 Expr::Binop(op, l, r) => Expr::mk_binop(*op, go(l, f), go(r, f)),
                 Expr::Char(c) => Expr::Char(*c),
                 Expr::String(ss) => {
@@ -1211,19 +1517,9 @@ impl Expr {
                 Expr::Case(a, bs) => Expr::mk_case(
                     go(a, f),
                     bs.iter()
-                        .map(|b| match &b.pattern {
-                            Pattern::Name => b.map_expr(|e| go(e, &Function::Under(1, f))),
-                            Pattern::Record { names, rest } => {
-                                let offset = names.len() + if *rest { 1 } else { 0 };
-                                b.map_expr(|e| go(e, &Function::Under(offset, f)))
-                            }
-                            Pattern::Variant { tag: _ } => {
-                                b.map_expr(|e| go(e, &Function::Under(1, f)))
-                            }
-                            Pattern::Char(_) => b.map_expr(|e| go(e, f)),
-                            Pattern::Int(_) => b.map_expr(|e| go(e, f)),
-                            Pattern::String(_) => b.map_expr(|e| go(e, f)),
-                            Pattern::Wildcard => b.map_expr(|e| go(e, f)),
+                        .map(|b| match b.pattern.bound_vars() {
+                            0 => b.map_expr(|e| go(e, f)),
+                            offset => b.map_expr(|e| go(e, &Function::Under(offset, f))),
                         })
                         .collect(),
                 ),
@@ -1233,10 +1529,13 @@ impl Expr {
                         .iter()
                         .map(|part| match part {
                             CmdPart::Literal(value) => CmdPart::Literal(value.clone()),
+                            CmdPart::Pipe => CmdPart::Pipe,
+                            CmdPart::Redirect(redirect) => CmdPart::Redirect(*redirect),
                             CmdPart::Expr(expr) => CmdPart::Expr(go(expr, f)),
                         })
                         .collect(),
                 ),
+                Expr::Located { pos, expr } => Expr::mk_located(*pos, go(expr, f)),
             }
         }
 
@@ -1297,8 +1596,8 @@ impl Expr {
                 body.__instantiate(if *arg { depth + 1 } else { depth }, val),
             ),
             Expr::Let { value, rest } => Expr::Let {
-                value: Rc::new(value.__instantiate(depth, value)),
-                rest: Rc::new(rest.__instantiate(depth + 1, rest)),
+                value: Rc::new(value.__instantiate(depth, val)),
+                rest: Rc::new(rest.__instantiate(depth + 1, val)),
             },
             Expr::True => Expr::True,
             Expr::False => Expr::False,
@@ -1308,6 +1607,7 @@ impl Expr {
                 c.__instantiate(depth, val),
             ),
             Expr::Int(n) => Expr::Int(*n),
+            Expr::Float(n) => Expr::Float(*n),
             Expr::Binop(a, b, c) => {
                 Expr::mk_binop(*a, b.__instantiate(depth, val), c.__instantiate(depth, val))
             }
@@ -1353,10 +1653,13 @@ impl Expr {
                     .iter()
                     .map(|part| match part {
                         CmdPart::Literal(value) => CmdPart::Literal(value.clone()),
+                        CmdPart::Pipe => CmdPart::Pipe,
+                        CmdPart::Redirect(redirect) => CmdPart::Redirect(*redirect),
                         CmdPart::Expr(expr) => CmdPart::Expr(expr.__instantiate(depth, val)),
                     })
                     .collect(),
             ),
+            Expr::Located { pos, expr } => Expr::mk_located(*pos, expr.__instantiate(depth, val)),
         }
     }
 
@@ -1392,6 +1695,7 @@ impl Expr {
                 Rc::make_mut(c).subst_placeholder(f)
             }
             Expr::Int(_) => Ok(()),
+            Expr::Float(_) => Ok(()),
             Expr::Binop(_, b, c) => {
                 Rc::make_mut(b).subst_placeholder(f)?;
                 Rc::make_mut(c).subst_placeholder(f)
@@ -1428,9 +1732,11 @@ impl Expr {
             Expr::Unit => Ok(()),
 
             Expr::Cmd(parts) => parts.iter_mut().try_for_each(|part| match part {
-                CmdPart::Literal(_) => Ok(()),
+                CmdPart::Literal(_) | CmdPart::Pipe | CmdPart::Redirect(_) => Ok(()),
                 CmdPart::Expr(expr) => expr.subst_placeholder(f),
             }),
+
+            Expr::Located { expr, .. } => Rc::make_mut(expr).subst_placeholder(f),
         }
     }
 
@@ -1471,6 +1777,7 @@ impl Expr {
                 c.__abstract_evar(depth, ev),
             ),
             Expr::Int(n) => Expr::Int(*n),
+            Expr::Float(n) => Expr::Float(*n),
             Expr::Binop(a, b, c) => Expr::mk_binop(
                 *a,
                 b.__abstract_evar(depth, ev),
@@ -1517,10 +1824,13 @@ impl Expr {
                     .iter()
                     .map(|part| match part {
                         CmdPart::Literal(value) => CmdPart::Literal(value.clone()),
+                        CmdPart::Pipe => CmdPart::Pipe,
+                        CmdPart::Redirect(redirect) => CmdPart::Redirect(*redirect),
                         CmdPart::Expr(expr) => CmdPart::Expr(expr.__abstract_evar(depth, ev)),
                     })
                     .collect(),
             ),
+            Expr::Located { pos, expr } => Expr::mk_located(*pos, expr.__abstract_evar(depth, ev)),
         }
     }
 
@@ -1568,8 +1878,8 @@ impl Expr {
     ///         vec![
     ///             Branch {
     ///                 pattern: Pattern::Record {
-    ///                     names: vec![
-    ///                         Expr::EVar(EVar(0))
+    ///                     fields: vec![
+    ///                         (Expr::EVar(EVar(0)), Pattern::Name)
     ///                     ],
     ///                     rest: true,
     ///                 },
@@ -1608,6 +1918,7 @@ impl<'a> Iterator for IterEVars<'a> {
                 Expr::False => Step::Skip,
                 Expr::IfThenElse(a, b, c) => Step::Continue3(a, b, c),
                 Expr::Int(_) => Step::Skip,
+                Expr::Float(_) => Step::Skip,
                 Expr::Binop(_, a, b) => Step::Continue2(a, b),
                 Expr::Char(_) => Step::Skip,
                 Expr::String(a) => Step::Continue(
@@ -1631,15 +1942,7 @@ impl<'a> Iterator for IterEVars<'a> {
                 Expr::Case(a, b) => Step::Continue({
                     let mut xs: Vec<&'a Expr> = vec![a];
                     xs.extend(b.iter().flat_map(|b| {
-                        let mut vals: Vec<&'a Expr> = match &b.pattern {
-                            Pattern::Name => Vec::new(),
-                            Pattern::Record { names, .. } => names.iter().collect(),
-                            Pattern::Variant { tag } => vec![tag],
-                            Pattern::Char(_) => Vec::new(),
-                            Pattern::Int(_) => Vec::new(),
-                            Pattern::String(_) => Vec::new(),
-                            Pattern::Wildcard => Vec::new(),
-                        };
+                        let mut vals: Vec<&'a Expr> = b.pattern.get_exprs();
                         vals.push(&b.body);
                         vals.into_iter()
                     }));
@@ -1650,11 +1953,12 @@ impl<'a> Iterator for IterEVars<'a> {
                     parts
                         .iter()
                         .filter_map(|part| match part {
-                            CmdPart::Literal(_) => None,
+                            CmdPart::Literal(_) | CmdPart::Pipe | CmdPart::Redirect(_) => None,
                             CmdPart::Expr(expr) => Some(expr),
                         })
                         .collect(),
                 ),
+                Expr::Located { expr, .. } => Step::Continue1(expr),
             }
         }
 
@@ -1731,6 +2035,21 @@ impl TypeSig {
 pub struct ClassMember {
     pub name: String,
     pub sig: TypeSig,
+
+    /**
+    The member's default implementation, if it has one.
+
+    A default is a function from a delayed class dictionary (`() -> dict`) to the member's
+    value. The dictionary is delayed because an instance that uses a default passes its own
+    dictionary, which contains the default.
+
+    The default's body is bound by [`ClassDeclaration::get_default_bindings`]. When the
+    declaration is in a typechecker's class context, this is a reference to that binding instead.
+
+    A member that has a default is stored in class dictionaries as a thunk (`() -> a`), so that
+    building a dictionary doesn't run the defaults that it contains.
+    */
+    pub default: Option<Rc<Expr>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -1750,6 +2069,7 @@ pub enum Declaration {
         body: Type,
     },
     Class(ClassDeclaration),
+    Data(DataDeclaration),
     Evidence {
         name: Rc<str>,
         body: Rc<Expr>,
@@ -1779,6 +2099,19 @@ pub enum Signature {
 }
 
 impl Declaration {
+    /// The top-level name introduced by the declaration, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Declaration::BuiltinType { name, .. }
+            | Declaration::Definition { name, .. }
+            | Declaration::TypeAlias { name, .. }
+            | Declaration::Module { name, .. } => Some(name),
+            Declaration::Class(decl) => Some(&decl.name),
+            Declaration::Data(decl) => Some(&decl.name),
+            Declaration::Evidence { .. } | Declaration::Instance { .. } => None,
+        }
+    }
+
     pub fn get_bindings(&self, common_kinds: &CommonKinds) -> HashMap<Name, Binding> {
         match self {
             Declaration::BuiltinType { .. } => HashMap::new(),
@@ -1797,7 +2130,13 @@ impl Declaration {
                 .get_bindings(common_kinds)
                 .into_iter()
                 .map(|(a, b)| (Name::Definition(a), Binding::Expr(b.1)))
+                .chain(
+                    decl.get_default_bindings()
+                        .into_iter()
+                        .map(|(a, b)| (Name::Evidence(a), Binding::Expr(b))),
+                )
                 .collect(),
+            Declaration::Data(_) => HashMap::new(),
             Declaration::Instance { .. } => HashMap::new(),
             Declaration::Module { name, decls } => HashMap::from([(
                 Name::Definition(name.clone()),
@@ -1826,6 +2165,7 @@ impl Declaration {
                 .into_iter()
                 .map(|(a, b)| (a, Signature::TypeSig(b.0)))
                 .collect(),
+            Declaration::Data(_) => HashMap::new(),
             Declaration::Instance { .. } => HashMap::new(),
             Declaration::Module { name, decls } => HashMap::from([(
                 name.clone(),
@@ -1904,23 +2244,169 @@ impl ClassDeclaration {
 
                     TypeSig::new(ty_vars, body)
                 };
-                let body: Rc<Expr> = Rc::new(Expr::mk_lam(
-                    true,
-                    Expr::mk_project(Expr::Var(0), Expr::Int(supers_len as i32 + ix as i32)),
-                ));
+                let body: Rc<Expr> = Rc::new(Expr::mk_lam(true, {
+                    let member_value =
+                        Expr::mk_project(Expr::Var(0), Expr::Int(supers_len as i64 + ix as i64));
+                    if member.default.is_some() {
+                        Expr::mk_app(member_value, Expr::Unit)
+                    } else {
+                        member_value
+                    }
+                }));
 
                 (member.name.clone(), (sig, body))
             })
             .collect()
     }
+
+    /**
+    The name of the binding that holds a member's default implementation.
+
+    Default implementations live in the evidence namespace so that they can't be referred to
+    by name in source code.
+    */
+    pub fn default_name(&self, member: &str) -> Rc<str> {
+        Rc::from(format!("{}.{} default", self.name, member))
+    }
+
+    pub fn get_default_bindings(&self) -> HashMap<Rc<str>, Rc<Expr>> {
+        self.members
+            .iter()
+            .filter_map(|member| {
+                member
+                    .default
+                    .as_ref()
+                    .map(|default| (self.default_name(&member.name), default.clone()))
+            })
+            .collect()
+    }
+}
+
+/**
+A user-defined data type.
+
+```text
+type Tree a = Leaf | Node (Tree a) a (Tree a)
+```
+
+Data constructors aren't definitions. They're inlined wherever they're used (see
+[`DataDeclaration::constructor_body`]), so a data declaration has no bindings.
+*/
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DataDeclaration {
+    pub name: Rc<str>,
+    pub args: Vec<(Rc<str>, Kind)>,
+    pub constructors: Vec<DataConstructor>,
+}
+
+/// A constructor of a [`DataDeclaration`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DataConstructor {
+    pub name: Rc<str>,
+
+    /// The constructor's fields, which may refer to the data type's arguments.
+    pub fields: Vec<Type>,
+}
+
+impl DataDeclaration {
+    /// The data type's kind.
+    pub fn kind(&self) -> Kind {
+        self.args
+            .iter()
+            .rev()
+            .fold(Kind::Type, |acc, (_, arg_kind)| {
+                Kind::mk_arrow(arg_kind, &acc)
+            })
+    }
+
+    /// The data type applied to all its arguments.
+    pub fn ty(&self) -> Type {
+        let args_len = self.args.len();
+        self.args.iter().enumerate().fold(
+            Type::unsafe_mk_name(self.name.clone(), self.kind()),
+            |acc, (arg_index, (_, arg_kind))| {
+                Type::app(
+                    acc,
+                    Type::unsafe_mk_var(args_len - 1 - arg_index, arg_kind.clone()),
+                )
+            },
+        )
+    }
+
+    /// Find a constructor and its tag.
+    pub fn get_constructor(&self, name: &str) -> Option<(usize, &DataConstructor)> {
+        self.constructors
+            .iter()
+            .enumerate()
+            .find(|(_, constructor)| constructor.name.as_ref() == name)
+    }
+
+    /**
+    The type signature of the constructor with tag `tag`.
+
+    e.g. `Node : forall a. Tree a -> a -> Tree a -> Tree a`
+    */
+    pub fn constructor_signature(&self, common_kinds: &CommonKinds, tag: usize) -> TypeSig {
+        TypeSig::new(
+            self.args.clone(),
+            self.constructors[tag]
+                .fields
+                .iter()
+                .rev()
+                .fold(self.ty(), |acc, field| {
+                    Type::arrow(common_kinds, field.clone(), acc)
+                }),
+        )
+    }
+
+    /**
+    The code for the constructor with tag `tag`.
+
+    A data constructor builds an [`Expr::Variant`] whose argument is a record of the
+    constructor's fields, indexed by position. `Node` becomes
+    `\a b c -> Variant(1) { 0 = a, 1 = b, 2 = c }`.
+    */
+    pub fn constructor_body(&self, tag: usize) -> Expr {
+        let fields_len = self.constructors[tag].fields.len();
+        let value = Expr::mk_app(
+            Expr::mk_variant(Expr::Int(tag as i64)),
+            Expr::mk_record(
+                (0..fields_len)
+                    .map(|index| (Expr::Int(index as i64), Expr::Var(fields_len - 1 - index)))
+                    .collect(),
+                None,
+            ),
+        );
+        (0..fields_len).fold(value, |body, _| Expr::mk_lam(true, body))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Module {
     pub decls: Vec<Declaration>,
+
+    /**
+    The top-level names that importers can use, or [`None`] when the module has no export
+    declarations and every top-level name is visible.
+
+    Exporting a class exports its members, and exporting a data type exports its constructors.
+    */
+    pub exports: Option<Vec<String>>,
 }
 
 impl Module {
+    /**
+    Whether the names introduced by a declaration are visible to importers.
+
+    Instances are always visible, because they can't be imported selectively.
+    */
+    pub fn exports(&self, decl: &Declaration) -> bool {
+        match (&self.exports, decl.name()) {
+            (Some(exports), Some(name)) => exports.iter().any(|export| export == name),
+            (None, _) | (_, None) => true,
+        }
+    }
+
     pub fn get_bindings(&self, common_kinds: &CommonKinds) -> HashMap<Name, Binding> {
         let bindings: HashMap<Name, Binding> = HashMap::new();
         self.decls.iter().fold(bindings, |mut acc, decl| {
@@ -1929,11 +2415,15 @@ impl Module {
         })
     }
 
+    /// The signatures of the definitions that the module [`exports`](Module::exports).
     pub fn get_signatures(&self, common_kinds: &CommonKinds) -> HashMap<String, Signature> {
         let signatures: HashMap<String, Signature> = HashMap::new();
-        self.decls.iter().fold(signatures, |mut acc, decl| {
-            acc.extend(decl.get_signatures(common_kinds).into_iter());
-            acc
-        })
+        self.decls
+            .iter()
+            .filter(|decl| self.exports(decl))
+            .fold(signatures, |mut acc, decl| {
+                acc.extend(decl.get_signatures(common_kinds).into_iter());
+                acc
+            })
     }
 }
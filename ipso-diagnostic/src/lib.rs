@@ -197,18 +197,23 @@ impl Diagnostic {
                         },
                         Some(offset) => {
                             let mut pos = offset;
-                            while file_entry.offset < offset {
-                                pos -= file_entry.line_str.len();
-                                file_entry.line_str.clear();
-                                match file_entry.file.read_line(&mut file_entry.line_str) {
+                            /*
+                            An offset at the start of a line belongs to that line, but an offset
+                            at the end of the file belongs to the last line.
+                            */
+                            while file_entry.offset <= offset {
+                                let mut line_str = String::new();
+                                match file_entry.file.read_line(&mut line_str) {
                                     Err(err) => return Err(err),
                                     Ok(bytes_read) => {
-                                        file_entry.offset += bytes_read;
-                                        file_entry.line += 1;
-
                                         if bytes_read == 0 {
                                             break;
                                         }
+
+                                        pos -= file_entry.line_str.len();
+                                        file_entry.line_str = line_str;
+                                        file_entry.offset += bytes_read;
+                                        file_entry.line += 1;
                                     }
                                 }
                             }
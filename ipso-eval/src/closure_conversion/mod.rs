@@ -2,7 +2,7 @@
 mod test;
 
 use fnv::FnvHashSet;
-use ipso_core::{Binop, Branch, Builtin, CmdPart, Name, Pattern, StringPart};
+use ipso_core::{Binop, Branch, Builtin, CmdPart, Float, Name, Pattern, StringPart};
 use ipso_syntax::ModuleRef;
 use std::rc::Rc;
 
@@ -33,7 +33,8 @@ pub enum Expr {
     False,
     IfThenElse(Rc<Expr>, Rc<Expr>, Rc<Expr>),
 
-    Int(i32),
+    Int(i64),
+    Float(Float),
 
     Binop(Binop, Rc<Expr>, Rc<Expr>),
 
@@ -53,6 +54,11 @@ pub enum Expr {
     Unit,
 
     Cmd(Vec<CmdPart<Expr>>),
+
+    Located {
+        pos: usize,
+        expr: Rc<Expr>,
+    },
 }
 
 pub struct ConvertResult<A> {
@@ -326,12 +332,20 @@ pub fn convert_expr(expr: &ipso_core::Expr) -> ConvertResult<Expr> {
             }
         }
         ipso_core::Expr::Cmd(parts) => convert_many(convert_cmd_part, parts).map(Expr::Cmd),
+        ipso_core::Expr::Located { pos, expr } => {
+            let pos = *pos;
+            convert_expr(expr).map(move |expr| Expr::Located {
+                pos,
+                expr: Rc::new(expr),
+            })
+        }
 
         // Simple expressions.
         ipso_core::Expr::Name(name) => ConvertResult::closed(Expr::Name(name.clone())),
         ipso_core::Expr::True => ConvertResult::closed(Expr::True),
         ipso_core::Expr::False => ConvertResult::closed(Expr::False),
         ipso_core::Expr::Int(i) => ConvertResult::closed(Expr::Int(*i)),
+        ipso_core::Expr::Float(n) => ConvertResult::closed(Expr::Float(*n)),
         ipso_core::Expr::Char(c) => ConvertResult::closed(Expr::Char(*c)),
         ipso_core::Expr::Unit => ConvertResult::closed(Expr::Unit),
         ipso_core::Expr::Builtin(builtin) => ConvertResult::closed(Expr::Builtin(*builtin)),
@@ -365,6 +379,8 @@ pub fn convert_cmd_part(part: &CmdPart<ipso_core::Expr>) -> ConvertResult<CmdPar
             let string = string.clone();
             ConvertResult::closed(CmdPart::Literal(string))
         }
+        CmdPart::Pipe => ConvertResult::closed(CmdPart::Pipe),
+        CmdPart::Redirect(redirect) => ConvertResult::closed(CmdPart::Redirect(*redirect)),
         CmdPart::Expr(expr) => convert_expr(expr).map(CmdPart::Expr),
     }
 }
@@ -415,12 +431,22 @@ pub fn convert_branch(branch: &Branch<ipso_core::Expr>) -> ConvertResult<Branch<
 
 pub fn convert_pattern(pattern: &Pattern<ipso_core::Expr>) -> ConvertResult<Pattern<Expr>> {
     match pattern {
-        ipso_core::Pattern::Record { names, rest } => {
+        ipso_core::Pattern::Record { fields, rest } => {
             let rest = *rest;
-            convert_many(convert_expr, names).map(move |names| Pattern::Record { names, rest })
+            convert_many(
+                |(index, pattern)| convert_expr(index).and(convert_pattern(pattern)),
+                fields,
+            )
+            .map(move |fields| Pattern::Record { fields, rest })
         }
-        ipso_core::Pattern::Variant { tag } => {
-            convert_expr(tag).map(move |tag| Pattern::Variant { tag: Rc::new(tag) })
+        ipso_core::Pattern::Variant { tag, arg } => convert_expr(tag)
+            .and(convert_pattern(arg))
+            .map(move |(tag, arg)| Pattern::Variant {
+                tag: Rc::new(tag),
+                arg: Rc::new(arg),
+            }),
+        ipso_core::Pattern::Array(items) => {
+            convert_many(convert_pattern, items).map(Pattern::Array)
         }
         ipso_core::Pattern::Name => ConvertResult::closed(Pattern::Name),
         ipso_core::Pattern::Char(c) => ConvertResult::closed(Pattern::Char(*c)),
@@ -288,6 +288,7 @@ fn convert_9() {
                         Branch {
                             pattern: Pattern::Variant {
                                 tag: Rc::new(Expr::Int(0)),
+                                arg: Rc::new(Pattern::Name),
                             },
                             body: Expr::Var(0),
                         },
@@ -319,6 +320,7 @@ fn convert_9() {
                         Branch {
                             pattern: Pattern::Variant {
                                 tag: Rc::new(Expr::Int(0)),
+                                arg: Rc::new(Pattern::Name),
                             },
                             body: Expr::Var(0),
                         },
@@ -357,6 +359,7 @@ fn convert_10() {
                         Branch {
                             pattern: Pattern::Variant {
                                 tag: Rc::new(Expr::Var(1)),
+                                arg: Rc::new(Pattern::Name),
                             },
                             body: Expr::App(Rc::new(Expr::Var(2)), Rc::new(Expr::Var(0))),
                         },
@@ -393,6 +396,7 @@ fn convert_10() {
                         Branch {
                             pattern: Pattern::Variant {
                                 tag: Rc::new(Expr::Var(1)),
+                                arg: Rc::new(Pattern::Name),
                             },
                             body: Expr::App(Rc::new(Expr::Var(2)), Rc::new(Expr::Var(0))),
                         },
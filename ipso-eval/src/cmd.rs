@@ -0,0 +1,541 @@
+//! Commands and pipelines.
+
+use ipso_core::Redirect;
+use std::{
+    fmt::{self, Display},
+    fs::{File, OpenOptions},
+    io::{self, Read},
+    os::unix::{
+        io::{FromRawFd, RawFd},
+        process::ExitStatusExt,
+    },
+    process::{self, Child, ExitStatus, Stdio},
+    rc::Rc,
+    thread,
+};
+
+/// The destination of a process' standard output or standard error.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Output {
+    /**
+    The destination of standard output before any redirections: the next process in the pipeline,
+    or the pipeline's own standard output.
+    */
+    Stdout,
+
+    /// The pipeline's standard error.
+    Stderr,
+
+    File {
+        path: Rc<str>,
+        append: bool,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Process {
+    pub args: Vec<Rc<str>>,
+    pub stdin: Option<Rc<str>>,
+    pub stdout: Output,
+    pub stderr: Output,
+}
+
+impl Process {
+    fn new() -> Self {
+        Process {
+            args: Vec::new(),
+            stdin: None,
+            stdout: Output::Stdout,
+            stderr: Output::Stderr,
+        }
+    }
+
+    fn redirect(&mut self, redirect: Redirect, target: Option<Rc<str>>) -> Result<(), Error> {
+        let target = || target.ok_or(Error::RedirectTarget { redirect, count: 0 });
+        match redirect {
+            Redirect::Stdin => self.stdin = Some(target()?),
+            Redirect::Stdout | Redirect::StdoutAppend => {
+                self.stdout = Output::File {
+                    path: target()?,
+                    append: redirect == Redirect::StdoutAppend,
+                }
+            }
+            Redirect::Stderr | Redirect::StderrAppend => {
+                self.stderr = Output::File {
+                    path: target()?,
+                    append: redirect == Redirect::StderrAppend,
+                }
+            }
+            Redirect::StderrToStdout => self.stderr = self.stdout.clone(),
+            Redirect::StdoutToStderr => self.stdout = self.stderr.clone(),
+        }
+        Ok(())
+    }
+
+    fn show(&self) -> String {
+        fn show_arg(string: &mut String, value: &str) {
+            if value.contains(|c| matches!(c, ' ' | '|' | '<' | '>' | '&')) {
+                string.push('"');
+                value.chars().for_each(|c| {
+                    if c == '"' {
+                        string.push('\\');
+                    }
+                    string.push(c);
+                });
+                string.push('"');
+            } else {
+                string.push_str(value);
+            }
+        }
+
+        fn show_redirect(string: &mut String, redirect: &str, target: Option<&str>) {
+            if !string.is_empty() {
+                string.push(' ');
+            }
+            string.push_str(redirect);
+            if let Some(target) = target {
+                string.push(' ');
+                show_arg(string, target);
+            }
+        }
+
+        let mut string = String::new();
+        self.args.iter().enumerate().for_each(|(ix, arg)| {
+            if ix > 0 {
+                string.push(' ');
+            }
+            show_arg(&mut string, arg);
+        });
+
+        if let Some(path) = &self.stdin {
+            show_redirect(&mut string, "<", Some(path));
+        }
+
+        // `2>&1` refers to the destination of standard output at the point where it's written.
+        let stderr_first = self.stderr == Output::Stdout && self.stdout != Output::Stdout;
+        if stderr_first {
+            show_redirect(&mut string, "2>&1", None);
+        }
+        match &self.stdout {
+            Output::Stdout => {}
+            Output::Stderr => show_redirect(&mut string, ">&2", None),
+            Output::File { path, append } => {
+                show_redirect(&mut string, if *append { ">>" } else { ">" }, Some(path))
+            }
+        }
+        if !stderr_first {
+            match &self.stderr {
+                Output::Stderr => {}
+                Output::Stdout => show_redirect(&mut string, "2>&1", None),
+                stderr if *stderr == self.stdout => show_redirect(&mut string, "2>&1", None),
+                Output::File { path, append } => {
+                    show_redirect(&mut string, if *append { "2>>" } else { "2>" }, Some(path))
+                }
+            }
+        }
+
+        string
+    }
+}
+
+/// A pipeline of processes, each of which writes its standard output to the next.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cmd {
+    pub processes: Vec<Process>,
+}
+
+/// Builds a [`Cmd`] from the evaluated parts of a command literal.
+pub struct Builder {
+    processes: Vec<Process>,
+    current: Process,
+    redirect: Option<Redirect>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Builder {
+            processes: Vec::new(),
+            current: Process::new(),
+            redirect: None,
+        }
+    }
+
+    /**
+    Add the arguments produced by a command part.
+
+    Fails when the arguments are the target of a redirection and there isn't exactly one of them.
+    */
+    pub fn args(&mut self, args: impl IntoIterator<Item = Rc<str>>) -> Result<(), Error> {
+        match self.redirect.take() {
+            None => {
+                self.current.args.extend(args);
+                Ok(())
+            }
+            Some(redirect) => {
+                let mut args = args.into_iter().collect::<Vec<_>>();
+                if args.len() == 1 {
+                    self.current.redirect(redirect, args.pop())
+                } else {
+                    Err(Error::RedirectTarget {
+                        redirect,
+                        count: args.len(),
+                    })
+                }
+            }
+        }
+    }
+
+    pub fn pipe(&mut self) {
+        let process = std::mem::replace(&mut self.current, Process::new());
+        self.processes.push(process);
+    }
+
+    pub fn redirect(&mut self, redirect: Redirect) -> Result<(), Error> {
+        if redirect.has_target() {
+            self.redirect = Some(redirect);
+            Ok(())
+        } else {
+            self.current.redirect(redirect, None)
+        }
+    }
+
+    pub fn build(mut self) -> Cmd {
+        self.processes.push(self.current);
+        Cmd {
+            processes: self.processes,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    EmptyCommand,
+    RedirectTarget { redirect: Redirect, count: usize },
+    Spawn { program: Rc<str>, error: io::Error },
+    Redirect { path: Rc<str>, error: io::Error },
+    Io(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EmptyCommand => write!(f, "empty command in pipeline"),
+            Error::RedirectTarget { redirect, count } => write!(
+                f,
+                "the target of {:?} must be a single argument, but it has {}",
+                redirect.render(),
+                count
+            ),
+            Error::Spawn { program, error } => {
+                write!(f, "failed to start process {:?}: {}", program, error)
+            }
+            Error::Redirect { path, error } => {
+                write!(f, "failed to redirect to {:?}: {}", path, error)
+            }
+            Error::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// The result of running a [`Cmd`] to completion.
+pub struct Finished {
+    /// The pipeline's standard output, if it was captured.
+    pub stdout: Vec<u8>,
+
+    /// The pipeline's standard error, if it was captured.
+    pub stderr: Vec<u8>,
+
+    /**
+    The program that determines the pipeline's status, and that status.
+
+    This is the last process to fail, or the last process of the pipeline when none of them
+    failed. Processes that were killed by `SIGPIPE` because a later process stopped reading
+    their output aren't considered failures.
+    */
+    pub program: Rc<str>,
+    pub status: ExitStatus,
+}
+
+impl Finished {
+    /**
+    The pipeline's exit code.
+
+    A process that was killed by a signal has the exit code `128 + signal`, like in a POSIX shell.
+    */
+    pub fn code(&self) -> i32 {
+        match self.status.code() {
+            Some(code) => code,
+            None => 128 + self.status.signal().unwrap_or(0),
+        }
+    }
+}
+
+fn check(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(result)
+    }
+}
+
+/**
+Create a pipe whose ends are closed when a process is spawned.
+
+On Linux the ends are created close-on-exec, so that a process spawned by another thread
+can't inherit them between `pipe` and `fcntl`.
+*/
+#[cfg(target_os = "linux")]
+fn pipe() -> io::Result<(File, File)> {
+    let mut fds: [RawFd; 2] = [0; 2];
+    check(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) })?;
+    Ok(unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) })
+}
+
+/// Create a pipe whose ends are closed when a process is spawned.
+#[cfg(not(target_os = "linux"))]
+fn pipe() -> io::Result<(File, File)> {
+    let mut fds: [RawFd; 2] = [0; 2];
+    check(unsafe { libc::pipe(fds.as_mut_ptr()) })?;
+    // Take ownership immediately so that the descriptors are closed on error.
+    let (read, write) = unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+    for fd in fds {
+        check(unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) })?;
+    }
+    Ok((read, write))
+}
+
+/// Duplicate one of the interpreter's standard streams.
+fn dup(fd: RawFd) -> io::Result<File> {
+    let fd = check(unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) })?;
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+fn open_output(path: &Rc<str>, append: bool) -> Result<File, Error> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(append)
+        .truncate(!append)
+        .open(path.as_ref())
+        .map_err(|error| Error::Redirect {
+            path: path.clone(),
+            error,
+        })
+}
+
+/**
+Open the destination of an output stream.
+
+`stdout` and `stderr` are the files that the process' standard output and standard error are
+written to before any redirections. `None` refers to the interpreter's own stream.
+*/
+fn open(output: &Output, stdout: &Option<File>, stderr: &Option<File>) -> Result<File, Error> {
+    match output {
+        Output::Stdout => match stdout {
+            Some(file) => Ok(file.try_clone()?),
+            None => Ok(dup(libc::STDOUT_FILENO)?),
+        },
+        Output::Stderr => match stderr {
+            Some(file) => Ok(file.try_clone()?),
+            None => Ok(dup(libc::STDERR_FILENO)?),
+        },
+        Output::File { path, append } => open_output(path, *append),
+    }
+}
+
+/// A pipe for capturing one of the pipeline's output streams, when `enabled` is set.
+fn capture(enabled: bool) -> io::Result<(Option<File>, Option<File>)> {
+    if enabled {
+        let (read, write) = pipe()?;
+        Ok((Some(read), Some(write)))
+    } else {
+        Ok((None, None))
+    }
+}
+
+impl Cmd {
+    /// A command that does nothing when it's run.
+    pub fn is_empty(&self) -> bool {
+        match self.processes.as_slice() {
+            [process] => *process == Process::new(),
+            _ => false,
+        }
+    }
+
+    pub fn show(&self) -> String {
+        self.processes
+            .iter()
+            .map(Process::show)
+            .collect::<Vec<String>>()
+            .join(" | ")
+    }
+
+    fn spawn(
+        &self,
+        stdout: Option<File>,
+        stderr: Option<File>,
+    ) -> Result<Vec<(Rc<str>, Child)>, Error> {
+        let mut children: Vec<(Rc<str>, Child)> = Vec::with_capacity(self.processes.len());
+        match self.spawn_into(&mut children, stdout, stderr) {
+            Ok(()) => Ok(children),
+            Err(error) => {
+                /*
+                A process that has already started would otherwise outlive the failed pipeline,
+                possibly blocked on a pipe that nothing reads.
+                */
+                for (_, child) in &mut children {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Start each process of the pipeline, adding it to `children` as soon as it's running.
+    fn spawn_into(
+        &self,
+        children: &mut Vec<(Rc<str>, Child)>,
+        stdout: Option<File>,
+        stderr: Option<File>,
+    ) -> Result<(), Error> {
+        let mut stdin: Option<File> = None;
+        let last = self.processes.len() - 1;
+
+        for (ix, process) in self.processes.iter().enumerate() {
+            let program = match process.args.first() {
+                Some(program) => program.clone(),
+                None => return Err(Error::EmptyCommand),
+            };
+
+            let (next_stdin, pipe_stdout) = if ix == last {
+                (None, stdout.as_ref().map(File::try_clone).transpose()?)
+            } else {
+                let (read, write) = pipe()?;
+                (Some(read), Some(write))
+            };
+
+            let mut command = process::Command::new(program.as_ref());
+            command.args(process.args[1..].iter().map(|arg| arg.as_ref()));
+
+            match &process.stdin {
+                Some(path) => {
+                    let file = File::open(path.as_ref()).map_err(|error| Error::Redirect {
+                        path: path.clone(),
+                        error,
+                    })?;
+                    command.stdin(file);
+                }
+                None => {
+                    if let Some(file) = stdin.take() {
+                        command.stdin(file);
+                    }
+                }
+            }
+
+            let stdout_file = match process.stdout {
+                Output::Stdout => pipe_stdout.as_ref().map(File::try_clone).transpose()?,
+                ref output => Some(open(output, &pipe_stdout, &stderr)?),
+            };
+            let stderr_file = if process.stderr == Output::Stderr {
+                stderr.as_ref().map(File::try_clone).transpose()?
+            } else if process.stderr == process.stdout {
+                // Both streams share a destination, like `> file 2>&1`.
+                Some(match &stdout_file {
+                    Some(file) => file.try_clone()?,
+                    None => dup(libc::STDOUT_FILENO)?,
+                })
+            } else {
+                Some(open(&process.stderr, &pipe_stdout, &stderr)?)
+            };
+            command.stdout(stdout_file.map_or_else(Stdio::inherit, Stdio::from));
+            command.stderr(stderr_file.map_or_else(Stdio::inherit, Stdio::from));
+
+            let child = command.spawn().map_err(|error| Error::Spawn {
+                program: program.clone(),
+                error,
+            })?;
+            children.push((program, child));
+
+            stdin = next_stdin;
+        }
+
+        Ok(())
+    }
+
+    /**
+    Run the pipeline to completion.
+
+    When `capture_stdout` is set, the pipeline's standard output is collected into
+    [`Finished::stdout`]. Otherwise it's inherited from the interpreter. `capture_stderr` does the
+    same for standard error.
+    */
+    pub fn run(&self, capture_stdout: bool, capture_stderr: bool) -> Result<Finished, Error> {
+        if self.is_empty() {
+            return Ok(Finished {
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+                program: Rc::from(""),
+                status: ExitStatus::from_raw(0),
+            });
+        }
+
+        let (stdout_reader, stdout_writer) = capture(capture_stdout)?;
+        let (stderr_reader, stderr_writer) = capture(capture_stderr)?;
+
+        // The write ends of the pipes are dropped here so that reading stops when the pipeline exits.
+        let children = self.spawn(stdout_writer, stderr_writer)?;
+
+        // Standard error is read on another thread so that neither pipe can fill up and block the
+        // pipeline.
+        let stderr_thread = stderr_reader.map(|mut reader| {
+            thread::spawn(move || {
+                let mut stderr = Vec::new();
+                reader.read_to_end(&mut stderr).map(|_| stderr)
+            })
+        });
+
+        let mut stdout = Vec::new();
+        if let Some(mut reader) = stdout_reader {
+            reader.read_to_end(&mut stdout)?;
+        }
+
+        let stderr = match stderr_thread {
+            None => Vec::new(),
+            Some(thread) => thread.join().map_err(|_| {
+                io::Error::new(io::ErrorKind::Other, "failed to read standard error")
+            })??,
+        };
+
+        let mut statuses: Vec<(Rc<str>, ExitStatus)> = Vec::with_capacity(children.len());
+        for (program, mut child) in children {
+            let status = child.wait()?;
+            statuses.push((program, status));
+        }
+
+        let last = statuses.len() - 1;
+        let failed = statuses.iter().enumerate().rposition(|(ix, (_, status))| {
+            let broken_pipe = ix < last && status.signal() == Some(libc::SIGPIPE);
+            !(status.success() || broken_pipe)
+        });
+        let (program, status) = statuses.swap_remove(failed.unwrap_or(last));
+        Ok(Finished {
+            stdout,
+            stderr,
+            program,
+            status,
+        })
+    }
+}
@@ -0,0 +1,94 @@
+//! Text encodings of binary data.
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as lowercase hexadecimal, two digits per byte.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        result.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+        result.push(char::from(HEX_DIGITS[usize::from(byte & 0xf)]));
+    }
+    result
+}
+
+/**
+Decode hexadecimal text, accepting either case.
+
+Returns [`None`] when the text has an odd number of digits or contains a non-hexadecimal character.
+*/
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    fn digit(c: u8) -> Option<u8> {
+        char::from(c).to_digit(16).map(|digit| digit as u8)
+    }
+
+    let text = text.as_bytes();
+    if text.len() % 2 != 0 {
+        return None;
+    }
+    text.chunks(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+/// Encode bytes as base64 using the standard alphabet, with padding (RFC 4648).
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |acc, (index, byte)| {
+            acc | u32::from(*byte) << (16 - 8 * index)
+        });
+        // A chunk of n bytes is encoded by n + 1 characters, and padded to 4 characters.
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                result.push(char::from(BASE64_ALPHABET[sextet as usize]));
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/**
+Decode base64 text that uses the standard alphabet and padding (RFC 4648).
+
+Returns [`None`] when the text isn't valid base64.
+*/
+pub fn from_base64(text: &str) -> Option<Vec<u8>> {
+    fn sextet(c: u8) -> Option<u32> {
+        BASE64_ALPHABET
+            .iter()
+            .position(|digit| *digit == c)
+            .map(|position| position as u32)
+    }
+
+    let text = text.as_bytes();
+    if text.len() % 4 != 0 {
+        return None;
+    }
+    let mut result = Vec::with_capacity(text.len() / 4 * 3);
+    let chunks = text.chunks(4);
+    let chunks_len = chunks.len();
+    for (chunk_index, chunk) in chunks.enumerate() {
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        // Padding is only allowed at the end of the text, and a chunk encodes at least one byte.
+        if padding > 2 || (padding > 0 && chunk_index + 1 != chunks_len) {
+            return None;
+        }
+        let group = chunk[..4 - padding]
+            .iter()
+            .enumerate()
+            .try_fold(0u32, |acc, (index, c)| {
+                Some(acc | sextet(*c)? << (18 - 6 * index))
+            })?;
+        for index in 0..3 - padding {
+            result.push((group >> (16 - 8 * index)) as u8);
+        }
+    }
+    Some(result)
+}
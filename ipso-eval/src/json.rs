@@ -0,0 +1,339 @@
+//! JSON parsing and rendering.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    /// A number without a fractional part or exponent, that fits in an `i64`.
+    Int(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// An object's members, in the order they appear in the source.
+    Object(Vec<(String, Json)>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+/// Parse a JSON document (RFC 8259).
+pub fn parse(input: &str) -> Result<Json, ParseError> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_whitespace();
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < input.len() {
+        return Err(parser.error("expected end of input"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> ParseError {
+        let consumed = &self.input[..self.pos];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count())
+            + 1;
+        ParseError {
+            message: String::from(message),
+            line,
+            column,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {:?}", expected)))
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Json) -> Result<Json, ParseError> {
+        if self.input[self.pos..].starts_with(keyword) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            Err(self.error("expected a JSON value"))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, ParseError> {
+        match self.peek() {
+            Some('n') => self.keyword("null", Json::Null),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.error("expected a JSON value")),
+        }
+    }
+
+    /// Parse comma-separated items between `open` and `close`.
+    fn sequence<T>(
+        &mut self,
+        open: char,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.expect(open)?;
+        self.skip_whitespace();
+        let mut items = Vec::new();
+        if self.peek() == Some(close) {
+            self.pos += 1;
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                }
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    return Ok(items);
+                }
+                _ => return Err(self.error(&format!("expected ',' or {:?}", close))),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, ParseError> {
+        self.sequence('[', ']', Self::value).map(Json::Array)
+    }
+
+    fn object(&mut self) -> Result<Json, ParseError> {
+        self.sequence('{', '}', |parser| {
+            if parser.peek() != Some('"') {
+                return Err(parser.error("expected a string"));
+            }
+            let key = parser.string()?;
+            parser.skip_whitespace();
+            parser.expect(':')?;
+            parser.skip_whitespace();
+            let value = parser.value()?;
+            Ok((key, value))
+        })
+        .map(Json::Object)
+    }
+
+    fn number(&mut self) -> Result<Json, ParseError> {
+        let start = self.pos;
+        let digits = |parser: &mut Self| -> usize {
+            let digits_start = parser.pos;
+            while let Some('0'..='9') = parser.peek() {
+                parser.pos += 1;
+            }
+            parser.pos - digits_start
+        };
+
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some('0') => self.pos += 1,
+            Some('1'..='9') => {
+                digits(self);
+            }
+            _ => return Err(self.error("expected a digit")),
+        }
+
+        let mut is_integer = true;
+        if self.peek() == Some('.') {
+            is_integer = false;
+            self.pos += 1;
+            if digits(self) == 0 {
+                return Err(self.error("expected a digit"));
+            }
+        }
+        if let Some('e' | 'E') = self.peek() {
+            is_integer = false;
+            self.pos += 1;
+            if let Some('+' | '-') = self.peek() {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                return Err(self.error("expected a digit"));
+            }
+        }
+
+        let text = &self.input[start..self.pos];
+        match text.parse::<i64>() {
+            Ok(value) if is_integer => Ok(Json::Int(value)),
+            _ => match text.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(Json::Float(value)),
+                _ => {
+                    self.pos = start;
+                    Err(self.error("number out of range"))
+                }
+            },
+        }
+    }
+
+    fn hex_escape(&mut self) -> Result<u32, ParseError> {
+        match self.input[self.pos..].get(..4) {
+            Some(digits) if digits.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                self.pos += 4;
+                Ok(u32::from_str_radix(digits, 16).unwrap())
+            }
+            _ => Err(self.error("expected 4 hexadecimal digits")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            match self.next() {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => return Ok(result),
+                Some('\\') => match self.next() {
+                    Some('"') => result.push('"'),
+                    Some('\\') => result.push('\\'),
+                    Some('/') => result.push('/'),
+                    Some('b') => result.push('\u{8}'),
+                    Some('f') => result.push('\u{c}'),
+                    Some('n') => result.push('\n'),
+                    Some('r') => result.push('\r'),
+                    Some('t') => result.push('\t'),
+                    Some('u') => {
+                        let escape_start = self.pos - 2;
+                        let mut code = self.hex_escape()?;
+                        // Characters outside the Basic Multilingual Plane are escaped as a UTF-16
+                        // surrogate pair.
+                        if (0xd800..0xdc00).contains(&code)
+                            && self.input[self.pos..].starts_with("\\u")
+                        {
+                            self.pos += 2;
+                            let low = self.hex_escape()?;
+                            code = if (0xdc00..0xe000).contains(&low) {
+                                0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00)
+                            } else {
+                                // An unpaired surrogate, which is rejected below.
+                                code
+                            };
+                        }
+                        match char::from_u32(code) {
+                            Some(c) => result.push(c),
+                            None => {
+                                self.pos = escape_start;
+                                return Err(self.error("invalid unicode escape"));
+                            }
+                        }
+                    }
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.error("invalid escape"));
+                    }
+                },
+                Some(c) if c < ' ' => {
+                    self.pos -= 1;
+                    return Err(self.error("unescaped control character in string"));
+                }
+                Some(c) => result.push(c),
+            }
+        }
+    }
+}
+
+/// Render a JSON value without any insignificant whitespace.
+pub fn render(value: &Json) -> String {
+    let mut result = String::new();
+    render_into(&mut result, value);
+    result
+}
+
+fn render_into(result: &mut String, value: &Json) {
+    match value {
+        Json::Null => result.push_str("null"),
+        Json::Bool(b) => result.push_str(if *b { "true" } else { "false" }),
+        Json::Int(n) => write!(result, "{}", n).unwrap(),
+        // JSON has no representation for NaN or infinity.
+        Json::Float(f) if !f.is_finite() => result.push_str("null"),
+        Json::Float(f) => write!(result, "{:?}", f).unwrap(),
+        Json::String(s) => render_string(result, s),
+        Json::Array(items) => {
+            result.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    result.push(',');
+                }
+                render_into(result, item);
+            }
+            result.push(']');
+        }
+        Json::Object(members) => {
+            result.push('{');
+            for (index, (key, value)) in members.iter().enumerate() {
+                if index > 0 {
+                    result.push(',');
+                }
+                render_string(result, key);
+                result.push(':');
+                render_into(result, value);
+            }
+            result.push('}');
+        }
+    }
+}
+
+fn render_string(result: &mut String, s: &str) {
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c < ' ' => write!(result, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+}
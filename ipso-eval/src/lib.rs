@@ -3,41 +3,521 @@ mod test;
 
 pub mod bindings;
 pub mod closure_conversion;
+pub mod cmd;
+mod encoding;
+mod json;
+pub mod map;
+mod path;
 
 use bindings::{Binding, Bindings};
 use closure_conversion::Expr;
 use ipso_core::{self as core, Binop, Builtin, CmdPart, CommonKinds, Name, Pattern, StringPart};
+use ipso_diagnostic::{Diagnostic, Location, Message, Source};
 use ipso_rope::Rope;
-use ipso_syntax::{ModuleId, ModuleRef, Modules};
+use ipso_syntax::{ModuleId, ModuleKey, ModuleRef, Modules};
 use paste::paste;
 use std::{
     cmp::Ordering,
     collections::HashMap,
+    convert::TryFrom,
     fmt::Debug,
     io::Write,
     io::{self, BufRead},
     ops::Index,
-    path::Path,
-    process::{self, ExitStatus, Stdio},
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::ExitStatus,
     rc::Rc,
 };
 
-fn check_exit_status(cmd: &str, status: &ExitStatus) {
-    match status.code() {
-        Some(code) => {
-            if code == 0 {
-            } else {
-                println!("process {:?} exited with code {:?}", cmd, code);
-                process::exit(1);
+/// Runtime error information.
+#[derive(Debug)]
+pub enum RuntimeErrorInfo {
+    IndexOutOfBounds {
+        index: i64,
+        length: usize,
+    },
+    SliceOutOfBounds {
+        start: i64,
+        length: i64,
+        array_length: usize,
+    },
+    StringSliceOutOfBounds {
+        start: i64,
+        length: i64,
+        string_length: usize,
+    },
+    BytesSliceOutOfBounds {
+        start: i64,
+        length: i64,
+        bytes_length: usize,
+    },
+    DivisionByZero,
+    IntegerOverflow,
+    NegativeExponent {
+        exponent: i64,
+    },
+    IncompletePatternMatch,
+    UnexpectedTag {
+        tag: usize,
+    },
+    InvalidUtf8 {
+        error: String,
+    },
+    Cmd {
+        error: cmd::Error,
+    },
+    ProcessFailed {
+        program: Rc<str>,
+        status: ExitStatus,
+    },
+    File {
+        path: String,
+        error: io::Error,
+    },
+    Io {
+        error: io::Error,
+    },
+
+    /**
+    The program asked to exit with a particular status code.
+
+    This isn't a failure, but it unwinds the interpreter in the same way.
+    */
+    Exit {
+        code: i32,
+    },
+}
+
+/// A runtime error.
+#[derive(Debug)]
+pub struct RuntimeError {
+    /**
+    The location of the innermost located expression that was being evaluated
+    when the error occurred.
+    */
+    pub location: Option<Location>,
+    pub info: RuntimeErrorInfo,
+}
+
+impl RuntimeError {
+    pub fn new(info: RuntimeErrorInfo) -> Self {
+        RuntimeError {
+            location: None,
+            info,
+        }
+    }
+
+    pub fn file(path: &str, error: io::Error) -> Self {
+        Self::new(RuntimeErrorInfo::File {
+            path: String::from(path),
+            error,
+        })
+    }
+
+    pub fn message(&self) -> String {
+        match &self.info {
+            RuntimeErrorInfo::IndexOutOfBounds { .. } => String::from("index out of bounds"),
+            RuntimeErrorInfo::SliceOutOfBounds { .. }
+            | RuntimeErrorInfo::StringSliceOutOfBounds { .. }
+            | RuntimeErrorInfo::BytesSliceOutOfBounds { .. } => String::from("slice out of bounds"),
+            RuntimeErrorInfo::DivisionByZero => String::from("division by zero"),
+            RuntimeErrorInfo::IntegerOverflow => String::from("integer overflow"),
+            RuntimeErrorInfo::NegativeExponent { .. } => String::from("negative exponent"),
+            RuntimeErrorInfo::IncompletePatternMatch => String::from("incomplete pattern match"),
+            RuntimeErrorInfo::UnexpectedTag { tag } => format!("unexpected tag {}", tag),
+            RuntimeErrorInfo::InvalidUtf8 { .. } => String::from("invalid UTF-8"),
+            RuntimeErrorInfo::Cmd { error } => error.to_string(),
+            RuntimeErrorInfo::ProcessFailed { program, status } => match status.code() {
+                Some(code) => format!("process {:?} exited with code {}", program, code),
+                None => format!("process {:?} terminated unexpectedly", program),
+            },
+            RuntimeErrorInfo::File { path, error } => format!("{}: {}", path, error),
+            RuntimeErrorInfo::Io { error } => error.to_string(),
+            RuntimeErrorInfo::Exit { code } => format!("exited with code {}", code),
+        }
+    }
+
+    pub fn addendum(&self) -> Option<String> {
+        match &self.info {
+            RuntimeErrorInfo::IndexOutOfBounds { index, length } => Some(format!(
+                "the index is {} but the length is {}",
+                index, length
+            )),
+            RuntimeErrorInfo::SliceOutOfBounds {
+                start,
+                length,
+                array_length,
+            } => Some(format!(
+                "the slice starts at {} and has length {}, but the array's length is {}",
+                start, length, array_length
+            )),
+            RuntimeErrorInfo::StringSliceOutOfBounds {
+                start,
+                length,
+                string_length,
+            } => Some(format!(
+                "the slice starts at {} and has length {}, but the string's length is {}",
+                start, length, string_length
+            )),
+            RuntimeErrorInfo::BytesSliceOutOfBounds {
+                start,
+                length,
+                bytes_length,
+            } => Some(format!(
+                "the slice starts at {} and has length {}, but there are {} bytes",
+                start, length, bytes_length
+            )),
+            RuntimeErrorInfo::NegativeExponent { exponent } => {
+                Some(format!("the exponent is {}", exponent))
             }
+            RuntimeErrorInfo::InvalidUtf8 { error } => Some(error.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn report(&self, diagnostic: &mut Diagnostic) {
+        diagnostic.item(
+            self.location.clone(),
+            Message {
+                content: self.message(),
+                addendum: self.addendum(),
+            },
+        )
+    }
+}
+
+impl From<io::Error> for RuntimeError {
+    fn from(error: io::Error) -> Self {
+        RuntimeError::new(RuntimeErrorInfo::Io { error })
+    }
+}
+
+impl From<cmd::Error> for RuntimeError {
+    fn from(error: cmd::Error) -> Self {
+        RuntimeError::new(RuntimeErrorInfo::Cmd { error })
+    }
+}
+
+/**
+Run a command to completion, failing if the command fails.
+
+The command's standard output is returned when `capture` is set.
+*/
+fn run_cmd(cmd: &cmd::Cmd, capture: bool) -> Result<Vec<u8>, RuntimeError> {
+    let finished = cmd.run(capture, false)?;
+    if finished.status.success() {
+        Ok(finished.stdout)
+    } else {
+        Err(RuntimeError::new(RuntimeErrorInfo::ProcessFailed {
+            program: finished.program,
+            status: finished.status,
+        }))
+    }
+}
+
+/**
+Turn the result of a checked integer operation into a value, failing if the
+operation overflowed.
+*/
+fn checked_int(result: Option<i64>) -> Result<Value, RuntimeError> {
+    result
+        .map(Value::Int)
+        .ok_or_else(|| RuntimeError::new(RuntimeErrorInfo::IntegerOverflow))
+}
+
+fn unpack_ordering(value: &Value) -> Result<Ordering, RuntimeError> {
+    let (tag, _) = value.unpack_variant();
+    match tag {
+        // Equal () : (| Equal : (), Greater : (), Less : () |)
+        0 => Ok(Ordering::Equal),
+        // Greater () : (| Equal : (), Greater : (), Less : () |)
+        1 => Ok(Ordering::Greater),
+        // Less () : (| Equal : (), Greater : (), Less : () |)
+        2 => Ok(Ordering::Less),
+        tag => Err(RuntimeError::new(RuntimeErrorInfo::UnexpectedTag {
+            tag: *tag,
+        })),
+    }
+}
+
+/// Compare two values using an ipso comparison function of type `a -> a -> Ordering`.
+fn compare_with(
+    interpreter: &mut Interpreter<'_>,
+    compare: &Value,
+    a: &Value,
+    b: &Value,
+) -> Result<Ordering, RuntimeError> {
+    unpack_ordering(
+        &compare
+            .apply(interpreter, a.clone())?
+            .apply(interpreter, b.clone())?,
+    )
+}
+
+/**
+Stable merge sort with a comparison function that can fail.
+
+[`slice::sort_by`] isn't used because ipso comparison functions can fail, and
+aren't guaranteed to be total orders.
+*/
+fn merge_sort_by<T: Clone, E>(
+    items: &[T],
+    compare: &mut impl FnMut(&T, &T) -> Result<Ordering, E>,
+) -> Result<Vec<T>, E> {
+    if items.len() <= 1 {
+        return Ok(items.to_vec());
+    }
+
+    let (left, right) = items.split_at(items.len() / 2);
+    let mut left = merge_sort_by(left, compare)?.into_iter().peekable();
+    let mut right = merge_sort_by(right, compare)?.into_iter().peekable();
+
+    let mut result = Vec::with_capacity(items.len());
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // Equal items are taken from the left, which keeps the sort stable.
+        let next = if compare(r, l)? == Ordering::Less {
+            right.next()
+        } else {
+            left.next()
+        };
+        result.extend(next);
+    }
+    result.extend(left);
+    result.extend(right);
+    Ok(result)
+}
+
+/**
+Apply a case mapping to a character, keeping the character unchanged when the mapping
+doesn't produce exactly one character (e.g. `'ß'.to_uppercase()` is `"SS"`).
+*/
+fn map_single_char<I: Iterator<Item = char>>(c: char, f: impl FnOnce(char) -> I) -> char {
+    let mut mapped = f(c);
+    match (mapped.next(), mapped.next()) {
+        (Some(mapped), None) => mapped,
+        _ => c,
+    }
+}
+
+/*
+At runtime, a `Json` is a variant of type
+
+```
+(|
+  Array : Array Json,
+  Bool : Bool,
+  Float : Float,
+  Int : Int,
+  Null : (),
+  Object : Array { key : String, value : Json },
+  String : String
+|)
+```
+
+which is what `json.view` exposes.
+*/
+const JSON_ARRAY: usize = 0;
+const JSON_BOOL: usize = 1;
+const JSON_FLOAT: usize = 2;
+const JSON_INT: usize = 3;
+const JSON_NULL: usize = 4;
+const JSON_OBJECT: usize = 5;
+const JSON_STRING: usize = 6;
+
+fn json_to_value(interpreter: &Interpreter<'_>, value: &json::Json) -> Value {
+    let (tag, arg) = match value {
+        json::Json::Null => (JSON_NULL, Value::Unit),
+        json::Json::Bool(b) => (JSON_BOOL, if *b { Value::True } else { Value::False }),
+        json::Json::Int(n) => (JSON_INT, Value::Int(*n)),
+        json::Json::Float(f) => (JSON_FLOAT, Value::Float(*f)),
+        json::Json::String(s) => (
+            JSON_STRING,
+            interpreter.alloc(Object::String(interpreter.alloc_str(s))),
+        ),
+        json::Json::Array(items) => {
+            let items =
+                interpreter.alloc_values(items.iter().map(|item| json_to_value(interpreter, item)));
+            (JSON_ARRAY, interpreter.alloc(Object::Array(items)))
+        }
+        json::Json::Object(members) => {
+            let members = interpreter.alloc_values(members.iter().map(|(key, value)| {
+                let key = interpreter.alloc(Object::String(interpreter.alloc_str(key)));
+                // { key : String, value : Json }
+                let fields = interpreter.alloc_values([key, json_to_value(interpreter, value)]);
+                interpreter.alloc(Object::Record(fields))
+            }));
+            (JSON_OBJECT, interpreter.alloc(Object::Array(members)))
         }
-        None => {
-            println!("process {:?} terminated unexpectedly", cmd);
-            process::exit(1);
+    };
+    interpreter.alloc(Object::Variant(tag, arg))
+}
+
+fn value_to_json(value: &Value) -> Result<json::Json, RuntimeError> {
+    let (tag, arg) = value.unpack_variant();
+    match *tag {
+        JSON_ARRAY => arg
+            .unpack_array()
+            .iter()
+            .map(value_to_json)
+            .collect::<Result<_, _>>()
+            .map(json::Json::Array),
+        JSON_BOOL => Ok(json::Json::Bool(arg.unpack_bool())),
+        JSON_FLOAT => Ok(json::Json::Float(arg.unpack_float())),
+        JSON_INT => Ok(json::Json::Int(arg.unpack_int())),
+        JSON_NULL => Ok(json::Json::Null),
+        JSON_OBJECT => arg
+            .unpack_array()
+            .iter()
+            .map(|member| {
+                let fields = member.unpack_record();
+                Ok((
+                    String::from(fields[0].unpack_string()),
+                    value_to_json(&fields[1])?,
+                ))
+            })
+            .collect::<Result<_, _>>()
+            .map(json::Json::Object),
+        JSON_STRING => Ok(json::Json::String(String::from(arg.unpack_string()))),
+        tag => Err(RuntimeError::new(RuntimeErrorInfo::UnexpectedTag { tag })),
+    }
+}
+
+/// Find the value of the first member of a JSON object's members with the given key.
+fn json_member(members: &[Value], key: &str) -> Option<Value> {
+    members.iter().find_map(|member| {
+        let fields = member.unpack_record();
+        if fields[0].unpack_string() == key {
+            Some(fields[1].clone())
+        } else {
+            None
+        }
+    })
+}
+
+/**
+Return `Some` of a `Json`'s contents when it was built with the constructor
+identified by `tag`, and `None` otherwise.
+*/
+fn json_as(interpreter: &Interpreter<'_>, value: &Value, tag: usize) -> Value {
+    match value.unpack_variant() {
+        // Some value : (| None : (), Some : a |)
+        (actual_tag, arg) if *actual_tag == tag => {
+            interpreter.alloc(Object::Variant(1, arg.clone()))
+        }
+        // None () : (| None : (), Some : a |)
+        _ => interpreter.alloc(Object::Variant(0, Value::Unit)),
+    }
+}
+
+/// Allocate a `(| None : (), Some : String |)`.
+fn alloc_string_option(interpreter: &Interpreter<'_>, string: Option<&str>) -> Value {
+    match string {
+        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+        Some(string) => {
+            let string = interpreter.alloc(Object::String(interpreter.alloc_str(string)));
+            interpreter.alloc(Object::Variant(1, string))
         }
     }
 }
 
+fn alloc_path_option(interpreter: &Interpreter<'_>, path: Option<&Path>) -> Value {
+    alloc_string_option(
+        interpreter,
+        path.map(|path| path.to_string_lossy()).as_deref(),
+    )
+}
+
+/// Whole seconds since the Unix epoch, which are negative for times before it.
+fn unix_seconds(time: std::time::SystemTime) -> i64 {
+    match time.duration_since(std::time::UNIX_EPOCH) {
+        Ok(duration) => i64::try_from(duration.as_secs()).unwrap_or(i64::MAX),
+        Err(err) => i64::try_from(err.duration().as_secs()).map_or(i64::MIN, |secs| -secs),
+    }
+}
+
+/**
+Integer division that rounds towards negative infinity, returning [`None`] if `b` is
+zero or the division overflowed.
+*/
+fn checked_div_floor(a: i64, b: i64) -> Option<i64> {
+    let quotient = a.checked_div(b)?;
+    if a % b != 0 && (a < 0) != (b < 0) {
+        // The remainder is non-zero, so `quotient` is closer to zero than `a` and this can't overflow.
+        Some(quotient - 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/**
+The remainder of [`checked_div_floor`], which has the same sign as `b`, so that
+`div a b * b + mod a b == a`. `b` must not be zero.
+*/
+fn mod_floor(a: i64, b: i64) -> i64 {
+    // `i64::MIN % -1` is the only remainder that overflows, and its result is 0.
+    let remainder = a.wrapping_rem(b);
+    if remainder != 0 && (remainder < 0) != (b < 0) {
+        remainder + b
+    } else {
+        remainder
+    }
+}
+
+/**
+The substring of `string` that starts at character `start` and is `length` characters long.
+*/
+fn slice_chars(string: &str, start: usize, length: usize) -> Option<&str> {
+    // The byte offset of the character at each index, and of the end of the string.
+    let mut offsets = string
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(string.len()));
+    let start_offset = offsets.nth(start)?;
+    let end_offset = if length == 0 {
+        start_offset
+    } else {
+        offsets.nth(length - 1)?
+    };
+    Some(&string[start_offset..end_offset])
+}
+
+/**
+Raise `base` to a non-negative `exponent`, returning [`None`] if the result overflowed.
+*/
+fn checked_pow(base: i64, exponent: i64) -> Option<i64> {
+    match u32::try_from(exponent) {
+        Ok(exponent) => base.checked_pow(exponent),
+        // Only 0, 1 and -1 can be raised to such a large power without overflowing.
+        Err(_) => match base {
+            0 | 1 => Some(base),
+            -1 => Some(if exponent % 2 == 0 { 1 } else { -1 }),
+            _ => None,
+        },
+    }
+}
+
+/**
+Set an environment variable, failing in the cases where [`std::env::set_var`]
+would panic.
+*/
+fn set_var(key: &str, value: &str) -> io::Result<()> {
+    if key.is_empty() || key.contains(|c| c == '=' || c == '\0') {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid environment variable name {:?}", key),
+        ))
+    } else if value.contains('\0') {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid value for environment variable {:?}", key),
+        ))
+    } else {
+        std::env::set_var(key, value);
+        Ok(())
+    }
+}
+
 macro_rules! function1 {
     ($name:ident, $self:expr, $body:expr) => {{
         paste! {
@@ -45,7 +525,7 @@ macro_rules! function1 {
                 eval: &mut Interpreter<'_>,
                 env: Rc<[Value]>,
                 arg: Value,
-            ) -> Value {
+            ) -> Result<Value, RuntimeError> {
                 // This clippy lint is `allow`ed because requiring `$body` to be a function
                 // seems to be the only way to make sure it's scope-checked.
                 #[allow(clippy::redundant_closure_call)]
@@ -72,7 +552,7 @@ macro_rules! function2 {
                         eval: &mut Interpreter<'_>,
                         env: Rc<[Value]>,
                         arg: Value,
-                    ) -> Value {
+                    ) -> Result<Value, RuntimeError> {
                         $body(eval, env, arg)
                     }
                     let env = eval.alloc_values({
@@ -80,10 +560,10 @@ macro_rules! function2 {
                         env.push(arg);
                         env
                     });
-                    eval.alloc(Object::StaticClosure {
+                    Ok(eval.alloc(Object::StaticClosure {
                         env,
                         body: StaticClosureBody([<$name _code_1>]),
-                    })
+                    }))
                 }
             })
         )
@@ -101,7 +581,7 @@ macro_rules! function3 {
                         eval: &mut Interpreter<'_>,
                         env: Rc<[Value]>,
                         arg: Value,
-                    ) -> Value {
+                    ) -> Result<Value, RuntimeError> {
                         $body(eval, env, arg)
                     }
                     let env = eval.alloc_values({
@@ -109,10 +589,39 @@ macro_rules! function3 {
                         env.push(arg);
                         env
                     });
-                    eval.alloc(Object::StaticClosure {
+                    Ok(eval.alloc(Object::StaticClosure {
                         env,
                         body: StaticClosureBody([<$name _code_2>]),
-                    })
+                    }))
+                }
+            })
+        )
+    }};
+}
+
+macro_rules! function4 {
+    ($name:ident, $self:expr, $body:expr) => {{
+        function3!(
+            $name,
+            $self,
+            (|eval: &mut Interpreter<'_>, env: Rc<[Value]>, arg| {
+                paste! {
+                    fn [<$name _code_3>](
+                        eval: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                        arg: Value,
+                    ) -> Result<Value, RuntimeError> {
+                        $body(eval, env, arg)
+                    }
+                    let env = eval.alloc_values({
+                        let mut env = Vec::from(env.as_ref());
+                        env.push(arg);
+                        env
+                    });
+                    Ok(eval.alloc(Object::StaticClosure {
+                        env,
+                        body: StaticClosureBody([<$name _code_3>]),
+                    }))
                 }
             })
         )
@@ -120,7 +629,9 @@ macro_rules! function3 {
 }
 
 #[derive(Clone)]
-pub struct StaticClosureBody(fn(&mut Interpreter<'_>, Rc<[Value]>, Value) -> Value);
+pub struct StaticClosureBody(
+    fn(&mut Interpreter<'_>, Rc<[Value]>, Value) -> Result<Value, RuntimeError>,
+);
 
 impl Debug for StaticClosureBody {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -129,10 +640,14 @@ impl Debug for StaticClosureBody {
 }
 
 #[derive(Clone)]
-pub struct IOBody(fn(&mut Interpreter<'_>, Rc<[Value]>) -> Value);
+pub struct IOBody(fn(&mut Interpreter<'_>, Rc<[Value]>) -> Result<Value, RuntimeError>);
 
 impl IOBody {
-    pub fn run(&self, interpreter: &mut Interpreter<'_>, env: Rc<[Value]>) -> Value {
+    pub fn run(
+        &self,
+        interpreter: &mut Interpreter<'_>,
+        env: Rc<[Value]>,
+    ) -> Result<Value, RuntimeError> {
         self.0(interpreter, env)
     }
 }
@@ -172,6 +687,19 @@ impl Env {
         }
     }
 
+    /// Remove the variables that were pushed after the environment had `len` variables.
+    fn truncate(&mut self, len: usize) {
+        match self {
+            Env::Empty => {}
+            Env::Borrowed(vs) => {
+                if len < vs.len() {
+                    *self = Env::Owned(vs[..len].to_vec());
+                }
+            }
+            Env::Owned(vs) => vs.truncate(len),
+        }
+    }
+
     fn len(&self) -> usize {
         match self {
             Env::Empty => 0,
@@ -210,6 +738,11 @@ pub enum Object {
     Variant(usize, Value),
     Array(Rc<[Value]>),
     Record(Rc<[Value]>),
+    /// A `Map k v`, whose keys are ordered by `Ord k`.
+    Map(map::Map<Value, Value>),
+    /// A `Set a`, whose elements are ordered by `Ord a`.
+    Set(map::Map<Value, ()>),
+    Regex(regex::Regex),
     Closure {
         env: Rc<[Value]>,
         arg: bool,
@@ -229,7 +762,17 @@ pub enum Object {
         env: Rc<[Value]>,
         body: IOBody,
     },
-    Cmd(Vec<Rc<str>>),
+    /**
+    An IO action, annotated with the position of the expression that produced it.
+
+    Runtime errors that occur while performing `io` are reported at this position.
+    */
+    LocatedIO {
+        module: Option<ModuleId>,
+        pos: usize,
+        io: Value,
+    },
+    Cmd(cmd::Cmd),
 }
 
 impl Object {
@@ -240,9 +783,15 @@ impl Object {
         }
     }
 
-    pub fn perform_io<'io>(&self, interpreter: &mut Interpreter<'io>) -> Value {
+    pub fn perform_io<'io>(
+        &self,
+        interpreter: &mut Interpreter<'io>,
+    ) -> Result<Value, RuntimeError> {
         match self {
             Object::IO { env, body } => body.0(interpreter, env.clone()),
+            Object::LocatedIO { module, pos, io } => io
+                .perform_io(interpreter)
+                .map_err(|error| interpreter.locate(error, *module, *pos)),
             val => panic!("expected io, got {:?}", val),
         }
     }
@@ -275,14 +824,39 @@ impl Object {
         }
     }
 
-    pub fn unpack_cmd(&self) -> &[Rc<str>] {
+    pub fn unpack_map(&self) -> &map::Map<Value, Value> {
+        match self {
+            Object::Map(entries) => entries,
+            val => panic!("expected map, got {:?}", val),
+        }
+    }
+
+    pub fn unpack_set(&self) -> &map::Map<Value, ()> {
+        match self {
+            Object::Set(items) => items,
+            val => panic!("expected set, got {:?}", val),
+        }
+    }
+
+    pub fn unpack_regex(&self) -> &regex::Regex {
+        match self {
+            Object::Regex(regex) => regex,
+            val => panic!("expected regex, got {:?}", val),
+        }
+    }
+
+    pub fn unpack_cmd(&self) -> &cmd::Cmd {
         match self {
-            Object::Cmd(values) => values,
+            Object::Cmd(cmd) => cmd,
             val => panic!("expected command, got {:?}", val),
         }
     }
 
-    pub fn apply<'io>(&self, interpreter: &mut Interpreter<'io>, arg: Value) -> Value {
+    pub fn apply<'io>(
+        &self,
+        interpreter: &mut Interpreter<'io>,
+        arg: Value,
+    ) -> Result<Value, RuntimeError> {
         match self {
             Object::Closure {
                 env,
@@ -313,7 +887,7 @@ impl Object {
         match self {
             Object::Closure { .. } => String::from("<closure>"),
             Object::StaticClosure { env: _, body: _ } => String::from("<static builtin>"),
-            Object::IO { env: _, body: _ } => String::from("<io>"),
+            Object::IO { env: _, body: _ } | Object::LocatedIO { .. } => String::from("<io>"),
             Object::String(s) => format!("{:?}", s),
             Object::Bytes(bs) => format!("{:?}", bs),
             Object::Array(items) => {
@@ -359,7 +933,19 @@ impl Object {
                 s.push(')');
                 s
             }
-            Object::Cmd(parts) => format!("Cmd({:?})", parts),
+            Object::Map(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{} => {}", key.render(), value.render()))
+                    .collect();
+                format!("Map({{ {} }})", entries.join(", "))
+            }
+            Object::Set(items) => {
+                let items: Vec<String> = items.iter().map(|(item, ())| item.render()).collect();
+                format!("Set({{ {} }})", items.join(", "))
+            }
+            Object::Regex(regex) => format!("Regex({:?})", regex.as_str()),
+            Object::Cmd(cmd) => format!("Cmd({:?})", cmd),
         }
     }
 }
@@ -411,12 +997,32 @@ impl PartialEq for Object {
                 Object::Record(items2) => items == items2,
                 _ => false,
             },
+            Object::Map(entries) => match other {
+                Object::Map(entries2) => entries == entries2,
+                _ => false,
+            },
+            Object::Set(items) => match other {
+                Object::Set(items2) => items == items2,
+                _ => false,
+            },
+            Object::Regex(regex) => match other {
+                Object::Regex(regex2) => regex.as_str() == regex2.as_str(),
+                _ => false,
+            },
             Object::Variant(tag, value) => match other {
                 Object::Variant(tag2, value2) => tag == tag2 && value == value2,
                 _ => false,
             },
-            Object::Cmd(parts) => match other {
-                Object::Cmd(parts2) => parts == parts2,
+            Object::LocatedIO { module, pos, io } => match other {
+                Object::LocatedIO {
+                    module: module2,
+                    pos: pos2,
+                    io: io2,
+                } => module == module2 && pos == pos2 && io == io2,
+                _ => false,
+            },
+            Object::Cmd(cmd) => match other {
+                Object::Cmd(cmd2) => cmd == cmd2,
                 _ => false,
             },
         }
@@ -427,7 +1033,8 @@ impl PartialEq for Object {
 pub enum Value {
     True,
     False,
-    Int(i32),
+    Int(i64),
+    Float(f64),
     Char(char),
     Unit,
 
@@ -435,11 +1042,18 @@ pub enum Value {
 }
 
 impl Value {
-    pub fn apply<'io>(&self, interpreter: &mut Interpreter<'io>, arg: Value) -> Value {
+    pub fn apply<'io>(
+        &self,
+        interpreter: &mut Interpreter<'io>,
+        arg: Value,
+    ) -> Result<Value, RuntimeError> {
         self.unpack_object().apply(interpreter, arg)
     }
 
-    pub fn perform_io<'io>(&self, interpreter: &mut Interpreter<'io>) -> Value {
+    pub fn perform_io<'io>(
+        &self,
+        interpreter: &mut Interpreter<'io>,
+    ) -> Result<Value, RuntimeError> {
         self.unpack_object().perform_io(interpreter)
     }
 
@@ -459,7 +1073,19 @@ impl Value {
         self.unpack_object().unpack_variant()
     }
 
-    pub fn unpack_cmd(&self) -> &[Rc<str>] {
+    pub fn unpack_map(&self) -> &map::Map<Value, Value> {
+        self.unpack_object().unpack_map()
+    }
+
+    pub fn unpack_set(&self) -> &map::Map<Value, ()> {
+        self.unpack_object().unpack_set()
+    }
+
+    pub fn unpack_regex(&self) -> &regex::Regex {
+        self.unpack_object().unpack_regex()
+    }
+
+    pub fn unpack_cmd(&self) -> &cmd::Cmd {
         self.unpack_object().unpack_cmd()
     }
 
@@ -478,13 +1104,20 @@ impl Value {
         }
     }
 
-    pub fn unpack_int(&self) -> i32 {
+    pub fn unpack_int(&self) -> i64 {
         match self {
             Value::Int(n) => *n,
             val => panic!("expected int, got {:?}", val),
         }
     }
 
+    pub fn unpack_float(&self) -> f64 {
+        match self {
+            Value::Float(n) => *n,
+            val => panic!("expected float, got {:?}", val),
+        }
+    }
+
     pub fn unpack_char(&self) -> char {
         match self {
             Value::Char(c) => *c,
@@ -501,6 +1134,7 @@ impl Value {
             Value::True => String::from("true"),
             Value::False => String::from("false"),
             Value::Int(n) => format!("{:?}", n),
+            Value::Float(n) => format!("{:?}", n),
             Value::Char(c) => format!("{:?}", c),
             Value::Unit => String::from("()"),
             Value::Object(o) => o.render(),
@@ -517,6 +1151,10 @@ impl PartialEq for Value {
                 Value::Int(n2) => n == n2,
                 _ => false,
             },
+            Value::Float(n) => match other {
+                Value::Float(n2) => n == n2,
+                _ => false,
+            },
             Value::Char(c) => match other {
                 Value::Char(c2) => c == c2,
                 _ => false,
@@ -531,12 +1169,14 @@ impl PartialEq for Value {
 }
 
 pub struct Module {
+    pub source: Source,
     pub bindings: Bindings,
 }
 
 struct Context {
     modules: Vec<ModuleId>,
     base: Bindings,
+    source: Source,
 }
 
 pub struct Interpreter<'io> {
@@ -552,14 +1192,25 @@ impl<'io> Interpreter<'io> {
         stdout: &'io mut dyn io::Write,
         common_kinds: &CommonKinds,
         modules: &Modules<core::Module>,
+        source: &Source,
         context: &HashMap<Name, ipso_core::Binding>,
     ) -> Self {
         let modules = modules
-            .iter_ids()
-            .map(|(module_id, module)| {
+            .iter_keys()
+            .map(|(module_key, module)| {
+                let module_id = modules.lookup_id(module_key).unwrap();
+                let source = match module_key {
+                    ModuleKey::Path(path) | ModuleKey::Library { path, .. } => Source::File {
+                        path: PathBuf::from(path),
+                    },
+                    ModuleKey::Name(name) => Source::Interactive {
+                        label: name.clone(),
+                    },
+                };
                 (
                     module_id,
                     Module {
+                        source,
                         bindings: Bindings::from(module.get_bindings(common_kinds)),
                     },
                 )
@@ -572,11 +1223,32 @@ impl<'io> Interpreter<'io> {
             context: Context {
                 modules: Vec::new(),
                 base: Bindings::from(context.clone()),
+                source: source.clone(),
             },
             modules,
         }
     }
 
+    /// Attach a location to an error that doesn't have one.
+    fn locate(
+        &self,
+        mut error: RuntimeError,
+        module: Option<ModuleId>,
+        pos: usize,
+    ) -> RuntimeError {
+        if error.location.is_none() {
+            let source = match module {
+                None => &self.context.source,
+                Some(module) => &self.modules[&module].source,
+            };
+            error.location = Some(Location {
+                source: source.clone(),
+                offset: Some(pos),
+            });
+        }
+        error
+    }
+
     pub fn alloc(&self, obj: Object) -> Value {
         Value::Object(Rc::new(obj))
     }
@@ -596,6 +1268,41 @@ where {
         Rc::from(values)
     }
 
+    /**
+    Allocate an `IOError`.
+
+    ```text
+    IOError =
+      {
+        kind : (| AlreadyExists : (), InvalidData : (), InvalidInput : (), NotFound : (), Other : (), PermissionDenied : () |),
+        message : String
+      }
+    ```
+    */
+    pub fn alloc_io_error(&self, error: &io::Error) -> Value {
+        let kind = match error.kind() {
+            io::ErrorKind::AlreadyExists => 0,
+            io::ErrorKind::InvalidData => 1,
+            io::ErrorKind::InvalidInput => 2,
+            io::ErrorKind::NotFound => 3,
+            io::ErrorKind::PermissionDenied => 5,
+            _ => 4,
+        };
+        let kind = self.alloc(Object::Variant(kind, Value::Unit));
+        let message = self.alloc(Object::String(self.alloc_str(&error.to_string())));
+        self.alloc(Object::Record(self.alloc_values([kind, message])))
+    }
+
+    /// Allocate a `(| Ok : a, Err : IOError |)`.
+    pub fn alloc_io_result(&self, result: io::Result<Value>) -> Value {
+        match result {
+            // Err error : (| Err : IOError, Ok : a |)
+            Err(error) => self.alloc(Object::Variant(0, self.alloc_io_error(&error))),
+            // Ok value : (| Err : IOError, Ok : a |)
+            Ok(value) => self.alloc(Object::Variant(1, value)),
+        }
+    }
+
     pub fn alloc_ordering(&self, ordering: Ordering) -> Value {
         match ordering {
             std::cmp::Ordering::Less => {
@@ -620,8 +1327,11 @@ where {
                     pure,
                     self,
                     |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn pure_io_1(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
-                            env[0].clone()
+                        fn pure_io_1(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
+                            Ok(env[0].clone())
                         }
                         let env = interpreter.alloc_values({
                             let mut env = Vec::from(env.as_ref());
@@ -632,7 +1342,7 @@ where {
                             env,
                             body: IOBody(pure_io_1),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
@@ -641,10 +1351,13 @@ where {
                     map_io,
                     self,
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        fn map_io_2(interpreter: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn map_io_2(
+                            interpreter: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             let f = env[0].clone();
                             let io_a = env[1].clone();
-                            let a = io_a.perform_io(interpreter); // type: a
+                            let a = io_a.perform_io(interpreter)?; // type: a
                             f.apply(interpreter, a) // type: b
                         }
                         let env = interpreter.alloc_values({
@@ -656,7 +1369,7 @@ where {
                             env,
                             body: IOBody(map_io_2),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
@@ -665,11 +1378,14 @@ where {
                     bind_io,
                     self,
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        fn bind_io_2(interpreter: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn bind_io_2(
+                            interpreter: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             let io_a = env[0].clone();
                             let f = env[1].clone();
-                            let a = io_a.perform_io(interpreter); // type: a
-                            let io_b = f.apply(interpreter, a); // type: IO b
+                            let a = io_a.perform_io(interpreter)?; // type: a
+                            let io_b = f.apply(interpreter, a)?; // type: IO b
                             io_b.perform_io(interpreter) // type: b
                         }
                         let env = interpreter.alloc_values({
@@ -682,7 +1398,7 @@ where {
                             env,
                             body: IOBody(bind_io_2),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
@@ -691,8 +1407,8 @@ where {
                     trace,
                     self,
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        writeln!(interpreter.stdout, "trace: {}", env[0].render()).unwrap();
-                        arg
+                        writeln!(interpreter.stdout, "trace: {}", env[0].render())?;
+                        Ok(arg)
                     }
                 )
             }
@@ -702,7 +1418,7 @@ where {
                     self,
                     |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
                         let a = arg.unpack_string();
-                        interpreter.alloc(Object::Bytes(Rc::from(a.as_bytes())))
+                        Ok(interpreter.alloc(Object::Bytes(Rc::from(a.as_bytes()))))
                     }
                 )
             }
@@ -711,12 +1427,14 @@ where {
                     println,
                     self,
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        fn println(interpreter: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn println(
+                            interpreter: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             // env[0] : String
                             let value = env[0].unpack_string();
-                            writeln!(interpreter.stdout, "{}", value)
-                                .expect("writing to stdout failed");
-                            Value::Unit
+                            writeln!(interpreter.stdout, "{}", value)?;
+                            Ok(Value::Unit)
                         }
                         let env = interpreter.alloc_values({
                             let mut env = Vec::from(env.as_ref());
@@ -724,10 +1442,10 @@ where {
                             env
                         });
 
-                        interpreter.alloc(Object::IO {
+                        Ok(interpreter.alloc(Object::IO {
                             env,
                             body: IOBody(println),
-                        })
+                        }))
                     }
                 )
             }
@@ -736,15 +1454,17 @@ where {
                     print,
                     self,
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        fn print(interpreter: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn print(
+                            interpreter: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             // env[0] : String
                             let value = env[0].unpack_string();
                             {
-                                write!(interpreter.stdout, "{}", value)
-                                    .expect("writing to stdout failed");
-                                interpreter.stdout.flush().expect("flushing stdout failed");
+                                write!(interpreter.stdout, "{}", value)?;
+                                interpreter.stdout.flush()?;
                             }
-                            Value::Unit
+                            Ok(Value::Unit)
                         }
                         let env = interpreter.alloc_values({
                             let mut env = Vec::from(env.as_ref());
@@ -752,20 +1472,23 @@ where {
                             env
                         });
 
-                        interpreter.alloc(Object::IO {
+                        Ok(interpreter.alloc(Object::IO {
                             env,
                             body: IOBody(print),
-                        })
+                        }))
                     }
                 )
             }
             Builtin::Readln => {
-                fn readln(interpreter: &mut Interpreter, _: Rc<[Value]>) -> Value {
+                fn readln(
+                    interpreter: &mut Interpreter,
+                    _: Rc<[Value]>,
+                ) -> Result<Value, RuntimeError> {
                     // env[0] : Stdin
                     let mut str = String::new();
-                    let _ = interpreter.stdin.read_line(&mut str).unwrap();
+                    let _ = interpreter.stdin.read_line(&mut str)?;
                     let str = interpreter.alloc_str(&str);
-                    interpreter.alloc(Object::String(str))
+                    Ok(interpreter.alloc(Object::String(str)))
                 }
 
                 self.alloc(Object::IO {
@@ -780,11 +1503,7 @@ where {
                     |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let a = env[0].unpack_string();
                         let b = arg.unpack_string();
-                        if a == b {
-                            Value::True
-                        } else {
-                            Value::False
-                        }
+                        Ok(if a == b { Value::True } else { Value::False })
                     }
                 )
             }
@@ -795,7 +1514,7 @@ where {
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let a = env[0].unpack_string();
                         let b = arg.unpack_string();
-                        interpreter.alloc_ordering(a.cmp(b))
+                        Ok(interpreter.alloc_ordering(a.cmp(b)))
                     }
                 )
             }
@@ -806,11 +1525,7 @@ where {
                     |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let a = env[0].unpack_int();
                         let b = arg.unpack_int();
-                        if a == b {
-                            Value::True
-                        } else {
-                            Value::False
-                        }
+                        Ok(if a == b { Value::True } else { Value::False })
                     }
                 )
             }
@@ -821,7 +1536,7 @@ where {
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let a = env[0].unpack_int();
                         let b = arg.unpack_int();
-                        interpreter.alloc_ordering(a.cmp(&b))
+                        Ok(interpreter.alloc_ordering(a.cmp(&b)))
                     }
                 )
             }
@@ -832,7 +1547,7 @@ where {
                     |eval: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
                         let a = arg.unpack_int();
                         let str = eval.alloc_str(&format!("{}", a));
-                        eval.alloc(Object::String(str))
+                        Ok(eval.alloc(Object::String(str)))
                     }
                 )
             }
@@ -849,8 +1564,8 @@ where {
                         if a.len() == b.len() {
                             for (a, b) in a.iter().zip(b.iter()) {
                                 let res = f
-                                    .apply(eval, a.clone())
-                                    .apply(eval, b.clone())
+                                    .apply(eval, a.clone())?
+                                    .apply(eval, b.clone())?
                                     .unpack_bool();
                                 if !res {
                                     acc = Value::False;
@@ -860,7 +1575,7 @@ where {
                         } else {
                             acc = Value::False;
                         }
-                        acc
+                        Ok(acc)
                     }
                 )
             }
@@ -879,26 +1594,13 @@ where {
                         let mut ordering = Ordering::Equal;
 
                         loop {
-                            fn unpack_ordering(value: &Value) -> Ordering {
-                                let (tag, _) = value.unpack_variant();
-                                match tag {
-                                    // Equal () : (| Equal : (), Greater : (), Less : () |)
-                                    0 => Ordering::Equal,
-                                    // Greater () : (| Equal : (), Greater : (), Less : () |)
-                                    1 => Ordering::Greater,
-                                    // Less () : (| Equal : (), Greater : (), Less : () |)
-                                    2 => Ordering::Less,
-                                    tag => panic!("unexpected tag {}", tag),
-                                }
-                            }
-
                             if index < a_len {
                                 if index < b_len {
                                     // precondition: a[0..index] == b[0..index]
                                     match unpack_ordering(
-                                        &f.apply(interpreter, a[index].clone())
-                                            .apply(interpreter, b[index].clone()),
-                                    ) {
+                                        &f.apply(interpreter, a[index].clone())?
+                                            .apply(interpreter, b[index].clone())?,
+                                    )? {
                                         Ordering::Less => {
                                             ordering = Ordering::Less;
                                             break;
@@ -929,7 +1631,7 @@ where {
                                 break;
                             }
                         }
-                        interpreter.alloc_ordering(ordering)
+                        Ok(interpreter.alloc_ordering(ordering))
                     }
                 )
             }
@@ -944,9 +1646,9 @@ where {
 
                         let mut acc = z;
                         for el in arr.as_ref() {
-                            acc = f.apply(eval, acc).apply(eval, el.clone());
+                            acc = f.apply(eval, acc)?.apply(eval, el.clone())?;
                         }
-                        acc
+                        Ok(acc)
                     }
                 )
             }
@@ -958,14 +1660,14 @@ where {
                         let len = env[0].unpack_int();
                         let f = arg;
 
-                        let mut array = Vec::with_capacity(len as usize);
+                        let mut array = Vec::with_capacity(usize::try_from(len).unwrap_or(0));
                         for ix in 0..len {
                             let ix = Value::Int(ix);
-                            array.push(f.apply(eval, ix));
+                            array.push(f.apply(eval, ix)?);
                         }
 
                         let array = eval.alloc_values(array);
-                        eval.alloc(Object::Array(array))
+                        Ok(eval.alloc(Object::Array(array)))
                     }
                 )
             }
@@ -976,7 +1678,7 @@ where {
                     |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
                         let arr = arg.unpack_array();
 
-                        Value::Int(arr.len() as i32)
+                        Ok(Value::Int(arr.len() as i64))
                     }
                 )
             }
@@ -985,10 +1687,16 @@ where {
                     index_array,
                     self,
                     |_eval: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        let ix = env[0].unpack_int() as usize;
+                        let ix = env[0].unpack_int();
                         let arr = arg.unpack_array();
 
-                        arr[ix].clone()
+                        match usize::try_from(ix).ok().and_then(|ix| arr.get(ix)) {
+                            Some(value) => Ok(value.clone()),
+                            None => Err(RuntimeError::new(RuntimeErrorInfo::IndexOutOfBounds {
+                                index: ix,
+                                length: arr.len(),
+                            })),
+                        }
                     }
                 )
             }
@@ -997,11 +1705,22 @@ where {
                     slice_array,
                     self,
                     |eval: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        let start = env[0].unpack_int() as usize;
-                        let len = env[1].unpack_int() as usize;
+                        let start = env[0].unpack_int();
+                        let len = env[1].unpack_int();
                         let arr = arg.unpack_array();
 
-                        eval.alloc(Object::Array(Rc::from(&arr[start..start + len])))
+                        let slice = usize::try_from(start)
+                            .ok()
+                            .zip(usize::try_from(len).ok())
+                            .and_then(|(start, len)| arr.get(start..start.checked_add(len)?));
+                        match slice {
+                            Some(slice) => Ok(eval.alloc(Object::Array(Rc::from(slice)))),
+                            None => Err(RuntimeError::new(RuntimeErrorInfo::SliceOutOfBounds {
+                                start,
+                                length: len,
+                                array_length: arr.len(),
+                            })),
+                        }
                     }
                 )
             }
@@ -1012,15 +1731,14 @@ where {
                     |eval: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let predicate = env[0].clone();
                         let string = arg.unpack_string();
-                        let new_string: String = string
-                            .chars()
-                            .filter(|c| {
-                                let c_val = Value::Char(*c);
-                                predicate.apply(eval, c_val).unpack_bool()
-                            })
-                            .collect();
+                        let mut new_string = String::new();
+                        for c in string.chars() {
+                            if predicate.apply(eval, Value::Char(c))?.unpack_bool() {
+                                new_string.push(c);
+                            }
+                        }
                         let str = eval.alloc_str(&new_string);
-                        eval.alloc(Object::String(str))
+                        Ok(eval.alloc(Object::String(str)))
                     }
                 )
             }
@@ -1031,11 +1749,7 @@ where {
                     |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let c1 = env[0].unpack_char();
                         let c2 = arg.unpack_char();
-                        if c1 == c2 {
-                            Value::True
-                        } else {
-                            Value::False
-                        }
+                        Ok(if c1 == c2 { Value::True } else { Value::False })
                     }
                 )
             }
@@ -1046,7 +1760,7 @@ where {
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let c1 = env[0].unpack_char();
                         let c2 = arg.unpack_char();
-                        interpreter.alloc_ordering(c1.cmp(&c2))
+                        Ok(interpreter.alloc_ordering(c1.cmp(&c2)))
                     }
                 )
             }
@@ -1060,7 +1774,7 @@ where {
                         let a = eval.alloc_values(
                             s.split(c).map(|s| eval.alloc(Object::String(Rc::from(s)))),
                         );
-                        eval.alloc(Object::Array(a))
+                        Ok(eval.alloc(Object::Array(a)))
                     }
                 )
             }
@@ -1072,7 +1786,7 @@ where {
                         let sep = env[0].unpack_string();
                         let strings = arg.unpack_array();
                         if strings.is_empty() {
-                            eval.alloc(Object::String(Rc::from("")))
+                            Ok(eval.alloc(Object::String(Rc::from(""))))
                         } else {
                             let mut joined = String::from(strings[0].unpack_string());
                             for string in &strings[1..] {
@@ -1080,7 +1794,7 @@ where {
                                 joined.push_str(string.unpack_string());
                             }
                             let joined = eval.alloc_str(&joined);
-                            eval.alloc(Object::String(joined))
+                            Ok(eval.alloc(Object::String(joined)))
                         }
                     }
                 )
@@ -1095,9 +1809,9 @@ where {
                         let s = arg.unpack_string();
                         for c in s.chars() {
                             let c_value = Value::Char(c);
-                            acc = f.apply(eval, acc).apply(eval, c_value);
+                            acc = f.apply(eval, acc)?.apply(eval, c_value)?;
                         }
-                        acc
+                        Ok(acc)
                     }
                 )
             }
@@ -1112,7 +1826,7 @@ where {
                             new_array.push(arg);
                             new_array
                         });
-                        eval.alloc(Object::Array(new_array))
+                        Ok(eval.alloc(Object::Array(new_array)))
                     }
                 )
             }
@@ -1121,25 +1835,13 @@ where {
                     run,
                     self,
                     |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
-                        fn run_1(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn run_1(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             let cmd = env[0].unpack_cmd();
-                            if cmd.is_empty() {
-                                Value::Unit
-                            } else {
-                                let status = process::Command::new(cmd[0].as_ref())
-                                    .args(
-                                        cmd[1..].iter().map(|arg| arg.as_ref()).collect::<Vec<_>>(),
-                                    )
-                                    .status()
-                                    .unwrap_or_else(|err| {
-                                        println!("failed to start process {:?}: {}", cmd[0], err);
-                                        process::exit(1);
-                                    });
-
-                                check_exit_status(&cmd[0], &status);
-
-                                Value::Unit
-                            }
+                            run_cmd(cmd, false)?;
+                            Ok(Value::Unit)
                         }
                         let env = interpreter.alloc_values({
                             let mut env = Vec::from(env.as_ref());
@@ -1150,7 +1852,7 @@ where {
                             env,
                             body: IOBody(run_1),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
@@ -1160,123 +1862,158 @@ where {
                 |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                     let a = env[0].unpack_bool();
                     let b = arg.unpack_bool();
-                    if a == b {
-                        Value::True
-                    } else {
-                        Value::False
-                    }
+                    Ok(if a == b { Value::True } else { Value::False })
                 }
             ),
             Builtin::Lines => function1!(
                 lines,
                 self,
                 |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
-                    fn lines_io_body(interpreter: &mut Interpreter<'_>, env: Rc<[Value]>) -> Value {
-                        let cmd: &[Rc<str>] = env[0].unpack_cmd();
-                        if cmd.is_empty() {
-                            Value::Unit
-                        } else {
-                            let output = process::Command::new(cmd[0].as_ref())
-                                .args(cmd[1..].iter().map(|arg| arg.as_ref()))
-                                .stdin(Stdio::inherit())
-                                .stdout(Stdio::piped())
-                                .stderr(Stdio::inherit())
-                                .output()
-                                .unwrap_or_else(|err| {
-                                    panic!("failed to start process {:?}: {}", cmd[0], err)
-                                });
-
-                            check_exit_status(&cmd[0], &output.status);
-
-                            let lines: Vec<Value> = output
-                                .stdout
-                                .lines()
-                                .map(|line| {
-                                    let line = line.unwrap_or_else(|err| {
-                                        panic!("failed to decode line: {}", err)
-                                    });
-                                    let line = interpreter.alloc_str(&line);
-                                    interpreter.alloc(Object::String(line))
-                                })
-                                .collect();
+                    fn lines_io_body(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let stdout = run_cmd(env[0].unpack_cmd(), true)?;
+                        let stdout = std::str::from_utf8(&stdout).map_err(|err| {
+                            RuntimeError::new(RuntimeErrorInfo::InvalidUtf8 {
+                                error: err.to_string(),
+                            })
+                        })?;
 
-                            let lines = interpreter.alloc_values(lines);
-                            interpreter.alloc(Object::Array(lines))
-                        }
+                        let lines: Vec<Value> = stdout
+                            .lines()
+                            .map(|line| {
+                                let line = interpreter.alloc_str(line);
+                                interpreter.alloc(Object::String(line))
+                            })
+                            .collect();
+
+                        let lines = interpreter.alloc_values(lines);
+                        Ok(interpreter.alloc(Object::Array(lines)))
                     }
 
                     let env = interpreter.alloc_values([arg]);
-                    interpreter.alloc(Object::IO {
+                    Ok(interpreter.alloc(Object::IO {
                         env,
                         body: IOBody(lines_io_body),
-                    })
+                    }))
                 }
             ),
             Builtin::CmdRead => function1!(
                 cmd_read,
                 self,
                 |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
-                    fn cmd_read_io(interpreter: &mut Interpreter<'_>, env: Rc<[Value]>) -> Value {
-                        let cmd: &[Rc<str>] = env[0].unpack_cmd();
-                        if cmd.is_empty() {
-                            interpreter.alloc(Object::String(Rc::from("")))
-                        } else {
-                            let output = process::Command::new(cmd[0].as_ref())
-                                .args(cmd[1..].iter().map(|arg| arg.as_ref()))
-                                .stdin(Stdio::inherit())
-                                .stdout(Stdio::piped())
-                                .stderr(Stdio::inherit())
-                                .output()
-                                .unwrap_or_else(|err| {
-                                    panic!("failed to start process {:?}: {}", cmd[0], err)
-                                });
-
-                            check_exit_status(&cmd[0], &output.status);
-
-                            let line: Rc<str> = Rc::from(
-                                std::str::from_utf8(&output.stdout)
-                                    .unwrap_or_else(|err| panic!("{:?}", err)),
-                            );
-
-                            interpreter.alloc(Object::String(line))
-                        }
+                    fn cmd_read_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let stdout = run_cmd(env[0].unpack_cmd(), true)?;
+
+                        let line: Rc<str> =
+                            Rc::from(std::str::from_utf8(&stdout).map_err(|err| {
+                                RuntimeError::new(RuntimeErrorInfo::InvalidUtf8 {
+                                    error: err.to_string(),
+                                })
+                            })?);
+
+                        Ok(interpreter.alloc(Object::String(line)))
                     }
 
                     let env = interpreter.alloc_values([arg]);
-                    interpreter.alloc(Object::IO {
+                    Ok(interpreter.alloc(Object::IO {
                         env,
                         body: IOBody(cmd_read_io),
-                    })
+                    }))
                 }
             ),
             Builtin::ShowCmd => function1!(
                 show_cmd,
                 self,
                 |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
-                    let cmd = arg
-                        .unpack_cmd()
-                        .iter()
-                        .map(|value| {
-                            if value.contains(' ') {
-                                let mut string = String::with_capacity(value.len() + 2);
-
-                                string.push('"');
-                                value.chars().for_each(|c| {
-                                    if c == '"' {
-                                        string.push('\\');
-                                    }
-                                    string.push(c);
-                                });
-                                string.push('"');
+                    let cmd = arg.unpack_cmd().show();
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(cmd.as_str()))))
+                }
+            ),
+            Builtin::CmdTry => function1!(
+                cmd_try,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn cmd_try_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        Ok(match env[0].unpack_cmd().run(false, false) {
+                            Err(err) => {
+                                let message = interpreter.alloc_str(&err.to_string());
+                                let message = interpreter.alloc(Object::String(message));
+                                // Err message : (| Err : String, Ok : Int |)
+                                interpreter.alloc(Object::Variant(0, message))
+                            }
+                            Ok(finished) => {
+                                // Ok status : (| Err : String, Ok : Int |)
+                                interpreter.alloc(Object::Variant(
+                                    1,
+                                    Value::Int(i64::from(finished.code())),
+                                ))
+                            }
+                        })
+                    }
 
-                                string
-                            } else {
-                                String::from(value.as_ref())
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(cmd_try_io),
+                    }))
+                }
+            ),
+            Builtin::CmdOutput => function1!(
+                cmd_output,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn cmd_output_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        Ok(match env[0].unpack_cmd().run(true, true) {
+                            Err(err) => {
+                                let message = interpreter.alloc_str(&err.to_string());
+                                let message = interpreter.alloc(Object::String(message));
+                                // Err message : (| Err : String, Ok : { status : Int, stderr : String, stdout : String } |)
+                                interpreter.alloc(Object::Variant(0, message))
+                            }
+                            Ok(finished) => {
+                                let stderr = interpreter
+                                    .alloc_str(&String::from_utf8_lossy(&finished.stderr));
+                                let stderr = interpreter.alloc(Object::String(stderr));
+                                let stdout = interpreter
+                                    .alloc_str(&String::from_utf8_lossy(&finished.stdout));
+                                let stdout = interpreter.alloc(Object::String(stdout));
+                                let output = interpreter.alloc_values([
+                                    Value::Int(i64::from(finished.code())),
+                                    stderr,
+                                    stdout,
+                                ]);
+                                let output = interpreter.alloc(Object::Record(output));
+                                // Ok output : (| Err : String, Ok : { status : Int, stderr : String, stdout : String } |)
+                                interpreter.alloc(Object::Variant(1, output))
                             }
                         })
-                        .collect::<Vec<String>>()
-                        .join(" ");
-                    interpreter.alloc(Object::String(interpreter.alloc_str(cmd.as_str())))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(cmd_output_io),
+                    }))
+                }
+            ),
+            Builtin::PipeCmd => function2!(
+                pipe_cmd,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let mut processes = env[0].unpack_cmd().processes.clone();
+                    processes.extend(arg.unpack_cmd().processes.iter().cloned());
+                    Ok(interpreter.alloc(Object::Cmd(cmd::Cmd { processes })))
                 }
             ),
             Builtin::FlatMap => function2!(
@@ -1289,7 +2026,7 @@ where {
                     let mut result: Vec<Value> = Vec::new();
                     for x in xs.as_ref() {
                         result.extend(
-                            f.apply(interpreter, x.clone())
+                            f.apply(interpreter, x.clone())?
                                 .unpack_array()
                                 .as_ref()
                                 .iter()
@@ -1297,7 +2034,7 @@ where {
                         )
                     }
 
-                    interpreter.alloc(Object::Array(interpreter.alloc_values(result)))
+                    Ok(interpreter.alloc(Object::Array(interpreter.alloc_values(result))))
                 }
             ),
             Builtin::MapArray => function2!(
@@ -1310,9 +2047,9 @@ where {
                     let result = xs
                         .iter()
                         .map(|x| f.apply(interpreter, x.clone()))
-                        .collect::<Vec<_>>();
+                        .collect::<Result<Vec<_>, _>>()?;
 
-                    interpreter.alloc(Object::Array(interpreter.alloc_values(result)))
+                    Ok(interpreter.alloc(Object::Array(interpreter.alloc_values(result))))
                 }
             ),
             Builtin::CharToString => function1!(
@@ -1321,7 +2058,7 @@ where {
                 |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
                     let char = arg.unpack_char();
                     let string = format!("{:?}", char);
-                    interpreter.alloc(Object::String(interpreter.alloc_str(&string)))
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
                 }
             ),
             Builtin::DebugString => function1!(
@@ -1330,7 +2067,7 @@ where {
                 |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
                     let string = arg.unpack_string();
                     let new_string = format!("{:?}", string);
-                    interpreter.alloc(Object::String(interpreter.alloc_str(&new_string)))
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&new_string))))
                 }
             ),
             Builtin::ArrayUnfoldr => {
@@ -1344,7 +2081,7 @@ where {
                         let mut array = Vec::new();
                         loop {
                             // (| Step : ..., Skip : ..., Done : ... |)
-                            let value = f.apply(interpreter, s);
+                            let value = f.apply(interpreter, s)?;
                             let (tag, value) = value.unpack_variant();
 
                             match tag {
@@ -1369,12 +2106,16 @@ where {
                                     array.push(value[1].clone())
                                 }
 
-                                _ => unreachable!(),
+                                tag => {
+                                    return Err(RuntimeError::new(
+                                        RuntimeErrorInfo::UnexpectedTag { tag: *tag },
+                                    ))
+                                }
                             }
                         }
 
                         let array = interpreter.alloc_values(array);
-                        interpreter.alloc(Object::Array(array))
+                        Ok(interpreter.alloc(Object::Array(array)))
                     }
                 )
             }
@@ -1385,7 +2126,11 @@ where {
                     |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
                         let a = env[0].unpack_int();
                         let b = arg.unpack_int();
-                        Value::Int(a % b)
+                        if b == 0 {
+                            Err(RuntimeError::new(RuntimeErrorInfo::DivisionByZero))
+                        } else {
+                            Ok(Value::Int(mod_floor(a, b)))
+                        }
                     }
                 )
             }
@@ -1394,13 +2139,16 @@ where {
                     path_exists,
                     self,
                     |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn path_exists_io_1(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn path_exists_io_1(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             let path = env[0].unpack_string();
-                            if Path::new(&path).exists() {
+                            Ok(if Path::new(&path).exists() {
                                 Value::True
                             } else {
                                 Value::False
-                            }
+                            })
                         }
                         let env = interpreter.alloc_values({
                             let mut env = Vec::from(env.as_ref());
@@ -1411,17 +2159,20 @@ where {
                             env,
                             body: IOBody(path_exists_io_1),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
             Builtin::EnvArgs => {
-                fn env_args_io(interpreter: &mut Interpreter, _: Rc<[Value]>) -> Value {
+                fn env_args_io(
+                    interpreter: &mut Interpreter,
+                    _: Rc<[Value]>,
+                ) -> Result<Value, RuntimeError> {
                     let args = std::env::args();
                     let args = interpreter.alloc_values(
                         args.map(|arg| interpreter.alloc(Object::String(Rc::from(arg)))),
                     );
-                    interpreter.alloc(Object::Array(args))
+                    Ok(interpreter.alloc(Object::Array(args)))
                 }
                 let closure = Object::IO {
                     env: Rc::from([]),
@@ -1434,22 +2185,27 @@ where {
                     env_getvar,
                     self,
                     |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn env_getvar_io(interpreter: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn env_getvar_io(
+                            interpreter: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             let var = env[0].unpack_string();
                             match std::env::var(var) {
                                 Err(err) => match err {
                                     std::env::VarError::NotPresent => {
                                         // None () : (| None : (), Some : String |)
-                                        interpreter.alloc(Object::Variant(0, Value::Unit))
+                                        Ok(interpreter.alloc(Object::Variant(0, Value::Unit)))
                                     }
-                                    err => {
-                                        panic!("getvar: {}", err)
+                                    std::env::VarError::NotUnicode(_) => {
+                                        Err(RuntimeError::new(RuntimeErrorInfo::InvalidUtf8 {
+                                            error: format!("environment variable {:?}", var),
+                                        }))
                                     }
                                 },
                                 Ok(value) => {
                                     let value = interpreter.alloc(Object::String(Rc::from(value)));
                                     // Some value : (| None : (), Some : String |)
-                                    interpreter.alloc(Object::Variant(1, value))
+                                    Ok(interpreter.alloc(Object::Variant(1, value)))
                                 }
                             }
                         }
@@ -1462,7 +2218,7 @@ where {
                             env,
                             body: IOBody(env_getvar_io),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
@@ -1471,11 +2227,14 @@ where {
                     env_setvar,
                     self,
                     |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn env_setvar_io(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
+                        fn env_setvar_io(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
                             let key = env[0].unpack_string();
                             let value = env[1].unpack_string();
-                            std::env::set_var(key, value);
-                            Value::Unit
+                            set_var(key, value)?;
+                            Ok(Value::Unit)
                         }
                         let env = interpreter.alloc_values({
                             let mut env = Vec::from(env.as_ref());
@@ -1486,124 +2245,2003 @@ where {
                             env,
                             body: IOBody(env_setvar_io),
                         };
-                        interpreter.alloc(closure)
+                        Ok(interpreter.alloc(closure))
                     }
                 )
             }
             Builtin::ExitSuccess => {
-                fn exit_success_io(_: &mut Interpreter, _: Rc<[Value]>) -> Value {
-                    std::process::exit(0)
+                fn exit_success_io(
+                    _: &mut Interpreter,
+                    _: Rc<[Value]>,
+                ) -> Result<Value, RuntimeError> {
+                    Err(RuntimeError::new(RuntimeErrorInfo::Exit { code: 0 }))
+                }
+                let closure = Object::IO {
+                    env: Rc::from([]),
+                    body: IOBody(exit_success_io),
+                };
+                self.alloc(closure)
+            }
+            Builtin::ExitFailure => {
+                fn exit_failure_io(
+                    _: &mut Interpreter,
+                    _: Rc<[Value]>,
+                ) -> Result<Value, RuntimeError> {
+                    Err(RuntimeError::new(RuntimeErrorInfo::Exit { code: 1 }))
+                }
+                let closure = Object::IO {
+                    env: Rc::from([]),
+                    body: IOBody(exit_failure_io),
+                };
+                self.alloc(closure)
+            }
+            Builtin::ExitWith => {
+                function1!(
+                    exit_with,
+                    self,
+                    |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
+                        fn exit_with_io(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
+                            let code = i32::try_from(env[0].unpack_int()).map_err(|_| {
+                                RuntimeError::new(RuntimeErrorInfo::IntegerOverflow)
+                            })?;
+                            Err(RuntimeError::new(RuntimeErrorInfo::Exit { code }))
+                        }
+                        let env = interpreter.alloc_values({
+                            let mut env = Vec::from(env.as_ref());
+                            env.push(arg);
+                            env
+                        });
+                        let closure = Object::IO {
+                            env,
+                            body: IOBody(exit_with_io),
+                        };
+                        Ok(interpreter.alloc(closure))
+                    }
+                )
+            }
+            Builtin::FileRead => function1!(
+                file_read,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn file_read_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let contents = std::fs::read_to_string(path)
+                            .map_err(|err| RuntimeError::file(path, err))?;
+                        Ok(interpreter.alloc(Object::String(Rc::from(contents))))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_read_io),
+                    }))
+                }
+            ),
+            Builtin::FileWrite => {
+                function2!(
+                    file_write,
+                    self,
+                    |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
+                        fn file_write_io(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
+                            let path = env[0].unpack_string();
+                            let content = env[1].unpack_string();
+                            std::fs::write(path, content)
+                                .map_err(|err| RuntimeError::file(path, err))?;
+                            Ok(Value::Unit)
+                        }
+                        let env = interpreter.alloc_values({
+                            let mut env = Vec::from(env.as_ref());
+                            env.push(arg);
+                            env
+                        });
+                        let closure = Object::IO {
+                            env,
+                            body: IOBody(file_write_io),
+                        };
+                        Ok(interpreter.alloc(closure))
+                    }
+                )
+            }
+            Builtin::FileAppend => {
+                function2!(
+                    file_append,
+                    self,
+                    |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
+                        fn file_append_io(
+                            _: &mut Interpreter,
+                            env: Rc<[Value]>,
+                        ) -> Result<Value, RuntimeError> {
+                            let path = env[0].unpack_string();
+                            let content = env[1].unpack_string();
+                            std::fs::OpenOptions::new()
+                                .create(true)
+                                .append(true)
+                                .open(path)
+                                .and_then(|mut file| file.write_all(content.as_bytes()))
+                                .map_err(|err| RuntimeError::file(path, err))?;
+                            Ok(Value::Unit)
+                        }
+                        let env = interpreter.alloc_values({
+                            let mut env = Vec::from(env.as_ref());
+                            env.push(arg);
+                            env
+                        });
+                        let closure = Object::IO {
+                            env,
+                            body: IOBody(file_append_io),
+                        };
+                        Ok(interpreter.alloc(closure))
+                    }
+                )
+            }
+            Builtin::FileTryRead => function1!(
+                file_try_read,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn file_try_read_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::read_to_string(path)
+                            .map(|contents| interpreter.alloc(Object::String(Rc::from(contents))));
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_try_read_io),
+                    }))
+                }
+            ),
+            Builtin::FileTryWrite => function2!(
+                file_try_write,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    fn file_try_write_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let content = env[1].unpack_string();
+                        let result = std::fs::write(path, content).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([env[0].clone(), arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_try_write_io),
+                    }))
+                }
+            ),
+            Builtin::FileTryAppend => function2!(
+                file_try_append,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    fn file_try_append_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let content = env[1].unpack_string();
+                        let result = std::fs::OpenOptions::new()
+                            .create(true)
+                            .append(true)
+                            .open(path)
+                            .and_then(|mut file| file.write_all(content.as_bytes()))
+                            .map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([env[0].clone(), arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_try_append_io),
+                    }))
+                }
+            ),
+            Builtin::EnvTryGetvar => function1!(
+                env_try_getvar,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn env_try_getvar_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let var = env[0].unpack_string();
+                        let result = match std::env::var(var) {
+                            Err(err @ std::env::VarError::NotPresent) => {
+                                Err(io::Error::new(io::ErrorKind::NotFound, err))
+                            }
+                            Err(err @ std::env::VarError::NotUnicode(_)) => {
+                                Err(io::Error::new(io::ErrorKind::InvalidData, err))
+                            }
+                            Ok(value) => Ok(interpreter.alloc(Object::String(Rc::from(value)))),
+                        };
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(env_try_getvar_io),
+                    }))
+                }
+            ),
+            Builtin::EnvTrySetvar => function2!(
+                env_try_setvar,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    fn env_try_setvar_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let key = env[0].unpack_string();
+                        let value = env[1].unpack_string();
+                        let result = set_var(key, value).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([env[0].clone(), arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(env_try_setvar_io),
+                    }))
+                }
+            ),
+            Builtin::EqFloat => function2!(
+                eq_float,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_float();
+                    let b = arg.unpack_float();
+                    Ok(if a == b { Value::True } else { Value::False })
+                }
+            ),
+            Builtin::CompareFloat => function2!(
+                compare_float,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_float();
+                    let b = arg.unpack_float();
+                    /*
+                    Comparisons involving NaN fall back to the IEEE 754 total order, so that
+                    sorting arrays of floats is well behaved.
+                    */
+                    let ordering = a.partial_cmp(&b).unwrap_or_else(|| a.total_cmp(&b));
+                    Ok(interpreter.alloc_ordering(ordering))
+                }
+            ),
+            Builtin::FloatToString => function1!(
+                float_to_string,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let a = arg.unpack_float();
+                    let str = interpreter.alloc_str(&format!("{:?}", a));
+                    Ok(interpreter.alloc(Object::String(str)))
+                }
+            ),
+            Builtin::FloatFromInt => function1!(
+                float_from_int,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(Value::Float(arg.unpack_int() as f64))
+                }
+            ),
+            Builtin::FloatToInt => function1!(
+                float_to_int,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let a = arg.unpack_float().trunc();
+                    // `i64::MAX as f64` rounds up to 2^63, which is out of range.
+                    if a >= i64::MIN as f64 && a < i64::MAX as f64 {
+                        Ok(Value::Int(a as i64))
+                    } else {
+                        Err(RuntimeError::new(RuntimeErrorInfo::IntegerOverflow))
+                    }
+                }
+            ),
+            Builtin::FloatParse => function1!(
+                float_parse,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match arg.unpack_string().parse::<f64>() {
+                        // None () : (| None : (), Some : Float |)
+                        Err(_) => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some value : (| None : (), Some : Float |)
+                        Ok(value) => interpreter.alloc(Object::Variant(1, Value::Float(value))),
+                    })
+                }
+            ),
+            Builtin::IntDiv => function2!(
+                int_div,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    if b == 0 {
+                        return Err(RuntimeError::new(RuntimeErrorInfo::DivisionByZero));
+                    }
+                    checked_int(checked_div_floor(a, b))
+                }
+            ),
+            Builtin::IntRem => function2!(
+                int_rem,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    if b == 0 {
+                        return Err(RuntimeError::new(RuntimeErrorInfo::DivisionByZero));
+                    }
+                    // `i64::MIN % -1` is the only remainder that overflows, and its result is 0.
+                    Ok(Value::Int(a.wrapping_rem(b)))
+                }
+            ),
+            Builtin::IntQuot => function2!(
+                int_quot,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    if b == 0 {
+                        return Err(RuntimeError::new(RuntimeErrorInfo::DivisionByZero));
+                    }
+                    checked_int(a.checked_div(b))
+                }
+            ),
+            Builtin::IntAbs => function1!(
+                int_abs,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    checked_int(arg.unpack_int().checked_abs())
+                }
+            ),
+            Builtin::IntPow => function2!(
+                int_pow,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    if b < 0 {
+                        return Err(RuntimeError::new(RuntimeErrorInfo::NegativeExponent {
+                            exponent: b,
+                        }));
+                    }
+                    checked_int(checked_pow(a, b))
+                }
+            ),
+            Builtin::IntMin => function2!(
+                int_min,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    Ok(Value::Int(a.min(b)))
+                }
+            ),
+            Builtin::IntMax => function2!(
+                int_max,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    Ok(Value::Int(a.max(b)))
+                }
+            ),
+            Builtin::IntSafeDiv => function2!(
+                int_safe_div,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let a = env[0].unpack_int();
+                    let b = arg.unpack_int();
+                    Ok(match checked_div_floor(a, b) {
+                        // None () : (| None : (), Some : Int |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some value : (| None : (), Some : Int |)
+                        Some(value) => interpreter.alloc(Object::Variant(1, Value::Int(value))),
+                    })
+                }
+            ),
+            Builtin::IntParse => function1!(
+                int_parse,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match arg.unpack_string().parse::<i64>() {
+                        // None () : (| None : (), Some : Int |)
+                        Err(_) => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some value : (| None : (), Some : Int |)
+                        Ok(value) => interpreter.alloc(Object::Variant(1, Value::Int(value))),
+                    })
+                }
+            ),
+            Builtin::StringLength => function1!(
+                string_length,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = arg.unpack_string();
+                    checked_int(i64::try_from(string.chars().count()).ok())
+                }
+            ),
+            Builtin::StringSlice => function3!(
+                string_slice,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let start = env[0].unpack_int();
+                    let length = env[1].unpack_int();
+                    let string = arg.unpack_string();
+
+                    let slice = usize::try_from(start)
+                        .ok()
+                        .zip(usize::try_from(length).ok())
+                        .and_then(|(start, length)| slice_chars(string, start, length));
+                    match slice {
+                        Some(slice) => {
+                            Ok(interpreter.alloc(Object::String(interpreter.alloc_str(slice))))
+                        }
+                        None => Err(RuntimeError::new(
+                            RuntimeErrorInfo::StringSliceOutOfBounds {
+                                start,
+                                length,
+                                string_length: string.chars().count(),
+                            },
+                        )),
+                    }
+                }
+            ),
+            Builtin::StringIndexOf => function2!(
+                string_index_of,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let needle = env[0].unpack_string();
+                    let string = arg.unpack_string();
+                    Ok(match string.find(needle) {
+                        // None () : (| None : (), Some : Int |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some index : (| None : (), Some : Int |)
+                        Some(offset) => {
+                            let index = string[..offset].chars().count();
+                            interpreter
+                                .alloc(Object::Variant(1, checked_int(i64::try_from(index).ok())?))
+                        }
+                    })
+                }
+            ),
+            Builtin::StringStartsWith => function2!(
+                string_starts_with,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let prefix = env[0].unpack_string();
+                    let string = arg.unpack_string();
+                    Ok(if string.starts_with(prefix) {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::StringEndsWith => function2!(
+                string_ends_with,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let suffix = env[0].unpack_string();
+                    let string = arg.unpack_string();
+                    Ok(if string.ends_with(suffix) {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::StringTrim => function1!(
+                string_trim,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = arg.unpack_string().trim();
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(string))))
+                }
+            ),
+            Builtin::StringReplace => function3!(
+                string_replace,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let from = env[0].unpack_string();
+                    let to = env[1].unpack_string();
+                    let string = arg.unpack_string().replace(from, to);
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::StringToChars => function1!(
+                string_to_chars,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let chars =
+                        interpreter.alloc_values(arg.unpack_string().chars().map(Value::Char));
+                    Ok(interpreter.alloc(Object::Array(chars)))
+                }
+            ),
+            Builtin::StringFromChars => function1!(
+                string_from_chars,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string: String =
+                        arg.unpack_array().iter().map(Value::unpack_char).collect();
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::StringToUpper => function1!(
+                string_to_upper,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = arg.unpack_string().to_uppercase();
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::StringToLower => function1!(
+                string_to_lower,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = arg.unpack_string().to_lowercase();
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::BytesLength => function1!(
+                bytes_length,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    checked_int(i64::try_from(arg.unpack_bytes().len()).ok())
+                }
+            ),
+            Builtin::BytesIndex => function2!(
+                bytes_index,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let ix = env[0].unpack_int();
+                    let bytes = arg.unpack_bytes();
+
+                    match usize::try_from(ix).ok().and_then(|ix| bytes.get(ix)) {
+                        Some(byte) => Ok(Value::Int(i64::from(*byte))),
+                        None => Err(RuntimeError::new(RuntimeErrorInfo::IndexOutOfBounds {
+                            index: ix,
+                            length: bytes.len(),
+                        })),
+                    }
+                }
+            ),
+            Builtin::BytesSlice => function3!(
+                bytes_slice,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let start = env[0].unpack_int();
+                    let length = env[1].unpack_int();
+                    let bytes = arg.unpack_bytes();
+
+                    let slice = usize::try_from(start)
+                        .ok()
+                        .zip(usize::try_from(length).ok())
+                        .and_then(|(start, length)| bytes.get(start..start.checked_add(length)?));
+                    match slice {
+                        Some(slice) => Ok(interpreter.alloc(Object::Bytes(Rc::from(slice)))),
+                        None => Err(RuntimeError::new(RuntimeErrorInfo::BytesSliceOutOfBounds {
+                            start,
+                            length,
+                            bytes_length: bytes.len(),
+                        })),
+                    }
+                }
+            ),
+            Builtin::BytesAppend => function2!(
+                bytes_append,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let bytes = [env[0].unpack_bytes(), arg.unpack_bytes()].concat();
+                    Ok(interpreter.alloc(Object::Bytes(Rc::from(bytes))))
+                }
+            ),
+            Builtin::BytesConcat => function1!(
+                bytes_concat,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let bytes: Vec<u8> = arg
+                        .unpack_array()
+                        .iter()
+                        .flat_map(|bytes| bytes.unpack_bytes().iter().copied())
+                        .collect();
+                    Ok(interpreter.alloc(Object::Bytes(Rc::from(bytes))))
+                }
+            ),
+            Builtin::BytesFromUtf8 => function1!(
+                bytes_from_utf8,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match std::str::from_utf8(arg.unpack_bytes()) {
+                        Err(err) => {
+                            let message = interpreter.alloc_str(&err.to_string());
+                            let message = interpreter.alloc(Object::String(message));
+                            let valid_up_to = checked_int(i64::try_from(err.valid_up_to()).ok())?;
+                            let error = interpreter.alloc_values([message, valid_up_to]);
+                            let error = interpreter.alloc(Object::Record(error));
+                            // Err error : (| Err : { message : String, validUpTo : Int }, Ok : String |)
+                            interpreter.alloc(Object::Variant(0, error))
+                        }
+                        Ok(string) => {
+                            let string =
+                                interpreter.alloc(Object::String(interpreter.alloc_str(string)));
+                            // Ok string : (| Err : { message : String, validUpTo : Int }, Ok : String |)
+                            interpreter.alloc(Object::Variant(1, string))
+                        }
+                    })
+                }
+            ),
+            Builtin::BytesToHex => function1!(
+                bytes_to_hex,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = encoding::to_hex(arg.unpack_bytes());
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::BytesFromHex => function1!(
+                bytes_from_hex,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match encoding::from_hex(arg.unpack_string()) {
+                        // None () : (| None : (), Some : Bytes |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some bytes : (| None : (), Some : Bytes |)
+                        Some(bytes) => {
+                            let bytes = interpreter.alloc(Object::Bytes(Rc::from(bytes)));
+                            interpreter.alloc(Object::Variant(1, bytes))
+                        }
+                    })
+                }
+            ),
+            Builtin::BytesToBase64 => function1!(
+                bytes_to_base64,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = encoding::to_base64(arg.unpack_bytes());
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::BytesFromBase64 => function1!(
+                bytes_from_base64,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match encoding::from_base64(arg.unpack_string()) {
+                        // None () : (| None : (), Some : Bytes |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some bytes : (| None : (), Some : Bytes |)
+                        Some(bytes) => {
+                            let bytes = interpreter.alloc(Object::Bytes(Rc::from(bytes)));
+                            interpreter.alloc(Object::Variant(1, bytes))
+                        }
+                    })
+                }
+            ),
+            Builtin::FileReadBytes => function1!(
+                file_read_bytes,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn file_read_bytes_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let contents =
+                            std::fs::read(path).map_err(|err| RuntimeError::file(path, err))?;
+                        Ok(interpreter.alloc(Object::Bytes(Rc::from(contents))))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_read_bytes_io),
+                    }))
+                }
+            ),
+            Builtin::FileWriteBytes => function2!(
+                file_write_bytes,
+                self,
+                |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
+                    fn file_write_bytes_io(
+                        _: &mut Interpreter,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let content = env[1].unpack_bytes();
+                        std::fs::write(path, content)
+                            .map_err(|err| RuntimeError::file(path, err))?;
+                        Ok(Value::Unit)
+                    }
+                    let env = interpreter.alloc_values({
+                        let mut env = Vec::from(env.as_ref());
+                        env.push(arg);
+                        env
+                    });
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_write_bytes_io),
+                    }))
+                }
+            ),
+            Builtin::CmdReadBytes => function1!(
+                cmd_read_bytes,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn cmd_read_bytes_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let stdout = run_cmd(env[0].unpack_cmd(), true)?;
+                        Ok(interpreter.alloc(Object::Bytes(Rc::from(stdout))))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(cmd_read_bytes_io),
+                    }))
+                }
+            ),
+            Builtin::CmdOutputBytes => function1!(
+                cmd_output_bytes,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn cmd_output_bytes_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        Ok(match env[0].unpack_cmd().run(true, true) {
+                            Err(err) => {
+                                let message = interpreter.alloc_str(&err.to_string());
+                                let message = interpreter.alloc(Object::String(message));
+                                // Err message : (| Err : String, Ok : { status : Int, stderr : Bytes, stdout : Bytes } |)
+                                interpreter.alloc(Object::Variant(0, message))
+                            }
+                            Ok(finished) => {
+                                let status = Value::Int(i64::from(finished.code()));
+                                let stderr =
+                                    interpreter.alloc(Object::Bytes(Rc::from(finished.stderr)));
+                                let stdout =
+                                    interpreter.alloc(Object::Bytes(Rc::from(finished.stdout)));
+                                let output = interpreter.alloc_values([status, stderr, stdout]);
+                                let output = interpreter.alloc(Object::Record(output));
+                                // Ok output : (| Err : String, Ok : { status : Int, stderr : Bytes, stdout : Bytes } |)
+                                interpreter.alloc(Object::Variant(1, output))
+                            }
+                        })
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(cmd_output_bytes_io),
+                    }))
+                }
+            ),
+            Builtin::MapEmpty => self.alloc(Object::Map(map::Map::new())),
+            Builtin::MapSize => function1!(
+                map_size,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    checked_int(i64::try_from(arg.unpack_map().len()).ok())
+                }
+            ),
+            Builtin::MapInsert => function4!(
+                map_insert,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let entries =
+                        arg.unpack_map()
+                            .insert(env[1].clone(), env[2].clone(), |a, b| {
+                                compare_with(interpreter, compare, a, b)
+                            })?;
+                    Ok(interpreter.alloc(Object::Map(entries)))
+                }
+            ),
+            Builtin::MapLookup => function3!(
+                map_lookup,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let value = arg
+                        .unpack_map()
+                        .get(&env[1], |a, b| compare_with(interpreter, compare, a, b))?
+                        .cloned();
+                    Ok(match value {
+                        // None () : (| None : (), Some : v |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some value : (| None : (), Some : v |)
+                        Some(value) => interpreter.alloc(Object::Variant(1, value)),
+                    })
+                }
+            ),
+            Builtin::MapDelete => function3!(
+                map_delete,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let entries = arg
+                        .unpack_map()
+                        .remove(&env[1], |a, b| compare_with(interpreter, compare, a, b))?;
+                    Ok(interpreter.alloc(Object::Map(entries)))
+                }
+            ),
+            Builtin::MapToArray => function1!(
+                map_to_array,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let entries: Vec<Value> = arg
+                        .unpack_map()
+                        .iter()
+                        .map(|(key, value)| {
+                            // { key : k, value : v }
+                            let fields = interpreter.alloc_values([key.clone(), value.clone()]);
+                            interpreter.alloc(Object::Record(fields))
+                        })
+                        .collect();
+                    let entries = interpreter.alloc_values(entries);
+                    Ok(interpreter.alloc(Object::Array(entries)))
+                }
+            ),
+            Builtin::MapFromArray => function2!(
+                map_from_array,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let mut entries = map::Map::new();
+                    for entry in arg.unpack_array().iter() {
+                        // { key : k, value : v }
+                        let fields = entry.unpack_object().unpack_record();
+                        entries =
+                            entries.insert(fields[0].clone(), fields[1].clone(), |a, b| {
+                                compare_with(interpreter, compare, a, b)
+                            })?;
+                    }
+                    Ok(interpreter.alloc(Object::Map(entries)))
+                }
+            ),
+            Builtin::MapFold => function3!(
+                map_fold,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    let mut acc = env[1].clone();
+                    for (key, value) in arg.unpack_map().iter() {
+                        acc = f
+                            .apply(interpreter, acc)?
+                            .apply(interpreter, key.clone())?
+                            .apply(interpreter, value.clone())?;
+                    }
+                    Ok(acc)
+                }
+            ),
+            Builtin::DebugMap => function3!(
+                debug_map,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let debug_key = env[0].clone();
+                    let debug_value = env[1].clone();
+                    let mut entries = Vec::with_capacity(arg.unpack_map().len());
+                    for (key, value) in arg.unpack_map().iter() {
+                        let key = debug_key.apply(interpreter, key.clone())?;
+                        let value = debug_value.apply(interpreter, value.clone())?;
+                        entries.push(format!(
+                            "{{ key = {}, value = {} }}",
+                            key.unpack_string(),
+                            value.unpack_string()
+                        ));
+                    }
+                    let string =
+                        interpreter.alloc_str(&format!("map.fromArray [{}]", entries.join(", ")));
+                    Ok(interpreter.alloc(Object::String(string)))
+                }
+            ),
+            Builtin::SetEmpty => self.alloc(Object::Set(map::Map::new())),
+            Builtin::SetSize => function1!(
+                set_size,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    checked_int(i64::try_from(arg.unpack_set().len()).ok())
+                }
+            ),
+            Builtin::SetInsert => function3!(
+                set_insert,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let items = arg.unpack_set().insert(env[1].clone(), (), |a, b| {
+                        compare_with(interpreter, compare, a, b)
+                    })?;
+                    Ok(interpreter.alloc(Object::Set(items)))
+                }
+            ),
+            Builtin::SetMember => function3!(
+                set_member,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let found = arg
+                        .unpack_set()
+                        .get(&env[1], |a, b| compare_with(interpreter, compare, a, b))?;
+                    Ok(if found.is_some() {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::SetDelete => function3!(
+                set_delete,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let items = arg
+                        .unpack_set()
+                        .remove(&env[1], |a, b| compare_with(interpreter, compare, a, b))?;
+                    Ok(interpreter.alloc(Object::Set(items)))
+                }
+            ),
+            Builtin::SetToArray => function1!(
+                set_to_array,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let items = interpreter
+                        .alloc_values(arg.unpack_set().iter().map(|(item, ())| item.clone()));
+                    Ok(interpreter.alloc(Object::Array(items)))
+                }
+            ),
+            Builtin::SetFromArray => function2!(
+                set_from_array,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let mut items = map::Map::new();
+                    for item in arg.unpack_array().iter() {
+                        items = items.insert(item.clone(), (), |a, b| {
+                            compare_with(interpreter, compare, a, b)
+                        })?;
+                    }
+                    Ok(interpreter.alloc(Object::Set(items)))
+                }
+            ),
+            Builtin::SetFold => function3!(
+                set_fold,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    let mut acc = env[1].clone();
+                    for (item, ()) in arg.unpack_set().iter() {
+                        acc = f
+                            .apply(interpreter, acc)?
+                            .apply(interpreter, item.clone())?;
+                    }
+                    Ok(acc)
+                }
+            ),
+            Builtin::DebugSet => function2!(
+                debug_set,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let debug_item = env[0].clone();
+                    let mut items = Vec::with_capacity(arg.unpack_set().len());
+                    for (item, ()) in arg.unpack_set().iter() {
+                        let item = debug_item.apply(interpreter, item.clone())?;
+                        items.push(String::from(item.unpack_string()));
+                    }
+                    let string =
+                        interpreter.alloc_str(&format!("set.fromArray [{}]", items.join(", ")));
+                    Ok(interpreter.alloc(Object::String(string)))
+                }
+            ),
+            Builtin::ArrayFilter => function2!(
+                array_filter,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    let mut result = Vec::new();
+                    for item in arg.unpack_array().iter() {
+                        if f.apply(interpreter, item.clone())?.unpack_bool() {
+                            result.push(item.clone());
+                        }
+                    }
+                    Ok(interpreter.alloc(Object::Array(interpreter.alloc_values(result))))
+                }
+            ),
+            Builtin::ArraySortBy => function2!(
+                array_sort_by,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let compare = &env[0];
+                    let result = merge_sort_by(&arg.unpack_array(), &mut |a, b| {
+                        compare_with(interpreter, compare, a, b)
+                    })?;
+                    Ok(interpreter.alloc(Object::Array(interpreter.alloc_values(result))))
+                }
+            ),
+            Builtin::ArrayReverse => function1!(
+                array_reverse,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let result = interpreter.alloc_values(arg.unpack_array().iter().rev().cloned());
+                    Ok(interpreter.alloc(Object::Array(result)))
+                }
+            ),
+            Builtin::ArrayZip => function2!(
+                array_zip,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let xs = env[0].unpack_array();
+                    let ys = arg.unpack_array();
+                    let result: Vec<Value> = xs
+                        .iter()
+                        .zip(ys.iter())
+                        .map(|(x, y)| {
+                            // { fst : a, snd : b }
+                            let fields = interpreter.alloc_values([x.clone(), y.clone()]);
+                            interpreter.alloc(Object::Record(fields))
+                        })
+                        .collect();
+                    Ok(interpreter.alloc(Object::Array(interpreter.alloc_values(result))))
+                }
+            ),
+            Builtin::ArrayFind => function2!(
+                array_find,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    for item in arg.unpack_array().iter() {
+                        if f.apply(interpreter, item.clone())?.unpack_bool() {
+                            // Some item : (| None : (), Some : a |)
+                            return Ok(interpreter.alloc(Object::Variant(1, item.clone())));
+                        }
+                    }
+                    // None () : (| None : (), Some : a |)
+                    Ok(interpreter.alloc(Object::Variant(0, Value::Unit)))
+                }
+            ),
+            Builtin::ArrayAll => function2!(
+                array_all,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    for item in arg.unpack_array().iter() {
+                        if !f.apply(interpreter, item.clone())?.unpack_bool() {
+                            return Ok(Value::False);
+                        }
+                    }
+                    Ok(Value::True)
+                }
+            ),
+            Builtin::ArrayConcat => function1!(
+                array_concat,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let arrays = arg.unpack_array();
+                    let result = interpreter.alloc_values(arrays.iter().flat_map(|array| {
+                        array.unpack_array().iter().cloned().collect::<Vec<_>>()
+                    }));
+                    Ok(interpreter.alloc(Object::Array(result)))
+                }
+            ),
+            Builtin::ArrayRange => function2!(
+                array_range,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let start = env[0].unpack_int();
+                    let end = arg.unpack_int();
+                    let result = interpreter.alloc_values((start..end).map(Value::Int));
+                    Ok(interpreter.alloc(Object::Array(result)))
+                }
+            ),
+            Builtin::ArrayFoldr => function3!(
+                array_foldr,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    let mut acc = env[1].clone();
+                    for item in arg.unpack_array().iter().rev() {
+                        acc = f
+                            .apply(interpreter, item.clone())?
+                            .apply(interpreter, acc)?;
+                    }
+                    Ok(acc)
+                }
+            ),
+            Builtin::ArrayPartition => function2!(
+                array_partition,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let f = env[0].clone();
+                    let mut pass = Vec::new();
+                    let mut fail = Vec::new();
+                    for item in arg.unpack_array().iter() {
+                        if f.apply(interpreter, item.clone())?.unpack_bool() {
+                            pass.push(item.clone());
+                        } else {
+                            fail.push(item.clone());
+                        }
+                    }
+                    let fail = interpreter.alloc(Object::Array(interpreter.alloc_values(fail)));
+                    let pass = interpreter.alloc(Object::Array(interpreter.alloc_values(pass)));
+                    // { fail : Array a, pass : Array a }
+                    let fields = interpreter.alloc_values([fail, pass]);
+                    Ok(interpreter.alloc(Object::Record(fields)))
+                }
+            ),
+            Builtin::ArrayIndexOf => function3!(
+                array_index_of,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let eq = env[0].clone();
+                    let needle = &env[1];
+                    for (index, item) in arg.unpack_array().iter().enumerate() {
+                        if eq
+                            .apply(interpreter, needle.clone())?
+                            .apply(interpreter, item.clone())?
+                            .unpack_bool()
+                        {
+                            let index = checked_int(i64::try_from(index).ok())?;
+                            // Some index : (| None : (), Some : Int |)
+                            return Ok(interpreter.alloc(Object::Variant(1, index)));
+                        }
+                    }
+                    // None () : (| None : (), Some : Int |)
+                    Ok(interpreter.alloc(Object::Variant(0, Value::Unit)))
+                }
+            ),
+            Builtin::ArrayGet => function2!(
+                array_get,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let index = env[0].unpack_int();
+                    let array = arg.unpack_array();
+                    Ok(
+                        match usize::try_from(index)
+                            .ok()
+                            .and_then(|index| array.get(index))
+                        {
+                            // None () : (| None : (), Some : a |)
+                            None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                            // Some item : (| None : (), Some : a |)
+                            Some(item) => interpreter.alloc(Object::Variant(1, item.clone())),
+                        },
+                    )
+                }
+            ),
+            Builtin::ArrayHead => function1!(
+                array_head,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match arg.unpack_array().first() {
+                        // None () : (| None : (), Some : a |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some item : (| None : (), Some : a |)
+                        Some(item) => interpreter.alloc(Object::Variant(1, item.clone())),
+                    })
+                }
+            ),
+            Builtin::ArrayLast => function1!(
+                array_last,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match arg.unpack_array().last() {
+                        // None () : (| None : (), Some : a |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some item : (| None : (), Some : a |)
+                        Some(item) => interpreter.alloc(Object::Variant(1, item.clone())),
+                    })
+                }
+            ),
+            Builtin::ArrayUncons => function1!(
+                array_uncons,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match arg.unpack_array().split_first() {
+                        // None () : (| None : (), Some : { first : a, rest : Array a } |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        Some((first, rest)) => {
+                            let rest = interpreter.alloc(Object::Array(Rc::from(rest)));
+                            // { first : a, rest : Array a }
+                            let fields = interpreter.alloc_values([first.clone(), rest]);
+                            let value = interpreter.alloc(Object::Record(fields));
+                            // Some value : (| None : (), Some : { first : a, rest : Array a } |)
+                            interpreter.alloc(Object::Variant(1, value))
+                        }
+                    })
+                }
+            ),
+            Builtin::CharIsDigit => function1!(
+                char_is_digit,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(if arg.unpack_char().is_ascii_digit() {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::CharIsAlpha => function1!(
+                char_is_alpha,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(if arg.unpack_char().is_alphabetic() {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::CharIsSpace => function1!(
+                char_is_space,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(if arg.unpack_char().is_whitespace() {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::CharIsUpper => function1!(
+                char_is_upper,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(if arg.unpack_char().is_uppercase() {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::CharIsLower => function1!(
+                char_is_lower,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(if arg.unpack_char().is_lowercase() {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::CharToUpper => function1!(
+                char_to_upper,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(Value::Char(map_single_char(
+                        arg.unpack_char(),
+                        char::to_uppercase,
+                    )))
+                }
+            ),
+            Builtin::CharToLower => function1!(
+                char_to_lower,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(Value::Char(map_single_char(
+                        arg.unpack_char(),
+                        char::to_lowercase,
+                    )))
+                }
+            ),
+            Builtin::CharToCode => function1!(
+                char_to_code,
+                self,
+                |_: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(Value::Int(i64::from(u32::from(arg.unpack_char()))))
+                }
+            ),
+            Builtin::CharFromCode => function1!(
+                char_from_code,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let c = u32::try_from(arg.unpack_int())
+                        .ok()
+                        .and_then(char::from_u32);
+                    Ok(match c {
+                        // None () : (| None : (), Some : Char |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some c : (| None : (), Some : Char |)
+                        Some(c) => interpreter.alloc(Object::Variant(1, Value::Char(c))),
+                    })
+                }
+            ),
+            Builtin::JsonParse => function1!(
+                json_parse,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match json::parse(arg.unpack_string()) {
+                        Err(err) => {
+                            let message = interpreter.alloc_str(&err.to_string());
+                            let message = interpreter.alloc(Object::String(message));
+                            // Err message : (| Err : String, Ok : Json |)
+                            interpreter.alloc(Object::Variant(0, message))
+                        }
+                        Ok(value) => {
+                            let value = json_to_value(interpreter, &value);
+                            // Ok value : (| Err : String, Ok : Json |)
+                            interpreter.alloc(Object::Variant(1, value))
+                        }
+                    })
+                }
+            ),
+            Builtin::JsonRender => function1!(
+                json_render,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    let string = json::render(&value_to_json(&arg)?);
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
+            Builtin::JsonField => function2!(
+                json_field,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let key = env[0].unpack_string();
+                    let member = match arg.unpack_variant() {
+                        (&JSON_OBJECT, members) => json_member(&members.unpack_array(), key),
+                        _ => None,
+                    };
+                    Ok(match member {
+                        // None () : (| None : (), Some : Json |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some value : (| None : (), Some : Json |)
+                        Some(value) => interpreter.alloc(Object::Variant(1, value)),
+                    })
+                }
+            ),
+            Builtin::JsonIndex => function2!(
+                json_index,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let index = env[0].unpack_int();
+                    let item = match arg.unpack_variant() {
+                        (&JSON_ARRAY, items) => usize::try_from(index)
+                            .ok()
+                            .and_then(|index| items.unpack_array().get(index).cloned()),
+                        _ => None,
+                    };
+                    Ok(match item {
+                        // None () : (| None : (), Some : Json |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some item : (| None : (), Some : Json |)
+                        Some(item) => interpreter.alloc(Object::Variant(1, item)),
+                    })
+                }
+            ),
+            Builtin::JsonAsBool => function1!(
+                json_as_bool,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(json_as(interpreter, &arg, JSON_BOOL))
+                }
+            ),
+            Builtin::JsonAsInt => function1!(
+                json_as_int,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(json_as(interpreter, &arg, JSON_INT))
+                }
+            ),
+            Builtin::JsonAsFloat => function1!(
+                json_as_float,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(match arg.unpack_variant() {
+                        // Integers are numbers too, so they're accepted as floats.
+                        (&JSON_INT, n) => interpreter
+                            .alloc(Object::Variant(1, Value::Float(n.unpack_int() as f64))),
+                        _ => json_as(interpreter, &arg, JSON_FLOAT),
+                    })
+                }
+            ),
+            Builtin::JsonAsString => function1!(
+                json_as_string,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(json_as(interpreter, &arg, JSON_STRING))
+                }
+            ),
+            Builtin::JsonAsArray => function1!(
+                json_as_array,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(json_as(interpreter, &arg, JSON_ARRAY))
+                }
+            ),
+            Builtin::JsonAsObject => function1!(
+                json_as_object,
+                self,
+                |interpreter: &mut Interpreter<'_>, _env: Rc<[Value]>, arg: Value| {
+                    Ok(json_as(interpreter, &arg, JSON_OBJECT))
+                }
+            ),
+            Builtin::JsonDecodeField => function3!(
+                json_decode_field,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let key = env[0].unpack_string();
+                    let decode = env[1].clone();
+                    Ok(match json_member(&arg.unpack_array(), key) {
+                        None => {
+                            let message = format!("missing field {:?}", key);
+                            let message =
+                                interpreter.alloc(Object::String(interpreter.alloc_str(&message)));
+                            // Err message : (| Err : String, Ok : a |)
+                            interpreter.alloc(Object::Variant(0, message))
+                        }
+                        Some(value) => {
+                            let result = decode.apply(interpreter, value)?;
+                            match result.unpack_variant() {
+                                (0, message) => {
+                                    let message =
+                                        format!("field {:?}: {}", key, message.unpack_string());
+                                    let message = interpreter
+                                        .alloc(Object::String(interpreter.alloc_str(&message)));
+                                    // Err message : (| Err : String, Ok : a |)
+                                    interpreter.alloc(Object::Variant(0, message))
+                                }
+                                _ => result.clone(),
+                            }
+                        }
+                    })
+                }
+            ),
+            Builtin::JsonDecodeArray => function2!(
+                json_decode_array,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let decode = env[0].clone();
+                    let items = match arg.unpack_variant() {
+                        (&JSON_ARRAY, items) => items.unpack_array(),
+                        _ => {
+                            let message =
+                                interpreter.alloc(Object::String(Rc::from("expected an array")));
+                            // Err message : (| Err : String, Ok : Array a |)
+                            return Ok(interpreter.alloc(Object::Variant(0, message)));
+                        }
+                    };
+                    let mut results = Vec::with_capacity(items.len());
+                    for (index, item) in items.iter().enumerate() {
+                        let result = decode.apply(interpreter, item.clone())?;
+                        match result.unpack_variant() {
+                            (0, message) => {
+                                let message =
+                                    format!("index {}: {}", index, message.unpack_string());
+                                let message = interpreter
+                                    .alloc(Object::String(interpreter.alloc_str(&message)));
+                                // Err message : (| Err : String, Ok : Array a |)
+                                return Ok(interpreter.alloc(Object::Variant(0, message)));
+                            }
+                            (_, value) => results.push(value.clone()),
+                        }
+                    }
+                    let results = interpreter.alloc(Object::Array(Rc::from(results)));
+                    // Ok results : (| Err : String, Ok : Array a |)
+                    Ok(interpreter.alloc(Object::Variant(1, results)))
+                }
+            ),
+            Builtin::FileRemove => function1!(
+                file_remove,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn file_remove_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::remove_file(path).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_remove_io),
+                    }))
+                }
+            ),
+            Builtin::FileCopy => function2!(
+                file_copy,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    fn file_copy_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let from = env[0].unpack_string();
+                        let to = env[1].unpack_string();
+                        let result = std::fs::copy(from, to).map(|_| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([env[0].clone(), arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_copy_io),
+                    }))
+                }
+            ),
+            Builtin::FileRename => function2!(
+                file_rename,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    fn file_rename_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let from = env[0].unpack_string();
+                        let to = env[1].unpack_string();
+                        let result = std::fs::rename(from, to).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([env[0].clone(), arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_rename_io),
+                    }))
+                }
+            ),
+            Builtin::FileMetadata => function1!(
+                file_metadata,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn file_metadata_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::metadata(path).and_then(|metadata| {
+                            let modified = unix_seconds(metadata.modified()?);
+                            let fields = interpreter.alloc_values([
+                                // isDir
+                                if metadata.is_dir() {
+                                    Value::True
+                                } else {
+                                    Value::False
+                                },
+                                // isFile
+                                if metadata.is_file() {
+                                    Value::True
+                                } else {
+                                    Value::False
+                                },
+                                // modified
+                                Value::Int(modified),
+                                // permissions
+                                Value::Int(i64::from(metadata.permissions().mode() & 0o7777)),
+                                // size
+                                Value::Int(i64::try_from(metadata.len()).unwrap_or(i64::MAX)),
+                            ]);
+                            Ok(interpreter.alloc(Object::Record(fields)))
+                        });
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_metadata_io),
+                    }))
+                }
+            ),
+            Builtin::FileChmod => function2!(
+                file_chmod,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    fn file_chmod_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let mode = env[1].unpack_int();
+                        let result = match u32::try_from(mode) {
+                            Ok(mode) if mode <= 0o7777 => std::fs::set_permissions(
+                                path,
+                                std::fs::Permissions::from_mode(mode),
+                            )
+                            .map(|()| Value::Unit),
+                            _ => Err(io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                format!("invalid file mode {}", mode),
+                            )),
+                        };
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([env[0].clone(), arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(file_chmod_io),
+                    }))
+                }
+            ),
+            Builtin::DirCreate => function1!(
+                dir_create,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn dir_create_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::create_dir_all(path).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(dir_create_io),
+                    }))
+                }
+            ),
+            Builtin::DirList => function1!(
+                dir_list,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn dir_list_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::read_dir(path)
+                            .and_then(|entries| {
+                                entries
+                                    .map(|entry| {
+                                        Ok(entry?.file_name().to_string_lossy().into_owned())
+                                    })
+                                    .collect::<io::Result<Vec<String>>>()
+                            })
+                            .map(|mut names| {
+                                // `read_dir`'s order is platform-dependent.
+                                names.sort();
+                                let names = interpreter.alloc_values(names.iter().map(|name| {
+                                    interpreter.alloc(Object::String(interpreter.alloc_str(name)))
+                                }));
+                                interpreter.alloc(Object::Array(names))
+                            });
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(dir_list_io),
+                    }))
+                }
+            ),
+            Builtin::DirRemove => function1!(
+                dir_remove,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn dir_remove_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::remove_dir(path).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(dir_remove_io),
+                    }))
+                }
+            ),
+            Builtin::DirRemoveAll => function1!(
+                dir_remove_all,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn dir_remove_all_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        let result = std::fs::remove_dir_all(path).map(|()| Value::Unit);
+                        Ok(interpreter.alloc_io_result(result))
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(dir_remove_all_io),
+                    }))
+                }
+            ),
+            Builtin::PathIsFile => function1!(
+                path_is_file,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn path_is_file_io(
+                        _: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        Ok(if Path::new(path).is_file() {
+                            Value::True
+                        } else {
+                            Value::False
+                        })
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(path_is_file_io),
+                    }))
+                }
+            ),
+            Builtin::PathIsDir => function1!(
+                path_is_dir,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn path_is_dir_io(
+                        _: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let path = env[0].unpack_string();
+                        Ok(if Path::new(path).is_dir() {
+                            Value::True
+                        } else {
+                            Value::False
+                        })
+                    }
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(path_is_dir_io),
+                    }))
+                }
+            ),
+            Builtin::PathJoin => function2!(
+                path_join,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let path = Path::new(env[0].unpack_string()).join(arg.unpack_string());
+                    Ok(interpreter.alloc(Object::String(
+                        interpreter.alloc_str(&path.to_string_lossy()),
+                    )))
                 }
-                let closure = Object::IO {
-                    env: Rc::from([]),
-                    body: IOBody(exit_success_io),
-                };
-                self.alloc(closure)
-            }
-            Builtin::ExitFailure => {
-                fn exit_failure_io(_: &mut Interpreter, _: Rc<[Value]>) -> Value {
-                    std::process::exit(1)
+            ),
+            Builtin::PathParent => function1!(
+                path_parent,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    // A relative path with a single component has an empty parent.
+                    let parent = Path::new(arg.unpack_string())
+                        .parent()
+                        .filter(|parent| !parent.as_os_str().is_empty());
+                    Ok(alloc_path_option(interpreter, parent))
                 }
-                let closure = Object::IO {
-                    env: Rc::from([]),
-                    body: IOBody(exit_failure_io),
-                };
-                self.alloc(closure)
-            }
-            Builtin::ExitWith => {
-                function1!(
-                    exit_with,
-                    self,
-                    |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn exit_with_io(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
-                            let code: i32 = env[0].unpack_int();
-                            std::process::exit(code as i32)
-                        }
-                        let env = interpreter.alloc_values({
-                            let mut env = Vec::from(env.as_ref());
-                            env.push(arg);
-                            env
-                        });
-                        let closure = Object::IO {
-                            env,
-                            body: IOBody(exit_with_io),
-                        };
-                        interpreter.alloc(closure)
+            ),
+            Builtin::PathFileName => function1!(
+                path_file_name,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    let file_name = Path::new(arg.unpack_string()).file_name();
+                    Ok(alloc_path_option(interpreter, file_name.map(Path::new)))
+                }
+            ),
+            Builtin::PathExtension => function1!(
+                path_extension,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    let extension = Path::new(arg.unpack_string()).extension();
+                    Ok(alloc_path_option(interpreter, extension.map(Path::new)))
+                }
+            ),
+            Builtin::PathWithExtension => function2!(
+                path_with_extension,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let extension = env[0].unpack_string();
+                    let path = Path::new(arg.unpack_string()).with_extension(extension);
+                    Ok(interpreter.alloc(Object::String(
+                        interpreter.alloc_str(&path.to_string_lossy()),
+                    )))
+                }
+            ),
+            Builtin::PathNormalize => function1!(
+                path_normalize,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    let path = path::normalize(Path::new(arg.unpack_string()));
+                    Ok(interpreter.alloc(Object::String(
+                        interpreter.alloc_str(&path.to_string_lossy()),
+                    )))
+                }
+            ),
+            Builtin::PathAbsolute => function1!(
+                path_absolute,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    fn path_absolute_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let current_dir = std::env::current_dir()
+                            .map_err(|error| RuntimeError::new(RuntimeErrorInfo::Io { error }))?;
+                        let path = path::normalize(&current_dir.join(env[0].unpack_string()));
+                        Ok(interpreter.alloc(Object::String(
+                            interpreter.alloc_str(&path.to_string_lossy()),
+                        )))
                     }
-                )
-            }
-            Builtin::FileRead => function1!(
-                file_read,
+
+                    let env = interpreter.alloc_values([arg]);
+                    Ok(interpreter.alloc(Object::IO {
+                        env,
+                        body: IOBody(path_absolute_io),
+                    }))
+                }
+            ),
+            Builtin::PathRelativeTo => function2!(
+                path_relative_to,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let base = Path::new(env[0].unpack_string());
+                    let path = path::relative_to(Path::new(arg.unpack_string()), base);
+                    Ok(alloc_path_option(interpreter, path.as_deref()))
+                }
+            ),
+            Builtin::PathGlob => function1!(
+                path_glob,
                 self,
                 |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
-                    fn file_read_io(interpreter: &mut Interpreter<'_>, env: Rc<[Value]>) -> Value {
-                        let path = env[0].unpack_string();
-                        let contents =
-                            std::fs::read_to_string(path).unwrap_or_else(|err| panic!("{}", err));
-                        interpreter.alloc(Object::String(Rc::from(contents)))
+                    fn path_glob_io(
+                        interpreter: &mut Interpreter<'_>,
+                        env: Rc<[Value]>,
+                    ) -> Result<Value, RuntimeError> {
+                        let paths = path::glob(env[0].unpack_string());
+                        let paths = interpreter.alloc_values(paths.iter().map(|path| {
+                            interpreter.alloc(Object::String(
+                                interpreter.alloc_str(&path.to_string_lossy()),
+                            ))
+                        }));
+                        Ok(interpreter.alloc(Object::Array(paths)))
                     }
 
                     let env = interpreter.alloc_values([arg]);
-                    interpreter.alloc(Object::IO {
+                    Ok(interpreter.alloc(Object::IO {
                         env,
-                        body: IOBody(file_read_io),
+                        body: IOBody(path_glob_io),
+                    }))
+                }
+            ),
+            Builtin::RegexCompile => function1!(
+                regex_compile,
+                self,
+                |interpreter: &mut Interpreter<'_>, _: Rc<[Value]>, arg: Value| {
+                    Ok(match regex::Regex::new(arg.unpack_string()) {
+                        // Err error : (| Err : String, Ok : Regex |)
+                        Err(error) => {
+                            let message =
+                                interpreter.alloc(Object::String(Rc::from(error.to_string())));
+                            interpreter.alloc(Object::Variant(0, message))
+                        }
+                        // Ok regex : (| Err : String, Ok : Regex |)
+                        Ok(regex) => {
+                            let regex = interpreter.alloc(Object::Regex(regex));
+                            interpreter.alloc(Object::Variant(1, regex))
+                        }
                     })
                 }
             ),
-            Builtin::FileWrite => {
-                function2!(
-                    file_write,
-                    self,
-                    |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn file_write_io(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
-                            let path = env[0].unpack_string();
-                            let content = env[1].unpack_string();
-                            std::fs::write(path, content).unwrap_or_else(|err| panic!("{}", err));
-                            Value::Unit
+            Builtin::RegexIsMatch => function2!(
+                regex_is_match,
+                self,
+                |_: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let regex = env[0].unpack_regex();
+                    Ok(if regex.is_match(arg.unpack_string()) {
+                        Value::True
+                    } else {
+                        Value::False
+                    })
+                }
+            ),
+            Builtin::RegexFind => function2!(
+                regex_find,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let regex = env[0].unpack_regex();
+                    let found = regex.find(arg.unpack_string());
+                    Ok(alloc_string_option(
+                        interpreter,
+                        found.map(|found| found.as_str()),
+                    ))
+                }
+            ),
+            Builtin::RegexFindAll => function2!(
+                regex_find_all,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let regex = env[0].unpack_regex();
+                    let found = interpreter.alloc_values(regex.find_iter(arg.unpack_string()).map(
+                        |found| {
+                            interpreter.alloc(Object::String(interpreter.alloc_str(found.as_str())))
+                        },
+                    ));
+                    Ok(interpreter.alloc(Object::Array(found)))
+                }
+            ),
+            Builtin::RegexCaptures => function2!(
+                regex_captures,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let regex = env[0].unpack_regex();
+                    Ok(match regex.captures(arg.unpack_string()) {
+                        // None () : (| None : (), Some : Array (| None : (), Some : String |) |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some groups : (| None : (), Some : Array (| None : (), Some : String |) |)
+                        Some(captures) => {
+                            let groups = interpreter.alloc_values(captures.iter().map(|group| {
+                                alloc_string_option(interpreter, group.map(|group| group.as_str()))
+                            }));
+                            let groups = interpreter.alloc(Object::Array(groups));
+                            interpreter.alloc(Object::Variant(1, groups))
                         }
-                        let env = interpreter.alloc_values({
-                            let mut env = Vec::from(env.as_ref());
-                            env.push(arg);
-                            env
-                        });
-                        let closure = Object::IO {
-                            env,
-                            body: IOBody(file_write_io),
-                        };
-                        interpreter.alloc(closure)
-                    }
-                )
-            }
-            Builtin::FileAppend => {
-                function2!(
-                    file_append,
-                    self,
-                    |interpreter: &mut Interpreter, env: Rc<[Value]>, arg: Value| {
-                        fn file_append_io(_: &mut Interpreter, env: Rc<[Value]>) -> Value {
-                            let path = env[0].unpack_string();
-                            let content = env[1].unpack_string();
-                            let mut file = std::fs::OpenOptions::new()
-                                .create(true)
-                                .append(true)
-                                .open(path)
-                                .unwrap_or_else(|err| panic!("{}", err));
-                            file.write_all(content.as_bytes())
-                                .unwrap_or_else(|err| panic!("{}", err));
-                            Value::Unit
+                    })
+                }
+            ),
+            Builtin::RegexNamedCaptures => function2!(
+                regex_named_captures,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let regex = env[0].unpack_regex();
+                    Ok(match regex.captures(arg.unpack_string()) {
+                        // None () : (| None : (), Some : Map String String |)
+                        None => interpreter.alloc(Object::Variant(0, Value::Unit)),
+                        // Some groups : (| None : (), Some : Map String String |)
+                        Some(captures) => {
+                            let mut groups = map::Map::new();
+                            for name in regex.capture_names().flatten() {
+                                if let Some(group) = captures.name(name) {
+                                    let name = interpreter.alloc(Object::String(Rc::from(name)));
+                                    let group = interpreter.alloc(Object::String(
+                                        interpreter.alloc_str(group.as_str()),
+                                    ));
+                                    groups = groups.insert(name, group, |a, b| {
+                                        Ok::<_, RuntimeError>(
+                                            a.unpack_string().cmp(b.unpack_string()),
+                                        )
+                                    })?;
+                                }
+                            }
+                            let groups = interpreter.alloc(Object::Map(groups));
+                            interpreter.alloc(Object::Variant(1, groups))
                         }
-                        let env = interpreter.alloc_values({
-                            let mut env = Vec::from(env.as_ref());
-                            env.push(arg);
-                            env
-                        });
-                        let closure = Object::IO {
-                            env,
-                            body: IOBody(file_append_io),
-                        };
-                        interpreter.alloc(closure)
-                    }
-                )
-            }
+                    })
+                }
+            ),
+            Builtin::RegexReplace => function3!(
+                regex_replace,
+                self,
+                |interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value| {
+                    let regex = env[0].unpack_regex();
+                    let replacement = env[1].unpack_string();
+                    let string = regex.replace_all(arg.unpack_string(), replacement);
+                    Ok(interpreter.alloc(Object::String(interpreter.alloc_str(&string))))
+                }
+            ),
         }
     }
 
@@ -1620,7 +4258,7 @@ where {
         id: &ModuleRef,
         path: &[String],
         item: &Name,
-    ) -> Value {
+    ) -> Result<Value, RuntimeError> {
         fn lookup_path<'a>(bindings: &'a mut Bindings, path: &[String]) -> &'a mut Bindings {
             if path.is_empty() {
                 bindings
@@ -1688,7 +4326,207 @@ where {
         self.context.modules.last().copied()
     }
 
-    pub fn eval(&mut self, env: &mut Env, expr: &Expr) -> Value {
+    /**
+    Find the first branch whose pattern matches a value.
+
+    Returns the branch's body, and the values of the variables that are bound by the branch's
+    pattern in the order they should be pushed onto the environment.
+    */
+    fn match_branches<'a>(
+        &mut self,
+        env: &mut Env,
+        value: &Value,
+        branches: &'a [core::Branch<Expr>],
+    ) -> Result<Option<(Vec<Value>, &'a Expr)>, RuntimeError> {
+        match value {
+            Value::Object(object) if matches!(object.as_ref(), Object::Variant(_, _)) => {
+                let (tag, arg) = match object.as_ref() {
+                    Object::Variant(tag, arg) => (tag, arg),
+                    _ => unreachable!(),
+                };
+
+                /*
+                Because of the way constructors are peeled from variants during
+                pattern matching (see [note: peeling constructors when matching on variants]),
+                the expected tag must change as each branch is checked.
+                */
+                let mut expected_tag: usize = *tag;
+
+                for branch in branches {
+                    match &branch.pattern {
+                        Pattern::Variant {
+                            tag: branch_tag,
+                            arg: arg_pattern,
+                        } => {
+                            let branch_tag = self.eval(env, branch_tag)?.unpack_int() as usize;
+
+                            match expected_tag.cmp(&branch_tag) {
+                                /*
+                                When the expected tag is less than the branch's tag, it
+                                means that the expected constructor comes *before* the
+                                branch's constructor in the lexicographically ordered row
+                                type.
+
+                                The branch's contructor has no impact on the expected constructor's
+                                position in the row type, so the expected tag doesn't need to be adjusted.
+
+                                An illustration with an array:
+
+                                ```
+                                [a, b, c, d]
+                                ```
+
+                                `b` is at position 1 in the array. If `c` or `d` is removed, `b`'s position
+                                doesn't change, because it preceds `c` and `d`.
+                                */
+                                std::cmp::Ordering::Less => {}
+
+                                /*
+                                The constructor is only peeled when the branch's argument
+                                pattern is irrefutable, in which case it always matches.
+                                */
+                                std::cmp::Ordering::Equal => {
+                                    let mut bindings = Vec::new();
+                                    if self.match_pattern(env, arg_pattern, arg, &mut bindings)? {
+                                        return Ok(Some((bindings, &branch.body)));
+                                    }
+                                }
+
+                                /*
+                                When the expected tag is greater than the branch's tag, it
+                                means that the expected constructor comes *after* the
+                                branch's constructor in the lexicographically ordered row
+                                type.
+
+                                If the branch's constructor was peeled, then it influences
+                                the expected constructor's position in the row type, so the
+                                expected tag *does* need to be adjusted.
+
+                                Another array illustration:
+
+                                ```
+                                [a, b, c, d]
+                                ```
+
+                                `c` is at position 2 in the array. If `a` or `b` is removed then `c`'s position
+                                changes.
+                                */
+                                std::cmp::Ordering::Greater => {
+                                    if arg_pattern.is_irrefutable() {
+                                        expected_tag -= 1;
+                                    }
+                                }
+                            }
+                        }
+                        Pattern::Name => {
+                            return Ok(Some((
+                                vec![self.alloc(Object::Variant(expected_tag, arg.clone()))],
+                                &branch.body,
+                            )));
+                        }
+                        Pattern::Wildcard => {
+                            return Ok(Some((Vec::new(), &branch.body)));
+                        }
+                        Pattern::String(_)
+                        | Pattern::Int(_)
+                        | Pattern::Record { .. }
+                        | Pattern::Array(_)
+                        | Pattern::Char(_) => {
+                            panic!("expected variant pattern, got: {:?}", branch.pattern);
+                        }
+                    }
+                }
+
+                Ok(None)
+            }
+            _ => {
+                for branch in branches {
+                    let mut bindings = Vec::new();
+                    if self.match_pattern(env, &branch.pattern, value, &mut bindings)? {
+                        return Ok(Some((bindings, &branch.body)));
+                    }
+                }
+
+                Ok(None)
+            }
+        }
+    }
+
+    /**
+    Match a value against a (possibly nested) pattern.
+
+    The values of the pattern's variables are appended to `bindings`, from left to right.
+    Expressions in the pattern are evaluated in `env`, which is the environment of the
+    `case` expression (see [`core::Pattern`]).
+    */
+    fn match_pattern(
+        &mut self,
+        env: &mut Env,
+        pattern: &Pattern<Expr>,
+        value: &Value,
+        bindings: &mut Vec<Value>,
+    ) -> Result<bool, RuntimeError> {
+        match pattern {
+            Pattern::Name => {
+                bindings.push(value.clone());
+                Ok(true)
+            }
+            Pattern::Wildcard => Ok(true),
+            Pattern::Record { fields, rest } => {
+                let values = value.unpack_record();
+                let mut extracted: Vec<usize> = fields
+                    .iter()
+                    .map(|(index, _)| Ok(self.eval(env, index)?.unpack_int() as usize))
+                    .collect::<Result<_, RuntimeError>>()?;
+
+                for (index, (_, field_pattern)) in extracted.iter().zip(fields.iter()) {
+                    if !self.match_pattern(env, field_pattern, &values[*index], bindings)? {
+                        return Ok(false);
+                    }
+                }
+
+                if *rest {
+                    let mut leftover_fields = Rope::from_vec(values.as_ref());
+                    extracted.sort_unstable();
+                    for ix in extracted.iter().rev() {
+                        leftover_fields = leftover_fields.delete(*ix).unwrap();
+                    }
+                    let leftover_fields = self.alloc_values(leftover_fields.iter().cloned());
+                    bindings.push(self.alloc(Object::Record(leftover_fields)));
+                }
+
+                Ok(true)
+            }
+            Pattern::Variant { tag, arg } => {
+                let (actual_tag, actual_arg) = value.unpack_variant();
+                let tag = self.eval(env, tag)?.unpack_int() as usize;
+                if *actual_tag == tag {
+                    self.match_pattern(env, arg, actual_arg, bindings)
+                } else {
+                    Ok(false)
+                }
+            }
+            Pattern::Array(items) => {
+                let values = value.unpack_array();
+                if values.len() != items.len() {
+                    return Ok(false);
+                }
+                for (item, value) in items.iter().zip(values.iter()) {
+                    if !self.match_pattern(env, item, value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Char(expected_char) => Ok(value.unpack_char() == *expected_char),
+            Pattern::Int(expected_int) => Ok(value.unpack_int() == *expected_int),
+            Pattern::String(expected_string) => {
+                Ok(value.unpack_string() == expected_string.as_ref())
+            }
+        }
+    }
+
+    pub fn eval(&mut self, env: &mut Env, expr: &Expr) -> Result<Value, RuntimeError> {
         fn lookup_index(env: &Env, ix: usize) -> Value {
             let env_len = env.len();
             if ix < env_len {
@@ -1712,15 +4550,15 @@ where {
             Expr::Var(ix) => lookup_index(env, *ix),
             Expr::Name(name) => {
                 let body = self.lookup_name(name);
-                self.eval(env, body.as_ref())
+                self.eval(env, body.as_ref())?
             }
-            Expr::Module { id, path, item } => self.eval_from_module(env, id, path, item),
+            Expr::Module { id, path, item } => self.eval_from_module(env, id, path, item)?,
             Expr::Builtin(name) => self.eval_builtin(name),
 
             Expr::App(a, b) => {
-                let a = self.eval(env, a);
-                let b = self.eval(env, b);
-                a.apply(self, b)
+                let a = self.eval(env, a)?;
+                let b = self.eval(env, b)?;
+                a.apply(self, b)?
             }
             Expr::Lam {
                 env: new_env,
@@ -1734,48 +4572,56 @@ where {
             }),
 
             Expr::Let { value, rest } => {
-                let value = self.eval(env, value);
+                let value = self.eval(env, value)?;
+                let env_len = env.len();
                 env.push(value);
-                self.eval(env, rest)
+                let value = self.eval(env, rest)?;
+                env.truncate(env_len);
+                value
             }
 
             Expr::True => Value::True,
             Expr::False => Value::False,
             Expr::IfThenElse(cond, t, e) => {
-                let cond = self.eval(env, cond);
+                let cond = self.eval(env, cond)?;
                 match cond {
-                    Value::True => self.eval(env, t),
-                    Value::False => self.eval(env, e),
+                    Value::True => self.eval(env, t)?,
+                    Value::False => self.eval(env, e)?,
                     cond => panic!("expected bool, got {:?}", cond),
                 }
             }
 
             Expr::Int(n) => Value::Int(*n),
+            Expr::Float(n) => Value::Float(n.0),
 
             Expr::Binop(op, a, b) => match op {
-                Binop::Add => {
-                    let a = self.eval(env, a).unpack_int();
-                    let b = self.eval(env, b).unpack_int();
-                    Value::Int(a + b)
-                }
-                Binop::Multiply => {
-                    let a = self.eval(env, a).unpack_int();
-                    let b = self.eval(env, b).unpack_int();
-                    Value::Int(a * b)
-                }
-                Binop::Subtract => {
-                    let a = self.eval(env, a).unpack_int();
-                    let b = self.eval(env, b).unpack_int();
-                    Value::Int(a - b)
-                }
-                Binop::Divide => {
-                    let a = self.eval(env, a).unpack_int();
-                    let b = self.eval(env, b).unpack_int();
-                    Value::Int(a / b)
-                }
+                Binop::Add => match (self.eval(env, a)?, self.eval(env, b)?) {
+                    (Value::Float(a), Value::Float(b)) => Value::Float(a + b),
+                    (a, b) => checked_int(a.unpack_int().checked_add(b.unpack_int()))?,
+                },
+                Binop::Multiply => match (self.eval(env, a)?, self.eval(env, b)?) {
+                    (Value::Float(a), Value::Float(b)) => Value::Float(a * b),
+                    (a, b) => checked_int(a.unpack_int().checked_mul(b.unpack_int()))?,
+                },
+                Binop::Subtract => match (self.eval(env, a)?, self.eval(env, b)?) {
+                    (Value::Float(a), Value::Float(b)) => Value::Float(a - b),
+                    (a, b) => checked_int(a.unpack_int().checked_sub(b.unpack_int()))?,
+                },
+                Binop::Divide => match (self.eval(env, a)?, self.eval(env, b)?) {
+                    // Float division follows IEEE 754, so dividing by zero gives an infinity or NaN.
+                    (Value::Float(a), Value::Float(b)) => Value::Float(a / b),
+                    (a, b) => {
+                        let a = a.unpack_int();
+                        let b = b.unpack_int();
+                        if b == 0 {
+                            return Err(RuntimeError::new(RuntimeErrorInfo::DivisionByZero));
+                        }
+                        checked_int(a.checked_div(b))?
+                    }
+                },
                 Binop::Append => {
-                    let a = self.eval(env, a).unpack_array();
-                    let b = self.eval(env, b).unpack_array();
+                    let a = self.eval(env, a)?.unpack_array();
+                    let b = self.eval(env, b)?.unpack_array();
                     let c = {
                         let mut c = Vec::with_capacity(a.len() + b.len());
                         c.extend(a.as_ref().iter().cloned());
@@ -1785,28 +4631,28 @@ where {
                     self.alloc(Object::Array(self.alloc_values(c)))
                 }
                 Binop::Or => {
-                    if self.eval(env, a).unpack_bool() {
+                    if self.eval(env, a)?.unpack_bool() {
                         Value::True
                     } else {
-                        self.eval(env, b)
+                        self.eval(env, b)?
                     }
                 }
                 Binop::And => {
-                    if self.eval(env, a).unpack_bool() {
-                        self.eval(env, b)
+                    if self.eval(env, a)?.unpack_bool() {
+                        self.eval(env, b)?
                     } else {
                         Value::False
                     }
                 }
                 Binop::RApply => {
-                    let left = self.eval(env, a);
-                    let right = self.eval(env, b);
-                    right.apply(self, left)
+                    let left = self.eval(env, a)?;
+                    let right = self.eval(env, b)?;
+                    right.apply(self, left)?
                 }
                 Binop::LApply => {
-                    let left = self.eval(env, a);
-                    let right = self.eval(env, b);
-                    left.apply(self, right)
+                    let left = self.eval(env, a)?;
+                    let right = self.eval(env, b)?;
+                    left.apply(self, right)?
                 }
             },
 
@@ -1818,7 +4664,7 @@ where {
                 for part in parts {
                     match part {
                         StringPart::Expr(expr) => {
-                            let s = self.eval(env, expr);
+                            let s = self.eval(env, expr)?;
                             let s = s.unpack_string();
                             value.push_str(s);
                         }
@@ -1830,15 +4676,18 @@ where {
             }
 
             Expr::Array(items) => {
-                let items: Vec<Value> = items.iter().map(|item| self.eval(env, item)).collect();
+                let items: Vec<Value> = items
+                    .iter()
+                    .map(|item| self.eval(env, item))
+                    .collect::<Result<_, _>>()?;
                 let items = self.alloc_values(items);
                 self.alloc(Object::Array(items))
             }
 
             Expr::Extend(ev, value, rest) => {
-                let ix = self.eval(env, ev).unpack_int();
-                let value = self.eval(env, value);
-                let rest = self.eval(env, rest);
+                let ix = self.eval(env, ev)?.unpack_int();
+                let value = self.eval(env, value)?;
+                let rest = self.eval(env, rest)?;
                 match rest.unpack_object() {
                     Object::Record(fields) => {
                         // assume: all stacks in fields are non-empty
@@ -1858,10 +4707,12 @@ where {
             }
             Expr::Record(fields) => {
                 let mut record: Vec<Value> = Vec::with_capacity(fields.len());
-                let mut fields: Vec<(i32, Value)> = fields
+                let mut fields: Vec<(i64, Value)> = fields
                     .iter()
-                    .map(|(ev, field)| (self.eval(env, ev).unpack_int(), self.eval(env, field)))
-                    .collect();
+                    .map(|(ev, field)| {
+                        Ok((self.eval(env, ev)?.unpack_int(), self.eval(env, field)?))
+                    })
+                    .collect::<Result<_, RuntimeError>>()?;
                 fields.sort_by_key(|x| x.0);
                 for (_index, field) in fields.into_iter() {
                     record.push(field);
@@ -1871,8 +4722,8 @@ where {
                 self.alloc(Object::Record(record))
             }
             Expr::Project(expr, index) => {
-                let index = self.eval(env, index).unpack_int();
-                let expr = self.eval(env, expr);
+                let index = self.eval(env, index)?.unpack_int();
+                let expr = self.eval(env, expr)?;
                 match expr.unpack_object() {
                     Object::Record(fields) => fields[index as usize].clone(),
                     expr => panic!("expected record, got {:?}", expr),
@@ -1880,11 +4731,15 @@ where {
             }
 
             Expr::Variant(tag) => {
-                let tag = self.eval(env, tag);
+                let tag = self.eval(env, tag)?;
                 let env = self.alloc_values(vec![tag]);
-                fn code(interpreter: &mut Interpreter<'_>, env: Rc<[Value]>, arg: Value) -> Value {
+                fn code(
+                    interpreter: &mut Interpreter<'_>,
+                    env: Rc<[Value]>,
+                    arg: Value,
+                ) -> Result<Value, RuntimeError> {
                     let tag = env[0].unpack_int() as usize;
-                    interpreter.alloc(Object::Variant(tag, arg))
+                    Ok(interpreter.alloc(Object::Variant(tag, arg)))
                 }
                 let closure = Object::StaticClosure {
                     env,
@@ -1893,8 +4748,8 @@ where {
                 self.alloc(closure)
             }
             Expr::Embed(tag, rest) => {
-                let tag = self.eval(env, tag).unpack_int() as usize;
-                let rest = self.eval(env, rest);
+                let tag = self.eval(env, tag)?.unpack_int() as usize;
+                let rest = self.eval(env, rest)?;
                 let (&old_tag, arg) = rest.unpack_variant();
                 self.alloc(Object::Variant(
                     if tag <= old_tag { old_tag + 1 } else { old_tag },
@@ -1902,201 +4757,63 @@ where {
                 ))
             }
             Expr::Case(expr, branches) => {
-                let expr = self.eval(env, expr);
-
-                let mut target: Option<&Expr> = None;
-
-                match expr {
-                    Value::Object(object) if matches!(object.as_ref(), Object::Variant(_, _)) => {
-                        let (tag, value) = match object.as_ref() {
-                            Object::Variant(tag, value) => (tag, value),
-                            _ => unreachable!(),
-                        };
-
-                        /*
-                        Because of the way constructors are peeled from variants during
-                        pattern matching (see [note: peeling constructors when matching on variants]),
-                        the expected tag must change as each branch is checked.
-                        */
-                        let mut expected_tag: usize = *tag;
-
-                        for branch in branches {
-                            match &branch.pattern {
-                                Pattern::Variant { tag: branch_tag } => {
-                                    let branch_tag =
-                                        self.eval(env, branch_tag).unpack_int() as usize;
-
-                                    match expected_tag.cmp(&branch_tag) {
-                                        /*
-                                        When the expected tag is less than the branch's tag, it
-                                        means that the expected constructor comes *before* the
-                                        branch's constructor in the lexicographically ordered row
-                                        type.
-
-                                        The branch's contructor has no impact on the expected constructor's
-                                        position in the row type, so the expected tag doesn't need to be adjusted.
-
-                                        An illustration with an array:
-
-                                        ```
-                                        [a, b, c, d]
-                                        ```
-
-                                        `b` is at position 1 in the array. If `c` or `d` is removed, `b`'s position
-                                        doesn't change, because it preceds `c` and `d`.
-                                        */
-                                        std::cmp::Ordering::Less => {}
-
-                                        std::cmp::Ordering::Equal => {
-                                            env.push(value.clone());
-                                            target = Some(&branch.body);
-                                            break;
-                                        }
-
-                                        /*
-                                        When the expected tag is less than the branch's tag, it
-                                        means that the expected constructor comes *after* the
-                                        branch's constructor in the lexicographically ordered row
-                                        type.
-
-                                        The branch's contructor influences the expected constructor's
-                                        position in the row type, so the expected tag *does* need to be adjusted.
-
-                                        Another array illustration:
-
-                                        ```
-                                        [a, b, c, d]
-                                        ```
-
-                                        `c` is at position 2 in the array. If `a` or `b` is removed then `c`'s position
-                                        changes.
-                                        */
-                                        std::cmp::Ordering::Greater => {
-                                            expected_tag -= 1;
-                                        }
-                                    }
-                                }
-                                Pattern::Name => {
-                                    env.push(
-                                        self.alloc(Object::Variant(expected_tag, value.clone())),
-                                    );
-
-                                    target = Some(&branch.body);
-                                    break;
-                                }
-                                Pattern::Wildcard => {
-                                    target = Some(&branch.body);
-                                    break;
-                                }
-                                Pattern::String(_)
-                                | Pattern::Int(_)
-                                | Pattern::Record { .. }
-                                | Pattern::Char(_) => {
-                                    panic!("expected variant pattern, got: {:?}", branch.pattern);
-                                }
-                            }
-                        }
+                let expr = self.eval(env, expr)?;
+
+                match self.match_branches(env, &expr, branches)? {
+                    Some((bindings, body)) => {
+                        let env_len = env.len();
+                        bindings.into_iter().for_each(|binding| env.push(binding));
+                        let value = self.eval(env, body)?;
+                        env.truncate(env_len);
+                        value
                     }
-                    _ => {
-                        for branch in branches {
-                            match &branch.pattern {
-                                Pattern::Name => {
-                                    env.push(expr);
-                                    target = Some(&branch.body);
-                                    break;
-                                }
-                                Pattern::Record { names, rest } => {
-                                    let fields = expr.unpack_record();
-                                    let mut extracted: Vec<usize> = names
-                                        .iter()
-                                        .map(|name| self.eval(env, name).unpack_int() as usize)
-                                        .collect();
-
-                                    let leftover_record = if *rest {
-                                        let mut leftover_fields = Rope::from_vec(fields.as_ref());
-                                        extracted.sort_unstable();
-                                        for ix in extracted.iter().rev() {
-                                            leftover_fields = leftover_fields.delete(*ix).unwrap();
-                                        }
-                                        let leftover_fields =
-                                            self.alloc_values(leftover_fields.iter().cloned());
-
-                                        Some(self.alloc(Object::Record(leftover_fields)))
-                                    } else {
-                                        None
-                                    };
-
-                                    for ix in extracted.iter() {
-                                        env.push(fields[*ix].clone());
-                                    }
-                                    if let Some(leftover_record) = leftover_record {
-                                        env.push(leftover_record)
-                                    };
-
-                                    target = Some(&branch.body);
-                                    break;
-                                }
-                                Pattern::Variant { tag: branch_tag } => {
-                                    let (tag, value) = expr.unpack_variant();
-                                    let branch_tag =
-                                        self.eval(env, branch_tag).unpack_int() as usize;
-                                    if *tag == branch_tag {
-                                        env.push(value.clone());
-                                        target = Some(&branch.body);
-                                        break;
-                                    }
-                                }
-                                Pattern::Char(actual_char) => {
-                                    let expected_char = expr.unpack_char();
-                                    if expected_char == *actual_char {
-                                        target = Some(&branch.body);
-                                        break;
-                                    }
-                                }
-                                Pattern::Int(actual_int) => {
-                                    let expected_int = expr.unpack_int();
-                                    if expected_int == *actual_int {
-                                        target = Some(&branch.body);
-                                        break;
-                                    }
-                                }
-                                Pattern::String(actual_string) => {
-                                    let expected_string = expr.unpack_string();
-                                    if expected_string == actual_string.as_ref() {
-                                        target = Some(&branch.body);
-                                        break;
-                                    }
-                                }
-                                Pattern::Wildcard => {
-                                    target = Some(&branch.body);
-                                    break;
-                                }
-                            }
-                        }
+                    None => {
+                        return Err(RuntimeError::new(RuntimeErrorInfo::IncompletePatternMatch))
                     }
                 }
-
-                match target {
-                    Some(target) => self.eval(env, target),
-                    None => panic!("incomplete pattern match"),
-                }
             }
             Expr::Unit => Value::Unit,
             Expr::Cmd(parts) => {
-                let mut new_parts: Vec<Rc<str>> = Vec::with_capacity(parts.len());
+                let mut builder = cmd::Builder::new();
                 for part in parts {
                     match part {
-                        CmdPart::Literal(value) => new_parts.push(value.clone()),
+                        CmdPart::Literal(value) => builder.args([value.clone()])?,
                         CmdPart::Expr(expr) => {
-                            let args = self.eval(env, expr).unpack_array();
-                            new_parts
-                                .extend(args.iter().map(|value| Rc::from(value.unpack_string())));
+                            let args = self.eval(env, expr)?.unpack_array();
+                            builder
+                                .args(args.iter().map(|value| Rc::from(value.unpack_string())))?;
                         }
+                        CmdPart::Pipe => builder.pipe(),
+                        CmdPart::Redirect(redirect) => builder.redirect(*redirect)?,
                     }
                 }
-                self.alloc(Object::Cmd(new_parts))
+                self.alloc(Object::Cmd(builder.build()))
+            }
+            Expr::Located { pos, expr } => {
+                let module = self.current_module();
+                let value = self
+                    .eval(env, expr)
+                    .map_err(|error| self.locate(error, module, *pos))?;
+
+                /*
+                IO actions are performed after they're evaluated, so a performed action
+                remembers the position of the expression that produced it.
+                */
+                let is_io = match &value {
+                    Value::Object(object) => matches!(object.as_ref(), Object::IO { .. }),
+                    _ => false,
+                };
+                if is_io {
+                    self.alloc(Object::LocatedIO {
+                        module,
+                        pos: *pos,
+                        io: value,
+                    })
+                } else {
+                    value
+                }
             }
         };
-        out
+        Ok(out)
     }
 }
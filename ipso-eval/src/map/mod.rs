@@ -0,0 +1,297 @@
+//! Persistent ordered maps.
+
+#[cfg(test)]
+mod test;
+
+use std::{cmp::Ordering, fmt::Debug, rc::Rc};
+
+/**
+A persistent ordered map, implemented as an AVL tree.
+
+Updates return a new map that shares most of its structure with the original.
+
+The keys of ipso values are ordered by their `Ord` instances, which can only be run by the
+interpreter. Instead of requiring `K: Ord`, each operation that searches the map takes a
+comparison function, which may fail.
+*/
+pub struct Map<K, V> {
+    root: Tree<K, V>,
+}
+
+type Tree<K, V> = Option<Rc<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    size: usize,
+    left: Tree<K, V>,
+    right: Tree<K, V>,
+}
+
+fn height<K, V>(tree: &Tree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.height)
+}
+
+fn size<K, V>(tree: &Tree<K, V>) -> usize {
+    tree.as_ref().map_or(0, |node| node.size)
+}
+
+fn node<K, V>(key: K, value: V, left: Tree<K, V>, right: Tree<K, V>) -> Tree<K, V> {
+    Some(Rc::new(Node {
+        key,
+        value,
+        height: 1 + height(&left).max(height(&right)),
+        size: 1 + size(&left) + size(&right),
+        left,
+        right,
+    }))
+}
+
+/**
+Construct a node whose subtrees' heights differ by at most 2, rotating it so that the heights
+differ by at most 1.
+*/
+fn balance<K: Clone, V: Clone>(
+    key: K,
+    value: V,
+    left: Tree<K, V>,
+    right: Tree<K, V>,
+) -> Tree<K, V> {
+    let left_height = height(&left);
+    let right_height = height(&right);
+
+    if left_height > right_height + 1 {
+        let l = left.as_ref().unwrap();
+        if height(&l.left) >= height(&l.right) {
+            node(
+                l.key.clone(),
+                l.value.clone(),
+                l.left.clone(),
+                node(key, value, l.right.clone(), right),
+            )
+        } else {
+            let lr = l.right.as_ref().unwrap();
+            node(
+                lr.key.clone(),
+                lr.value.clone(),
+                node(
+                    l.key.clone(),
+                    l.value.clone(),
+                    l.left.clone(),
+                    lr.left.clone(),
+                ),
+                node(key, value, lr.right.clone(), right),
+            )
+        }
+    } else if right_height > left_height + 1 {
+        let r = right.as_ref().unwrap();
+        if height(&r.right) >= height(&r.left) {
+            node(
+                r.key.clone(),
+                r.value.clone(),
+                node(key, value, left, r.left.clone()),
+                r.right.clone(),
+            )
+        } else {
+            let rl = r.left.as_ref().unwrap();
+            node(
+                rl.key.clone(),
+                rl.value.clone(),
+                node(key, value, left, rl.left.clone()),
+                node(
+                    r.key.clone(),
+                    r.value.clone(),
+                    rl.right.clone(),
+                    r.right.clone(),
+                ),
+            )
+        }
+    } else {
+        node(key, value, left, right)
+    }
+}
+
+fn insert<K: Clone, V: Clone, E>(
+    tree: &Tree<K, V>,
+    key: K,
+    value: V,
+    compare: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+) -> Result<Tree<K, V>, E> {
+    match tree {
+        None => Ok(node(key, value, None, None)),
+        Some(n) => Ok(match compare(&key, &n.key)? {
+            Ordering::Less => balance(
+                n.key.clone(),
+                n.value.clone(),
+                insert(&n.left, key, value, compare)?,
+                n.right.clone(),
+            ),
+            Ordering::Greater => balance(
+                n.key.clone(),
+                n.value.clone(),
+                n.left.clone(),
+                insert(&n.right, key, value, compare)?,
+            ),
+            Ordering::Equal => node(key, value, n.left.clone(), n.right.clone()),
+        }),
+    }
+}
+
+/// Remove a node's least entry, returning the entry and the remaining tree.
+fn remove_min<K: Clone, V: Clone>(n: &Node<K, V>) -> (K, V, Tree<K, V>) {
+    match &n.left {
+        None => (n.key.clone(), n.value.clone(), n.right.clone()),
+        Some(left) => {
+            let (key, value, left) = remove_min(left);
+            (
+                key,
+                value,
+                balance(n.key.clone(), n.value.clone(), left, n.right.clone()),
+            )
+        }
+    }
+}
+
+fn remove<K: Clone, V: Clone, E>(
+    tree: &Tree<K, V>,
+    key: &K,
+    compare: &mut impl FnMut(&K, &K) -> Result<Ordering, E>,
+) -> Result<Tree<K, V>, E> {
+    match tree {
+        None => Ok(None),
+        Some(n) => Ok(match compare(key, &n.key)? {
+            Ordering::Less => balance(
+                n.key.clone(),
+                n.value.clone(),
+                remove(&n.left, key, compare)?,
+                n.right.clone(),
+            ),
+            Ordering::Greater => balance(
+                n.key.clone(),
+                n.value.clone(),
+                n.left.clone(),
+                remove(&n.right, key, compare)?,
+            ),
+            Ordering::Equal => match &n.right {
+                None => n.left.clone(),
+                Some(right) => {
+                    let (key, value, right) = remove_min(right);
+                    balance(key, value, n.left.clone(), right)
+                }
+            },
+        }),
+    }
+}
+
+impl<K, V> Map<K, V> {
+    pub fn new() -> Self {
+        Map { root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn get<E>(
+        &self,
+        key: &K,
+        mut compare: impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Option<&V>, E> {
+        let mut tree = &self.root;
+        while let Some(n) = tree {
+            match compare(key, &n.key)? {
+                Ordering::Less => tree = &n.left,
+                Ordering::Greater => tree = &n.right,
+                Ordering::Equal => return Ok(Some(&n.value)),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Iterate over the map's entries in ascending order of their keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(&self.root);
+        iter
+    }
+}
+
+impl<K: Clone, V: Clone> Map<K, V> {
+    /// Insert an entry, replacing any entry that has an equal key.
+    pub fn insert<E>(
+        &self,
+        key: K,
+        value: V,
+        mut compare: impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Self, E> {
+        Ok(Map {
+            root: insert(&self.root, key, value, &mut compare)?,
+        })
+    }
+
+    pub fn remove<E>(
+        &self,
+        key: &K,
+        mut compare: impl FnMut(&K, &K) -> Result<Ordering, E>,
+    ) -> Result<Self, E> {
+        Ok(Map {
+            root: remove(&self.root, key, &mut compare)?,
+        })
+    }
+}
+
+impl<K, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for Map<K, V> {
+    fn clone(&self) -> Self {
+        Map {
+            root: self.root.clone(),
+        }
+    }
+}
+
+/// Maps with the same entries are equal, regardless of their trees' shapes.
+impl<K: PartialEq, V: PartialEq> PartialEq for Map<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Map<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    /// The nodes whose entries and right subtrees haven't been visited.
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut tree: &'a Tree<K, V>) {
+        while let Some(n) = tree {
+            self.stack.push(n);
+            tree = &n.left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.stack.pop()?;
+        self.push_left(&n.right);
+        Some((&n.key, &n.value))
+    }
+}
@@ -0,0 +1,47 @@
+use super::{Map, Tree};
+use std::{cmp::Ordering, convert::Infallible};
+
+fn compare(a: &i64, b: &i64) -> Result<Ordering, Infallible> {
+    Ok(a.cmp(b))
+}
+
+fn check_balanced(map: &Map<i64, ()>) {
+    fn go(tree: &Tree<i64, ()>) -> usize {
+        match tree {
+            None => 0,
+            Some(n) => {
+                let left = go(&n.left);
+                let right = go(&n.right);
+                assert!(left.max(right) - left.min(right) <= 1);
+                assert_eq!(n.height, 1 + left.max(right));
+                n.height
+            }
+        }
+    }
+    go(&map.root);
+}
+
+#[test]
+fn insert_remove() {
+    let mut map = Map::new();
+    for key in (0..100).map(|n| (n * 37) % 100) {
+        map = map.insert(key, (), compare).unwrap();
+        check_balanced(&map);
+    }
+    assert_eq!(map.len(), 100);
+    assert!(map
+        .iter()
+        .map(|(key, _)| *key)
+        .eq((0..100).collect::<Vec<_>>()));
+
+    let old_map = map.clone();
+    for key in (0..100).filter(|n| n % 3 == 0) {
+        map = map.remove(&key, compare).unwrap();
+        check_balanced(&map);
+    }
+    assert_eq!(map.len(), 66);
+    assert_eq!(map.get(&3, compare), Ok(None));
+    assert_eq!(map.get(&4, compare), Ok(Some(&())));
+    assert_eq!(old_map.len(), 100);
+    assert_eq!(old_map.get(&3, compare), Ok(Some(&())));
+}
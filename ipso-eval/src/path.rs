@@ -0,0 +1,235 @@
+//! Path manipulation and glob expansion.
+
+use std::path::{Component, Path, PathBuf};
+
+/**
+Remove `.` components and redundant separators, and resolve `..` components against the
+component before them.
+
+This is purely lexical, so it doesn't follow symbolic links. Leading `..` components of a
+relative path are kept, and `/..` is `/`.
+*/
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut components: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match components.last() {
+                Some(Component::Normal(_)) => {
+                    components.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                Some(Component::ParentDir | Component::CurDir) | None => components.push(component),
+            },
+            _ => components.push(component),
+        }
+    }
+    if components.is_empty() {
+        PathBuf::from(".")
+    } else {
+        components.iter().collect()
+    }
+}
+
+/**
+Find a relative path that leads from `base` to `path`.
+
+Both paths are [`normalize`]d first. Returns [`None`] when one path is absolute and the other
+is relative, or when `base` escapes a directory whose name can't be known (e.g. `path` is `a`
+and `base` is `..`).
+*/
+pub fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+    fn components(path: &Path) -> Vec<Component> {
+        path.components()
+            .filter(|component| *component != Component::CurDir)
+            .collect()
+    }
+
+    let path = normalize(path);
+    let base = normalize(base);
+    if path.has_root() != base.has_root() {
+        return None;
+    }
+    let path = components(&path);
+    let base = components(&base);
+    let common = path
+        .iter()
+        .zip(base.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if base[common..].contains(&Component::ParentDir) {
+        return None;
+    }
+    let result: PathBuf = base[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(path[common..].iter().copied())
+        .collect();
+    Some(if result.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        result
+    })
+}
+
+/**
+Find the paths that match a glob pattern, sorted.
+
+Within a path component, `*` matches any sequence of characters, `?` matches a single
+character, and `[...]` matches a single character from a set such as `[abc]` or `[a-z]`
+(`[!...]` negates the set). A component that is exactly `**` matches any number of
+directories. Wildcards don't match names that start with `.` unless the component
+explicitly starts with `.`.
+
+Directories that can't be read are skipped.
+*/
+pub fn glob(pattern: &str) -> Vec<PathBuf> {
+    let (root, pattern) = match pattern.strip_prefix('/') {
+        Some(pattern) => (PathBuf::from("/"), pattern),
+        None => (PathBuf::new(), pattern),
+    };
+    let segments: Vec<&str> = pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let mut results = Vec::new();
+    if !segments.is_empty() {
+        expand(&root, &segments, &mut results);
+    }
+    results.sort();
+    results.dedup();
+    results
+}
+
+fn expand(dir: &Path, segments: &[&str], results: &mut Vec<PathBuf>) {
+    let (segment, rest) = match segments.split_first() {
+        None => {
+            results.push(dir.to_path_buf());
+            return;
+        }
+        Some(split) => split,
+    };
+
+    if *segment == "**" {
+        if !rest.is_empty() {
+            expand(dir, rest, results);
+        }
+        for path in entries(dir, |name| !name.starts_with('.')) {
+            if rest.is_empty() {
+                results.push(path.clone());
+            }
+            // Symbolic links to directories aren't followed, because they could form a cycle.
+            if path
+                .symlink_metadata()
+                .map_or(false, |metadata| metadata.is_dir())
+            {
+                expand(&path, segments, results);
+            }
+        }
+    } else if !segment.contains(['*', '?', '[']) {
+        let path = dir.join(segment);
+        if rest.is_empty() {
+            if path.symlink_metadata().is_ok() {
+                results.push(path);
+            }
+        } else if path.is_dir() {
+            expand(&path, rest, results);
+        }
+    } else {
+        let pattern: Vec<char> = segment.chars().collect();
+        for path in entries(dir, |name| {
+            (pattern[0] == '.' || !name.starts_with('.'))
+                && matches(&pattern, &name.chars().collect::<Vec<char>>())
+        }) {
+            if rest.is_empty() {
+                results.push(path);
+            } else if path.is_dir() {
+                expand(&path, rest, results);
+            }
+        }
+    }
+}
+
+/// The paths of the entries in `dir` whose names satisfy `predicate`.
+fn entries(dir: &Path, predicate: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let read_dir = if dir.as_os_str().is_empty() {
+        std::fs::read_dir(".")
+    } else {
+        std::fs::read_dir(dir)
+    };
+    match read_dir {
+        Err(_) => Vec::new(),
+        Ok(read_dir) => read_dir
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                let name = name.to_str()?;
+                if predicate(name) {
+                    Some(dir.join(name))
+                } else {
+                    None
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Match a single path component against a wildcard pattern.
+fn matches(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((&'*', rest)) => (0..=name.len()).any(|index| matches(rest, &name[index..])),
+        Some((&'?', rest)) => match name.split_first() {
+            Some((_, name)) => matches(rest, name),
+            None => false,
+        },
+        Some((&'[', rest)) => match char_class(rest) {
+            Some((class_matches, rest)) => match name.split_first() {
+                Some((c, name)) => class_matches(*c) && matches(rest, name),
+                None => false,
+            },
+            // An unterminated `[` matches itself.
+            None => name.first() == Some(&'[') && matches(rest, &name[1..]),
+        },
+        Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+    }
+}
+
+/**
+Parse the contents of a `[...]` character class, starting after the `[`.
+
+Returns a predicate for the class and the rest of the pattern.
+*/
+fn char_class(pattern: &[char]) -> Option<(impl Fn(char) -> bool, &[char])> {
+    let (negated, pattern) = match pattern.split_first() {
+        Some((&('!' | '^'), pattern)) => (true, pattern),
+        _ => (false, pattern),
+    };
+    let mut ranges = Vec::new();
+    let mut index = 0;
+    loop {
+        match pattern.get(index) {
+            None => return None,
+            // A `]` at the start of the class is part of the class.
+            Some(&']') if index > 0 => break,
+            Some(start) => match (pattern.get(index + 1), pattern.get(index + 2)) {
+                (Some(&'-'), Some(end)) if *end != ']' => {
+                    ranges.push((*start, *end));
+                    index += 3;
+                }
+                _ => {
+                    ranges.push((*start, *start));
+                    index += 1;
+                }
+            },
+        }
+    }
+    Some((
+        move |c: char| {
+            ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&c))
+                != negated
+        },
+        &pattern[index + 1..],
+    ))
+}
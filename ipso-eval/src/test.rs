@@ -1,6 +1,10 @@
 use super::{Interpreter, Value};
-use crate::{closure_conversion::Expr, Env, Object};
-use ipso_core::{Builtin, CommonKinds, StringPart};
+use crate::{
+    checked_div_floor, closure_conversion::Expr, encoding, json, merge_sort_by, mod_floor, path,
+    Env, Object, RuntimeErrorInfo,
+};
+use ipso_core::{Binop, Branch, Builtin, CommonKinds, Pattern, StringPart};
+use ipso_diagnostic::{Location, Source};
 use ipso_syntax::Modules;
 use std::{collections::HashMap, rc::Rc};
 
@@ -18,12 +22,21 @@ fn eval_1() {
     let common_kinds = CommonKinds::default();
     let context = HashMap::new();
     let modules = Modules::new();
-    let mut interpreter =
-        Interpreter::new(&mut stdin, &mut stdout, &common_kinds, &modules, &context);
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+    let mut interpreter = Interpreter::new(
+        &mut stdin,
+        &mut stdout,
+        &common_kinds,
+        &modules,
+        &source,
+        &context,
+    );
     let mut env = Env::new();
 
     let expected_value = Value::Int(1);
-    let actual_value = interpreter.eval(&mut env, &term);
+    let actual_value = interpreter.eval(&mut env, &term).unwrap();
     assert_eq!(expected_value, actual_value);
 
     let actual_stdout = String::from_utf8(stdout).unwrap();
@@ -43,11 +56,319 @@ fn eval_2() {
     let common_kinds = CommonKinds::default();
     let context = HashMap::new();
     let modules = Modules::new();
-    let mut interpreter =
-        Interpreter::new(&mut stdin, &mut stdout, &common_kinds, &modules, &context);
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+    let mut interpreter = Interpreter::new(
+        &mut stdin,
+        &mut stdout,
+        &common_kinds,
+        &modules,
+        &source,
+        &context,
+    );
     let mut env = Env::new();
 
     let expected_value = interpreter.alloc(Object::Bytes(Rc::from(str.as_bytes())));
-    let actual_value = interpreter.eval(&mut env, &term);
+    let actual_value = interpreter.eval(&mut env, &term).unwrap();
     assert_eq!(expected_value, actual_value);
 }
+
+#[test]
+fn eval_index_out_of_bounds() {
+    let mut stdin = std::io::empty();
+    let mut stdout = Vec::new();
+    let term = Expr::Located {
+        pos: 7,
+        expr: Rc::new(Expr::App(
+            Rc::new(Expr::App(
+                Rc::new(Expr::Builtin(Builtin::IndexArray)),
+                Rc::new(Expr::Int(2)),
+            )),
+            Rc::new(Expr::Array(vec![Expr::Int(0), Expr::Int(1)])),
+        )),
+    };
+    let common_kinds = CommonKinds::default();
+    let context = HashMap::new();
+    let modules = Modules::new();
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+    let mut interpreter = Interpreter::new(
+        &mut stdin,
+        &mut stdout,
+        &common_kinds,
+        &modules,
+        &source,
+        &context,
+    );
+    let mut env = Env::new();
+
+    let error = interpreter.eval(&mut env, &term).unwrap_err();
+    assert!(
+        matches!(
+            error.info,
+            RuntimeErrorInfo::IndexOutOfBounds {
+                index: 2,
+                length: 2
+            }
+        ),
+        "{:?}",
+        error
+    );
+    assert_eq!(
+        error.location,
+        Some(Location {
+            source,
+            offset: Some(7)
+        })
+    );
+}
+
+#[test]
+fn eval_integer_overflow() {
+    let mut stdin = std::io::empty();
+    let mut stdout = Vec::new();
+    let term = Expr::Binop(
+        Binop::Add,
+        Rc::new(Expr::Int(i64::MAX)),
+        Rc::new(Expr::Int(1)),
+    );
+    let common_kinds = CommonKinds::default();
+    let context = HashMap::new();
+    let modules = Modules::new();
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+    let mut interpreter = Interpreter::new(
+        &mut stdin,
+        &mut stdout,
+        &common_kinds,
+        &modules,
+        &source,
+        &context,
+    );
+    let mut env = Env::new();
+
+    let error = interpreter.eval(&mut env, &term).unwrap_err();
+    assert!(
+        matches!(error.info, RuntimeErrorInfo::IntegerOverflow),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn eval_nested_pattern() {
+    let mut stdin = std::io::empty();
+    let mut stdout = Vec::new();
+    /*
+    case Some (Ok 5) of
+      Some (Err _) -> 0
+      Some (Ok x) -> x
+    */
+    let term = Expr::Case(
+        Rc::new(Expr::App(
+            Rc::new(Expr::Variant(Rc::new(Expr::Int(0)))),
+            Rc::new(Expr::App(
+                Rc::new(Expr::Variant(Rc::new(Expr::Int(1)))),
+                Rc::new(Expr::Int(5)),
+            )),
+        )),
+        vec![
+            Branch {
+                pattern: Pattern::Variant {
+                    tag: Rc::new(Expr::Int(0)),
+                    arg: Rc::new(Pattern::Variant {
+                        tag: Rc::new(Expr::Int(0)),
+                        arg: Rc::new(Pattern::Wildcard),
+                    }),
+                },
+                body: Expr::Int(0),
+            },
+            Branch {
+                pattern: Pattern::Variant {
+                    tag: Rc::new(Expr::Int(0)),
+                    arg: Rc::new(Pattern::Variant {
+                        tag: Rc::new(Expr::Int(1)),
+                        arg: Rc::new(Pattern::Name),
+                    }),
+                },
+                body: Expr::Var(0),
+            },
+        ],
+    );
+    let common_kinds = CommonKinds::default();
+    let context = HashMap::new();
+    let modules = Modules::new();
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+    let mut interpreter = Interpreter::new(
+        &mut stdin,
+        &mut stdout,
+        &common_kinds,
+        &modules,
+        &source,
+        &context,
+    );
+    let mut env = Env::new();
+
+    let expected_value = Value::Int(5);
+    let actual_value = interpreter.eval(&mut env, &term).unwrap();
+    assert_eq!(expected_value, actual_value);
+    assert_eq!(0, env.len());
+}
+
+#[test]
+fn eval_int_div_overflow() {
+    let mut stdin = std::io::empty();
+    let mut stdout = Vec::new();
+    let term = Expr::App(
+        Rc::new(Expr::App(
+            Rc::new(Expr::Builtin(Builtin::IntDiv)),
+            Rc::new(Expr::Int(i64::MIN)),
+        )),
+        Rc::new(Expr::Int(-1)),
+    );
+    let common_kinds = CommonKinds::default();
+    let context = HashMap::new();
+    let modules = Modules::new();
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+    let mut interpreter = Interpreter::new(
+        &mut stdin,
+        &mut stdout,
+        &common_kinds,
+        &modules,
+        &source,
+        &context,
+    );
+    let mut env = Env::new();
+
+    let error = interpreter.eval(&mut env, &term).unwrap_err();
+    assert!(
+        matches!(error.info, RuntimeErrorInfo::IntegerOverflow),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn int_div_mod_negative() {
+    let cases = [(7, 2), (-7, 2), (7, -2), (-7, -2), (6, -3), (i64::MIN, -1)];
+    assert_eq!(
+        vec![Some(3), Some(-4), Some(-4), Some(3), Some(-2), None],
+        cases
+            .iter()
+            .map(|&(a, b)| checked_div_floor(a, b))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![1, 1, -1, -1, 0, 0],
+        cases
+            .iter()
+            .map(|&(a, b)| mod_floor(a, b))
+            .collect::<Vec<_>>()
+    );
+    // `div a b * b + mod a b == a` whenever the division doesn't overflow.
+    for &(a, b) in &cases[..5] {
+        assert_eq!(a, checked_div_floor(a, b).unwrap() * b + mod_floor(a, b));
+    }
+}
+
+#[test]
+fn base64_round_trip() {
+    for bytes in [&b""[..], b"f", b"fo", b"foo", b"foob", b"\xff\x00\xfe"] {
+        let encoded = encoding::to_base64(bytes);
+        assert_eq!(Some(Vec::from(bytes)), encoding::from_base64(&encoded));
+    }
+    assert_eq!(None, encoding::from_base64("Zg="));
+    assert_eq!(None, encoding::from_base64("Zg==Zg=="));
+}
+
+#[test]
+fn merge_sort_by_is_stable() {
+    let items: Vec<(i64, char)> = vec![(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd'), (1, 'e')];
+    let sorted = merge_sort_by(&items, &mut |a, b| Ok::<_, ()>(a.0.cmp(&b.0)));
+    assert_eq!(
+        Ok(vec![(0, 'd'), (1, 'b'), (1, 'e'), (2, 'a'), (2, 'c')]),
+        sorted
+    );
+}
+
+#[test]
+fn json_escapes() {
+    let input = r#""caf\u00e9 \ud83d\ude00 \"\\\/\n""#;
+    assert_eq!(
+        Ok(json::Json::String(String::from(
+            "caf\u{e9} \u{1f600} \"\\/\n"
+        ))),
+        json::parse(input)
+    );
+    assert_eq!(
+        r#""\"\\\n\u0001""#,
+        json::render(&json::Json::String(String::from("\"\\\n\u{1}")))
+    );
+    assert_eq!(
+        Err(String::from("invalid unicode escape at line 1, column 2")),
+        json::parse(r#""\ud83d""#).map_err(|err| err.to_string())
+    );
+}
+
+#[test]
+fn json_numbers() {
+    assert_eq!(Ok(json::Json::Int(-12)), json::parse("-12"));
+    assert_eq!(Ok(json::Json::Float(1500.0)), json::parse("1.5e3"));
+    // Integers that don't fit in an Int are parsed as floats.
+    assert_eq!(
+        Ok(json::Json::Float(1e19)),
+        json::parse("10000000000000000000")
+    );
+    assert_eq!(
+        Err(String::from("number out of range at line 2, column 1")),
+        json::parse("\n1e999").map_err(|err| err.to_string())
+    );
+    assert_eq!(
+        "[1,2.5,null]",
+        json::render(&json::parse("[1, 2.5, null]").unwrap())
+    );
+}
+
+#[test]
+fn path_normalize() {
+    use std::path::Path;
+    assert_eq!(
+        Path::new("src/lib/mod.rs"),
+        path::normalize(Path::new("./src//bin/../lib/./mod.rs"))
+    );
+    assert_eq!(
+        Path::new("../../b"),
+        path::normalize(Path::new("../a/../../b"))
+    );
+    assert_eq!(Path::new("/etc"), path::normalize(Path::new("/../etc")));
+    assert_eq!(Path::new("."), path::normalize(Path::new("a/..")));
+}
+
+#[test]
+fn path_relative_to() {
+    use std::path::Path;
+    assert_eq!(
+        Some(Path::new("../lib/mod.rs").to_path_buf()),
+        path::relative_to(Path::new("src/lib/mod.rs"), Path::new("src/bin"))
+    );
+    assert_eq!(
+        Some(Path::new("..").to_path_buf()),
+        path::relative_to(Path::new("/home"), Path::new("/home/user/"))
+    );
+    assert_eq!(
+        Some(Path::new(".").to_path_buf()),
+        path::relative_to(Path::new("a/b/.."), Path::new("a"))
+    );
+    assert_eq!(
+        None,
+        path::relative_to(Path::new("src"), Path::new("/home"))
+    );
+    assert_eq!(None, path::relative_to(Path::new("a"), Path::new("..")));
+}
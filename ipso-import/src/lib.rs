@@ -17,12 +17,23 @@ pub enum Error {
     ModuleNotFound {
         source: Source,
         pos: usize,
-        module_path: PathBuf,
+
+        /// The files that could have contained the module, in the order they were tried.
+        searched: Vec<PathBuf>,
     },
     NameNotFound {
         source: Source,
         pos: usize,
     },
+    NameNotExported {
+        source: Source,
+        pos: usize,
+    },
+    DuplicateModuleName {
+        source: Source,
+        pos: usize,
+        module: String,
+    },
     IO(io::Error),
     Parse(parse::Error),
     Desugar(desugar::Error),
@@ -35,7 +46,7 @@ impl Error {
             Error::ModuleNotFound {
                 source,
                 pos,
-                module_path,
+                searched,
             } => diagnostic.item(
                 Some(Location {
                     source: source.clone(),
@@ -43,7 +54,16 @@ impl Error {
                 }),
                 Message {
                     content: String::from("module not found"),
-                    addendum: Some(format!("file {} does not exist", module_path.display())),
+                    addendum: Some(match searched.as_slice() {
+                        [module_path] => format!("file {} does not exist", module_path.display()),
+                        _ => searched.iter().fold(
+                            String::from("none of these files exist:"),
+                            |mut addendum, module_path| {
+                                addendum.push_str(&format!("\n  {}", module_path.display()));
+                                addendum
+                            },
+                        ),
+                    }),
                 },
             ),
             Error::NameNotFound { source, pos } => diagnostic.item(
@@ -56,6 +76,33 @@ impl Error {
                     addendum: None,
                 },
             ),
+            Error::NameNotExported { source, pos } => diagnostic.item(
+                Some(Location {
+                    source: source.clone(),
+                    offset: Some(*pos),
+                }),
+                Message {
+                    content: String::from("not exported by module"),
+                    addendum: None,
+                },
+            ),
+            Error::DuplicateModuleName {
+                source,
+                pos,
+                module,
+            } => diagnostic.item(
+                Some(Location {
+                    source: source.clone(),
+                    offset: Some(*pos),
+                }),
+                Message {
+                    content: String::from("a module with this name is already imported"),
+                    addendum: Some(format!(
+                        "give it a different name with `import {} as <name>`",
+                        module
+                    )),
+                },
+            ),
             Error::IO(err) => panic!("ioerror: {}", err),
             Error::Parse(err) => err.report(diagnostic),
             Error::Desugar(err) => err.report(diagnostic),
@@ -293,7 +340,7 @@ pub fn rewrite_module_accessors_expr(
             }
         }
         syntax::Expr::Cmd(parts) => parts.iter_mut().for_each(|part| match part {
-            syntax::CmdPart::Literal(_) => {}
+            syntax::CmdPart::Literal(_) | syntax::CmdPart::Pipe | syntax::CmdPart::Redirect(_) => {}
             syntax::CmdPart::Expr(expr) => {
                 rewrite_module_accessors_expr(exclude, imported_items, expr)
             }
@@ -309,6 +356,7 @@ pub fn rewrite_module_accessors_expr(
         syntax::Expr::True
         | syntax::Expr::False
         | syntax::Expr::Int(_)
+        | syntax::Expr::Float(_)
         | syntax::Expr::Char(_)
         | syntax::Expr::Variant(_)
         | syntax::Expr::Unit => {}
@@ -321,23 +369,22 @@ pub fn resolve_from_import_all(
     imported_module_id: ModuleId,
     imported_module: &core::Module,
 ) {
+    /*
+    Only the definitions that the module exports are brought into scope. Evidence values
+    are internal to their respective modules, so they have no signatures.
+    */
     imported_items.extend(
         imported_module
-            .get_bindings(common_kinds)
+            .get_signatures(common_kinds)
             .into_keys()
-            .filter_map(|name| match name {
-                core::Name::Definition(name) => Some((
+            .map(|name| {
+                (
                     name,
                     ImportedItemInfo::DefinitionImportedFrom {
                         id: imported_module_id,
                         path: vec![],
                     },
-                )),
-                /*
-                Only definitions are brought into scope. Evidence values
-                are internal to their respective modules.
-                */
-                core::Name::Evidence(_) => None,
+                )
             }),
     );
 }
@@ -346,6 +393,7 @@ fn resolve_imports(
     common_kinds: &CommonKinds,
     modules: &mut Modules<core::Module>,
     builtins_module_id: ModuleId,
+    search_path: &[PathBuf],
     working_dir: &Path,
     path: &Path,
     module: &mut syntax::Module,
@@ -383,27 +431,55 @@ fn resolve_imports(
                 }));
 
                 let available_names: HashSet<String> = imported_module
-                    .get_bindings(common_kinds)
+                    .get_signatures(common_kinds)
                     .into_keys()
-                    .filter_map(|name| match name {
-                        core::Name::Evidence(_) => None,
-                        core::Name::Definition(name) => Some(name),
-                    })
+                    .chain(
+                        imported_module
+                            .decls
+                            .iter()
+                            .filter(|decl| imported_module.exports(decl))
+                            .flat_map(|decl| match decl {
+                                /*
+                                Importing a data type brings its constructors into scope, and
+                                each constructor can also be imported on its own.
+                                */
+                                core::Declaration::Data(data_decl) => {
+                                    std::iter::once(String::from(data_decl.name.as_ref()))
+                                        .chain(data_decl.constructors.iter().map(|constructor| {
+                                            String::from(constructor.name.as_ref())
+                                        }))
+                                        .collect()
+                                }
+                                core::Declaration::Class(class_decl) => {
+                                    vec![String::from(class_decl.name.as_ref())]
+                                }
+                                _ => Vec::new(),
+                            }),
+                    )
                     .collect();
 
-                names
-                    .iter()
-                    .try_for_each(|name| {
-                        if available_names.contains(&name.item) {
-                            Ok(())
-                        } else {
-                            Err(name)
-                        }
-                    })
-                    .map_err(|name| Error::NameNotFound {
-                        source,
-                        pos: name.pos,
-                    })
+                names.iter().try_for_each(|name| {
+                    if available_names.contains(&name.item) {
+                        Ok(())
+                    } else if imported_module
+                        .get_bindings(common_kinds)
+                        .contains_key(&core::Name::definition(name.item.as_str()))
+                        || imported_module
+                            .decls
+                            .iter()
+                            .any(|decl| decl.name() == Some(name.item.as_str()))
+                    {
+                        Err(Error::NameNotExported {
+                            source: source.clone(),
+                            pos: name.pos,
+                        })
+                    } else {
+                        Err(Error::NameNotFound {
+                            source: source.clone(),
+                            pos: name.pos,
+                        })
+                    }
+                })
             }
         }
     }
@@ -429,21 +505,42 @@ fn resolve_imports(
                     module,
                     as_name,
                 } => {
-                    let id = import(
+                    let id = import_by_name(
                         modules,
                         builtins_module_id,
+                        search_path,
                         &Source::File {
                             path: PathBuf::from(path),
                         },
                         module.pos,
-                        &working_dir.join(&module.item).with_extension("ipso"),
+                        working_dir,
+                        &module.item,
                         common_kinds,
                     )?;
 
-                    imported_items.insert(
-                        as_name.as_ref().unwrap_or(module).item.clone(),
-                        ImportedItemInfo::ModuleImportedAs { id },
-                    );
+                    // `import a.b` brings `b` into scope.
+                    let (name, pos) = match as_name {
+                        Some(as_name) => (as_name.item.clone(), as_name.pos),
+                        None => (
+                            String::from(module.item.rsplit('.').next().unwrap()),
+                            module.pos,
+                        ),
+                    };
+
+                    // A second module with the same name would replace the first.
+                    if let Some(ImportedItemInfo::ModuleImportedAs { .. }) =
+                        imported_items.get(&name)
+                    {
+                        return Err(Error::DuplicateModuleName {
+                            source: Source::File {
+                                path: PathBuf::from(path),
+                            },
+                            pos,
+                            module: module.item.clone(),
+                        });
+                    }
+
+                    imported_items.insert(name, ImportedItemInfo::ModuleImportedAs { id });
 
                     *resolved = Some(id);
 
@@ -458,12 +555,14 @@ fn resolve_imports(
                         path: PathBuf::from(path),
                     };
 
-                    let imported_module_id = import(
+                    let imported_module_id = import_by_name(
                         modules,
                         builtins_module_id,
+                        search_path,
                         &source,
                         module.pos,
-                        &working_dir.join(&module.item).with_extension("ipso"),
+                        working_dir,
+                        &module.item,
                         common_kinds,
                     )?;
 
@@ -520,15 +619,42 @@ fn resolve_imports(
                     Ok(())
                 }
 
-                syntax::Declaration::Class { name, members, .. } => {
+                syntax::Declaration::Class {
+                    name,
+                    members,
+                    defaults,
+                    ..
+                } => {
                     exclude.insert(name.clone());
                     exclude.insert_all(members.iter().map(|(name, _)| Rc::from(name.as_str())));
+                    for default in defaults {
+                        let to_exclude: Vec<Rc<str>> = default
+                            .args
+                            .iter()
+                            .flat_map(|pattern| {
+                                pattern.item.iter_names().map(|name| name.item.clone())
+                            })
+                            .collect();
+
+                        exclude.insert_all(to_exclude.iter().cloned());
+                        rewrite_module_accessors_expr(
+                            &mut exclude,
+                            &imported_items,
+                            &mut default.body,
+                        );
+                        exclude.remove_all(to_exclude.into_iter());
+                    }
                     Ok(())
                 }
                 syntax::Declaration::TypeAlias { name, .. } => {
                     exclude.insert(Rc::from(name.as_str()));
                     Ok(())
                 }
+                syntax::Declaration::Data { name, .. } => {
+                    exclude.insert(name.clone());
+                    Ok(())
+                }
+                syntax::Declaration::Export { .. } => Ok(()),
             }
         })?;
 
@@ -554,54 +680,154 @@ fn resolve_imports(
     Ok(())
 }
 
+/**
+Find the file that contains a module.
+
+Each `.`-separated component of a module name is a directory, except for the last, which
+names an `.ipso` file (e.g. `a.b` is `a/b.ipso`). The module is first looked for relative to
+`working_dir`, and then in each directory of `search_path`, in order.
+
+A module that's found in the search path is a library module, which is identified by its
+name and the file that it was found in. Its key can't be confused with the key of a module that
+isn't defined in a file, like the builtins.
+
+Returns the files that were tried when the module can't be found.
+*/
+pub fn find_module(
+    search_path: &[PathBuf],
+    working_dir: &Path,
+    name: &str,
+) -> Result<(ModuleKey, PathBuf), Vec<PathBuf>> {
+    let relative_path = name.split('.').collect::<PathBuf>().with_extension("ipso");
+
+    let local_path = working_dir.join(&relative_path);
+    if local_path.exists() {
+        return Ok((ModuleKey::from(local_path.as_path()), local_path));
+    }
+
+    let mut searched = vec![local_path];
+    for dir in search_path {
+        let library_path = dir.join(&relative_path);
+        if library_path.exists() {
+            return Ok((
+                ModuleKey::Library {
+                    name: String::from(name),
+                    path: library_path.clone(),
+                },
+                library_path,
+            ));
+        }
+        searched.push(library_path);
+    }
+
+    Err(searched)
+}
+
+/**
+Import a module by name (see [`find_module`]).
+
+* `source` - source file location for error reporting
+* `pos` - source file offset for error reporting
+* `working_dir` - the directory that contains the importing module
+* `name` - the module's name
+*/
+#[allow(clippy::too_many_arguments)]
+fn import_by_name(
+    modules: &mut Modules<core::Module>,
+    builtins_module_id: ModuleId,
+    search_path: &[PathBuf],
+    source: &Source,
+    pos: usize,
+    working_dir: &Path,
+    name: &str,
+    common_kinds: &CommonKinds,
+) -> Result<ModuleId, Error> {
+    match find_module(search_path, working_dir, name) {
+        Ok((key, path)) => import_file(
+            modules,
+            builtins_module_id,
+            search_path,
+            key,
+            &path,
+            common_kinds,
+        ),
+        Err(searched) => Err(Error::ModuleNotFound {
+            source: source.clone(),
+            pos,
+            searched,
+        }),
+    }
+}
+
 /// Import a module.
 ///
 /// Module imports are cached, so importing the same module repeatedly is cheap.
 ///
+/// * `search_path` - directories that contain library modules (see [`find_module`])
 /// * `source` - source file location for error reporting
 /// * `pos` - source file offset for error reporting
 /// * `path` - file path to import
 pub fn import(
     modules: &mut Modules<core::Module>,
     builtins_module_id: ModuleId,
+    search_path: &[PathBuf],
     source: &Source,
     pos: usize,
     path: &Path,
     common_kinds: &CommonKinds,
 ) -> Result<ModuleId, Error> {
-    match modules.lookup_id(&ModuleKey::from(path)) {
+    if path.exists() {
+        import_file(
+            modules,
+            builtins_module_id,
+            search_path,
+            ModuleKey::from(path),
+            path,
+            common_kinds,
+        )
+    } else {
+        Err(Error::ModuleNotFound {
+            source: source.clone(),
+            pos,
+            searched: vec![path.to_path_buf()],
+        })
+    }
+}
+
+/// Import an existing module file, identified by `key`.
+fn import_file(
+    modules: &mut Modules<core::Module>,
+    builtins_module_id: ModuleId,
+    search_path: &[PathBuf],
+    key: ModuleKey,
+    path: &Path,
+    common_kinds: &CommonKinds,
+) -> Result<ModuleId, Error> {
+    match modules.lookup_id(&key) {
         None => {
-            if path.exists() {
-                let target_source = Source::File {
-                    path: PathBuf::from(path),
-                };
+            let target_source = Source::File {
+                path: PathBuf::from(path),
+            };
 
-                let module = parse::parse_file(path)?;
-                let mut module = desugar::desugar_module(&target_source, module)?;
+            let module = parse::parse_file(path)?;
+            let mut module = desugar::desugar_module(&target_source, module)?;
 
-                let working_dir = path.parent().unwrap();
+            let working_dir = path.parent().unwrap();
 
-                resolve_imports(
-                    common_kinds,
-                    modules,
-                    builtins_module_id,
-                    working_dir,
-                    path,
-                    &mut module,
-                )?;
-
-                let module =
-                    typecheck::module::check(common_kinds, modules, &target_source, &module)?;
-                let module_id: ModuleId = modules.insert(ModuleKey::from(path), module);
-
-                Ok(module_id)
-            } else {
-                Err(Error::ModuleNotFound {
-                    source: source.clone(),
-                    pos,
-                    module_path: path.to_path_buf(),
-                })
-            }
+            resolve_imports(
+                common_kinds,
+                modules,
+                builtins_module_id,
+                search_path,
+                working_dir,
+                path,
+                &mut module,
+            )?;
+
+            let module = typecheck::module::check(common_kinds, modules, &target_source, &module)?;
+            let module_id: ModuleId = modules.insert(key, module);
+
+            Ok(module_id)
         }
         Some(module_id) => Ok(module_id),
     }
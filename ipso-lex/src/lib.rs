@@ -3,6 +3,7 @@ mod test;
 
 pub mod token;
 
+use ipso_syntax::Float;
 use std::{fmt::Write, rc::Rc, str::Chars};
 use token::{Sign, Token};
 
@@ -56,16 +57,58 @@ impl<'input> Lexer<'input> {
         self.column = 0;
     }
 
-    fn consume_int(&mut self, sign: Sign, c: char, pos: usize, column: usize) -> Option<Token> {
+    /// Lex an integer or float literal whose first digit is `c`.
+    fn consume_number(&mut self, sign: Sign, c: char, pos: usize, column: usize) -> Option<Token> {
+        // The input that follows `c`, which is used to recover the text of float literals.
+        let rest = self.input.as_str();
+
         self.consume();
         let mut length = 1;
-        let mut value: usize = c.to_digit(10).unwrap() as usize;
+        /*
+        Literals that don't fit in a `u64` saturate, and are rejected by the parser
+        when they're converted to an `Int`.
+        */
+        let mut value: u64 = c.to_digit(10).unwrap() as u64;
         while let Some(n) = self.current.and_then(|cur| cur.to_digit(10)) {
             self.consume();
-            value *= 10;
-            value += n as usize;
+            value = value.saturating_mul(10).saturating_add(n as u64);
+            length += 1;
+        }
+
+        /*
+        A `.` is only part of the literal when it's followed by a digit, so that things like
+        `1..` still lex as an integer followed by `..`.
+        */
+        if self.current == Some('.')
+            && self
+                .input
+                .clone()
+                .next()
+                .map_or(false, |next| next.is_ascii_digit())
+        {
+            self.consume();
             length += 1;
+            while self.current.map_or(false, |cur| cur.is_ascii_digit()) {
+                self.consume();
+                length += 1;
+            }
+
+            let mut text = String::with_capacity(length + 1);
+            if sign == Sign::Negative {
+                text.push('-');
+            }
+            text.push(c);
+            text.push_str(&rest[0..length - 1]);
+            return Some(Token {
+                data: token::Data::Float {
+                    value: Float(text.parse().unwrap()),
+                    length: text.len(),
+                },
+                pos,
+                column,
+            });
         }
+
         Some(Token {
             data: token::Data::Int {
                 sign,
@@ -185,7 +228,7 @@ impl<'input> Iterator for Lexer<'input> {
                                                 })
                                             }
                                             Some(c) => match c {
-                                                '$' | '"' => {
+                                                '$' | '"' | '\\' => {
                                                     textual_len += 1;
                                                     self.consume();
                                                     str.push(c);
@@ -541,7 +584,7 @@ impl<'input> Iterator for Lexer<'input> {
                                 })
                             }
                             Some(c) if c.is_ascii_digit() => {
-                                self.consume_int(Sign::Negative, c, pos, column)
+                                self.consume_number(Sign::Negative, c, pos, column)
                             }
                             _ => Some(Token {
                                 data: token::Data::Hyphen,
@@ -625,7 +668,7 @@ impl<'input> Iterator for Lexer<'input> {
                             column,
                         })
                     }
-                    _ if c.is_ascii_digit() => self.consume_int(Sign::None, c, pos, column),
+                    _ if c.is_ascii_digit() => self.consume_number(Sign::None, c, pos, column),
                     _ => {
                         self.consume();
                         Some(Token {
@@ -678,13 +721,45 @@ impl<'input> Iterator for Lexer<'input> {
                                 column,
                             })
                         }
+                        '|' => {
+                            self.consume();
+                            Some(Token {
+                                data: token::Data::Pipe,
+                                pos,
+                                column,
+                            })
+                        }
+                        '<' => {
+                            self.consume();
+                            Some(Token {
+                                data: token::Data::LAngle,
+                                pos,
+                                column,
+                            })
+                        }
+                        '>' => {
+                            self.consume();
+                            Some(Token {
+                                data: token::Data::RAngle,
+                                pos,
+                                column,
+                            })
+                        }
+                        '&' => {
+                            self.consume();
+                            Some(Token {
+                                data: token::Data::Ampersand,
+                                pos,
+                                column,
+                            })
+                        }
                         _ => {
                             let mut textual_length = 0;
                             let mut value = String::new();
 
                             while let Some(c) = self.current {
                                 match c {
-                                    '`' | ' ' | '"' | '$' => {
+                                    '`' | ' ' | '"' | '$' | '|' | '<' | '>' | '&' => {
                                         break;
                                     }
                                     '\\' => {
@@ -702,9 +777,9 @@ impl<'input> Iterator for Lexer<'input> {
                                                 value.write_char('`').unwrap();
                                                 textual_length += 1;
                                             }
-                                            Some('\\') => {
+                                            Some(c @ ('\\' | '|' | '<' | '>' | '&')) => {
                                                 self.consume();
-                                                value.write_char('\\').unwrap();
+                                                value.write_char(c).unwrap();
                                                 textual_length += 1;
                                             }
                                             Some(c) => {
@@ -732,6 +807,26 @@ impl<'input> Iterator for Lexer<'input> {
                             }
 
                             if textual_length > 0 {
+                                /*
+                                A file descriptor number that is immediately followed by `>`
+                                belongs to a redirection (e.g. `2> file`, `2>&1`).
+                                */
+                                if self.current == Some('>')
+                                    && value.chars().all(|c| c.is_ascii_digit())
+                                {
+                                    if let Ok(fd) = value.parse::<u64>() {
+                                        return Some(Token {
+                                            data: token::Data::Int {
+                                                sign: Sign::None,
+                                                value: fd,
+                                                length: textual_length,
+                                            },
+                                            pos,
+                                            column,
+                                        });
+                                    }
+                                }
+
                                 Some(Token {
                                     data: token::Data::Cmd(Rc::from(value)),
                                     pos,
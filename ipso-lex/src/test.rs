@@ -1,5 +1,6 @@
 use super::Lexer;
 use crate::token::{self, Sign, Token};
+use ipso_syntax::Float;
 use std::rc::Rc;
 
 #[test]
@@ -143,6 +144,99 @@ fn lex_int_2() {
     )
 }
 
+#[test]
+fn lex_int_3() {
+    assert_eq!(
+        {
+            let input = Rc::from("99999999999999999999999");
+            let lexer = Lexer::new(&input);
+            lexer.collect::<Vec<Token>>()
+        },
+        vec![
+            Token {
+                data: token::Data::Int {
+                    sign: Sign::None,
+                    value: u64::MAX,
+                    length: 23
+                },
+                pos: 0,
+                column: 0
+            },
+            Token {
+                data: token::Data::Eof,
+                pos: 23,
+                column: 23
+            }
+        ]
+    )
+}
+
+#[test]
+fn lex_float_1() {
+    assert_eq!(
+        {
+            let input = Rc::from("1.5 -0.25");
+            let lexer = Lexer::new(&input);
+            lexer.collect::<Vec<Token>>()
+        },
+        vec![
+            Token {
+                data: token::Data::Float {
+                    value: Float(1.5),
+                    length: 3
+                },
+                pos: 0,
+                column: 0
+            },
+            Token {
+                data: token::Data::Float {
+                    value: Float(-0.25),
+                    length: 5
+                },
+                pos: 4,
+                column: 4
+            },
+            Token {
+                data: token::Data::Eof,
+                pos: 9,
+                column: 9
+            }
+        ]
+    )
+}
+
+#[test]
+fn lex_float_2() {
+    assert_eq!(
+        {
+            let input = Rc::from("1..");
+            let lexer = Lexer::new(&input);
+            lexer.collect::<Vec<Token>>()
+        },
+        vec![
+            Token {
+                data: token::Data::Int {
+                    sign: Sign::None,
+                    value: 1,
+                    length: 1
+                },
+                pos: 0,
+                column: 0
+            },
+            Token {
+                data: token::Data::DotDot,
+                pos: 1,
+                column: 1
+            },
+            Token {
+                data: token::Data::Eof,
+                pos: 3,
+                column: 3
+            }
+        ]
+    )
+}
+
 #[test]
 fn lex_import() {
     assert_eq!(
@@ -700,6 +794,39 @@ fn lex_string_7() {
     assert_eq!(expected, actual)
 }
 
+#[test]
+fn lex_string_8() {
+    let input = Rc::from("\"\\\\d+\"");
+    let lexer = Lexer::new(&input);
+    let expected = vec![
+        Token {
+            data: token::Data::DoubleQuote,
+            pos: 0,
+            column: 0,
+        },
+        Token {
+            data: token::Data::String {
+                value: String::from("\\d+"),
+                length: 4,
+            },
+            pos: 1,
+            column: 1,
+        },
+        Token {
+            data: token::Data::DoubleQuote,
+            pos: 5,
+            column: 5,
+        },
+        Token {
+            data: token::Data::Eof,
+            pos: 6,
+            column: 6,
+        },
+    ];
+    let actual = lexer.collect::<Vec<Token>>();
+    assert_eq!(expected, actual)
+}
+
 #[test]
 fn lex_cmd_1() {
     let input = Rc::from("`  ls  -laR   `");
@@ -822,3 +949,87 @@ fn lex_cmd_3() {
     let actual = lexer.collect::<Vec<Token>>();
     assert_eq!(expected, actual)
 }
+
+#[test]
+fn lex_cmd_4() {
+    let input = Rc::from("`a|b 2>&1 >>c \\|`");
+    let lexer = Lexer::new(&input);
+    let expected = vec![
+        Token {
+            data: token::Data::Backtick,
+            pos: 0,
+            column: 0,
+        },
+        Token {
+            data: token::Data::Cmd(Rc::from("a")),
+            pos: 1,
+            column: 1,
+        },
+        Token {
+            data: token::Data::Pipe,
+            pos: 2,
+            column: 2,
+        },
+        Token {
+            data: token::Data::Cmd(Rc::from("b")),
+            pos: 3,
+            column: 3,
+        },
+        Token {
+            data: token::Data::Int {
+                sign: Sign::None,
+                value: 2,
+                length: 1,
+            },
+            pos: 5,
+            column: 5,
+        },
+        Token {
+            data: token::Data::RAngle,
+            pos: 6,
+            column: 6,
+        },
+        Token {
+            data: token::Data::Ampersand,
+            pos: 7,
+            column: 7,
+        },
+        Token {
+            data: token::Data::Cmd(Rc::from("1")),
+            pos: 8,
+            column: 8,
+        },
+        Token {
+            data: token::Data::RAngle,
+            pos: 10,
+            column: 10,
+        },
+        Token {
+            data: token::Data::RAngle,
+            pos: 11,
+            column: 11,
+        },
+        Token {
+            data: token::Data::Cmd(Rc::from("c")),
+            pos: 12,
+            column: 12,
+        },
+        Token {
+            data: token::Data::Cmd(Rc::from("|")),
+            pos: 14,
+            column: 14,
+        },
+        Token {
+            data: token::Data::Backtick,
+            pos: 16,
+            column: 16,
+        },
+        Token {
+            data: token::Data::Eof,
+            pos: 17,
+            column: 17,
+        },
+    ];
+    let actual = lexer.collect::<Vec<Token>>();
+    assert_eq!(expected, actual)
+}
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod test;
 
-use ipso_syntax::Keyword;
+use ipso_syntax::{Float, Keyword};
 use quickcheck::Arbitrary;
 use std::rc::Rc;
 
@@ -29,6 +29,7 @@ pub enum Name {
     Ident,
     Keyword(Keyword),
     Int,
+    Float,
     DoubleQuote,
     Dollar,
     DollarLBrace,
@@ -78,6 +79,7 @@ impl Arbitrary for Name {
             Name::Ident,
             Name::Keyword(Keyword::arbitrary(g)),
             Name::Int,
+            Name::Float,
             Name::DoubleQuote,
             Name::Dollar,
             Name::DollarLBrace,
@@ -122,7 +124,7 @@ impl Arbitrary for Name {
 
 impl Name {
     pub fn num_variants() -> usize {
-        44 + Keyword::num_variants()
+        45 + Keyword::num_variants()
     }
 
     pub fn from_int(ix: usize) -> Option<Self> {
@@ -188,6 +190,8 @@ impl Name {
             58 => Some(Self::Ampersand),
             59 => Some(Self::LParenPipe),
             60 => Some(Self::PipeRParen),
+            61 => Some(Self::Float),
+            62 => Some(Self::Keyword(Keyword::Export)),
             _ => None,
         }
     }
@@ -255,6 +259,8 @@ impl Name {
             Self::Ampersand => 58,
             Self::LParenPipe => 59,
             Self::PipeRParen => 60,
+            Self::Float => 61,
+            Self::Keyword(Keyword::Export) => 62,
         }
     }
 
@@ -264,6 +270,7 @@ impl Name {
             Name::Ident => String::from("identifier"),
             Name::Keyword(keyword) => String::from(keyword.to_string()),
             Name::Int => String::from("integer"),
+            Name::Float => String::from("float"),
             Name::Comment => String::from("comment"),
             Name::DoubleQuote => String::from("'\"'"),
             Name::Dollar => String::from("'$'"),
@@ -337,7 +344,11 @@ pub enum Data {
     Ident(Rc<str>),
     Int {
         sign: Sign,
-        value: usize,
+        value: u64,
+        length: usize,
+    },
+    Float {
+        value: Float,
         length: usize,
     },
 
@@ -412,6 +423,7 @@ impl Data {
                 Sign::Negative => *length + 1,
                 Sign::None => *length,
             },
+            Data::Float { value: _, length } => *length,
             Data::LBrace => 1,
             Data::RBrace => 1,
             Data::LParen => 1,
@@ -460,6 +472,7 @@ impl Data {
             Data::Ctor => Name::Ctor,
             Data::Ident(_) => Name::Ident,
             Data::Int { .. } => Name::Int,
+            Data::Float { .. } => Name::Float,
             Data::DoubleQuote => Name::DoubleQuote,
             Data::Dollar => Name::Dollar,
             Data::DollarLBrace => Name::DollarLBrace,
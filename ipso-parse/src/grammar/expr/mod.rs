@@ -4,11 +4,14 @@
 mod test;
 
 use crate::{
-    between, choices, grammar::pattern::pattern, indent, indent_scope, keep_left, keep_right, many,
-    map0, map2, operator::operator, optional, sep_by, spanned, Parsed, Parser,
+    between, choices,
+    grammar::pattern::{pattern, pattern_atom},
+    indent, indent_scope, keep_left, keep_right, many, map0, map2,
+    operator::operator,
+    optional, sep_by, spanned, Parsed, Parser,
 };
-use ipso_lex::token;
-use ipso_syntax::{Binop, Branch, CmdPart, CompLine, Expr, Keyword, Spanned, StringPart};
+use ipso_lex::token::{self, Sign};
+use ipso_syntax::{Binop, Branch, CmdPart, CompLine, Expr, Keyword, Redirect, Spanned, StringPart};
 use std::rc::Rc;
 
 /**
@@ -262,11 +265,15 @@ cmd_part ::=
 
 ```text
 cmd_char ::=
-  (ASCII characters excluding '\', '`', '"', '$')
+  (ASCII characters excluding '\', '`', '"', '$', '|', '<', '>', '&')
   '\' '\'
   '\' '`'
   '\' '"'
   '\' '$'
+  '\' '|'
+  '\' '<'
+  '\' '>'
+  '\' '&'
 ```
 */
 pub fn cmd_part(parser: &mut Parser) -> Parsed<CmdPart> {
@@ -301,17 +308,117 @@ pub fn cmd_part(parser: &mut Parser) -> Parsed<CmdPart> {
     )
 }
 
+/**
+A file descriptor that prefixes a redirection.
+
+```text
+cmd_fd ::=
+  '1'
+  '2'
+```
+
+Any other number is reported as an unsupported file descriptor.
+*/
+fn cmd_fd(parser: &mut Parser) -> Parsed<usize> {
+    parser.expecting.insert(token::Name::Int);
+    match &parser.current {
+        None => Parsed::unexpected(false),
+        Some(token) => match token.data {
+            token::Data::Int {
+                sign: Sign::None,
+                value,
+                length: _,
+            } => {
+                if value == 1 || value == 2 {
+                    map0!(value as usize, parser.consume())
+                } else {
+                    Parsed::unsupported_file_descriptor(token.pos, value)
+                }
+            }
+            _ => Parsed::unexpected(false),
+        },
+    }
+}
+
+/**
+The file descriptor that follows `>&`.
+*/
+fn cmd_fd_ref(parser: &mut Parser, expected: &str) -> Parsed<()> {
+    parser.expecting.insert(token::Name::Cmd);
+    match &parser.current {
+        None => Parsed::unexpected(false),
+        Some(token) => match &token.data {
+            token::Data::Cmd(value) if value.as_ref() == expected => parser.consume(),
+            _ => Parsed::unexpected(false),
+        },
+    }
+}
+
+/**
+```text
+cmd_redirect ::=
+  '<' cmd_part
+  cmd_fd? '>' cmd_part
+  cmd_fd? '>' '>' cmd_part
+  '1'? '>' '&' '2'
+  '2' '>' '&' '1'
+```
+*/
+pub fn cmd_redirect(parser: &mut Parser) -> Parsed<Vec<CmdPart>> {
+    choices!(
+        keep_right!(parser.token(&token::Data::LAngle), cmd_part(parser))
+            .map(|target| vec![CmdPart::Redirect(Redirect::Stdin), target]),
+        keep_left!(
+            optional!(cmd_fd(parser)),
+            parser.token(&token::Data::RAngle)
+        )
+        .and_then(|fd| {
+            let (truncate, append, duplicate, other_fd) = match fd {
+                Some(2) => (
+                    Redirect::Stderr,
+                    Redirect::StderrAppend,
+                    Redirect::StderrToStdout,
+                    "1",
+                ),
+                _ => (
+                    Redirect::Stdout,
+                    Redirect::StdoutAppend,
+                    Redirect::StdoutToStderr,
+                    "2",
+                ),
+            };
+            choices!(
+                keep_right!(parser.token(&token::Data::RAngle), cmd_part(parser))
+                    .map(|target| vec![CmdPart::Redirect(append), target]),
+                keep_right!(
+                    parser.token(&token::Data::Ampersand),
+                    cmd_fd_ref(parser, other_fd)
+                )
+                .map(|()| vec![CmdPart::Redirect(duplicate)]),
+                cmd_part(parser).map(|target| vec![CmdPart::Redirect(truncate), target])
+            )
+        })
+    )
+}
+
 /**
 ```text
 expr_cmd ::=
-  '`' cmd_part* '`'
+  '`' (cmd_part | '|' | cmd_redirect)* '`'
 ```
 */
 pub fn expr_cmd(parser: &mut Parser) -> Parsed<Vec<CmdPart>> {
     between!(
         parser.token(&token::Data::Backtick),
         parser.token(&token::Data::Backtick),
-        many!(cmd_part(parser))
+        many!(choices!(
+            cmd_part(parser).map(|part| vec![part]),
+            parser
+                .token(&token::Data::Pipe)
+                .map(|()| vec![CmdPart::Pipe]),
+            cmd_redirect(parser)
+        ))
+        .map(|parts| parts.into_iter().flatten().collect())
     )
 }
 
@@ -319,6 +426,7 @@ pub fn expr_cmd(parser: &mut Parser) -> Parsed<Vec<CmdPart>> {
 ```text
 expr_atom ::=
   int
+  float
   char
   'false'
   'true'
@@ -337,6 +445,7 @@ pub fn expr_atom(parser: &mut Parser) -> Parsed<Spanned<Expr>> {
         parser,
         choices!(
             parser.int().map(Expr::Int),
+            parser.float().map(Expr::Float),
             parser.char().map(Expr::Char),
             parser.keyword(&Keyword::False).map(|_| Expr::False),
             parser.keyword(&Keyword::True).map(|_| Expr::True),
@@ -366,12 +475,23 @@ pub fn expr_atom(parser: &mut Parser) -> Parsed<Spanned<Expr>> {
 expr_project ::=
   expr_atom ('.' ident)*
 ```
+
+Constructors are only brought into scope by `from ... import`, so a constructor after a `.`
+(e.g. `lib.Some`) is reported as an error.
 */
 pub fn expr_project(parser: &mut Parser) -> Parsed<Spanned<Expr>> {
     expr_atom(parser).and_then(|val| {
         many!(keep_right!(
             indent!(parser, Relation::Gt, parser.token(&token::Data::Dot)),
-            indent!(parser, Relation::Gt, spanned!(parser, parser.ident_owned()))
+            indent!(
+                parser,
+                Relation::Gt,
+                choices!(
+                    spanned!(parser, parser.ident_owned()),
+                    spanned!(parser, parser.ctor())
+                        .and_then(|ctor| Parsed::qualified_constructor(ctor.pos))
+                )
+            )
         ))
         .map(|fields| {
             let mut expr = val;
@@ -555,7 +675,7 @@ pub fn expr_op(parser: &mut Parser) -> Parsed<Spanned<Expr>> {
 /**
 ```text
 expr_lam ::=
-  '\' pattern '->' expr
+  '\' pattern_atom* '->' expr
 ```
 */
 pub fn expr_lam(parser: &mut Parser) -> Parsed<Spanned<Expr>> {
@@ -566,7 +686,7 @@ pub fn expr_lam(parser: &mut Parser) -> Parsed<Spanned<Expr>> {
             many!(indent!(
                 parser,
                 Relation::Gt,
-                spanned!(parser, pattern(parser))
+                spanned!(parser, pattern_atom(parser))
             ))
             .and_then(|args| keep_right!(
                 indent!(parser, Relation::Gt, parser.token(&token::Data::Arrow)),
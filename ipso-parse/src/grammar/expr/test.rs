@@ -1,4 +1,4 @@
-use super::{expr_app, expr_case, string};
+use super::{expr_app, expr_case, expr_cmd, string};
 use crate::Error;
 use crate::{keep_left, map2, Parser};
 use ipso_diagnostic::Source;
@@ -6,7 +6,7 @@ use ipso_lex::{
     token::{self, Relation},
     Lexer,
 };
-use ipso_syntax::{Branch, Expr, Keyword, Pattern, Spanned, StringPart};
+use ipso_syntax::{Branch, CmdPart, Expr, Float, Keyword, Pattern, Redirect, Spanned, StringPart};
 use std::rc::Rc;
 
 macro_rules! parse_test {
@@ -225,6 +225,7 @@ fn parse_case_4() {
                 token::Name::DoubleQuote,
                 token::Name::SingleQuote,
                 token::Name::Int,
+                token::Name::Float,
                 token::Name::Indent(Relation::Eq, 2),
                 token::Name::Comment,
                 token::Name::Backtick,
@@ -319,3 +320,143 @@ fn parse_string_3() {
         ])
     )
 }
+
+#[test]
+fn parse_cmd_pipeline_1() {
+    parse_test!(
+        "`cat < $input | sort 2>&1 >> out`",
+        expr_cmd,
+        Ok(vec![
+            CmdPart::Literal(Rc::from("cat")),
+            CmdPart::Redirect(Redirect::Stdin),
+            CmdPart::Expr(Spanned {
+                pos: 8,
+                item: Expr::Var(String::from("input"))
+            }),
+            CmdPart::Pipe,
+            CmdPart::Literal(Rc::from("sort")),
+            CmdPart::Redirect(Redirect::StderrToStdout),
+            CmdPart::Redirect(Redirect::StdoutAppend),
+            CmdPart::Literal(Rc::from("out")),
+        ])
+    )
+}
+
+#[test]
+fn parse_cmd_redirect_invalid_fd() {
+    parse_test!(
+        "`a 2>&2`",
+        expr_cmd,
+        Err(Error::Unexpected {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 6,
+            expecting: vec![token::Name::Comment, token::Name::Cmd]
+                .into_iter()
+                .collect(),
+        })
+    )
+}
+
+#[test]
+fn parse_cmd_redirect_unsupported_fd() {
+    parse_test!(
+        "`echo 3>x`",
+        expr_cmd,
+        Err(Error::UnsupportedFileDescriptor {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 6,
+            fd: 3
+        })
+    )
+}
+
+#[test]
+fn parse_int_1() {
+    parse_test!(
+        "[9223372036854775807, -9223372036854775808]",
+        expr_app,
+        Ok(Spanned {
+            pos: 0,
+            item: Expr::Array(vec![
+                Spanned {
+                    pos: 1,
+                    item: Expr::Int(i64::MAX)
+                },
+                Spanned {
+                    pos: 22,
+                    item: Expr::Int(i64::MIN)
+                }
+            ])
+        })
+    )
+}
+
+#[test]
+fn parse_int_2() {
+    parse_test!(
+        "f 9223372036854775808",
+        expr_app,
+        Err(Error::IntegerOutOfRange {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 2
+        })
+    )
+}
+
+#[test]
+fn parse_int_3() {
+    parse_test!(
+        "-99999999999999999999999",
+        expr_app,
+        Err(Error::IntegerOutOfRange {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 0
+        })
+    )
+}
+
+#[test]
+fn parse_qualified_constructor_1() {
+    parse_test!(
+        "f (lib.Some 1)",
+        expr_app,
+        Err(Error::QualifiedConstructor {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 7
+        })
+    )
+}
+
+#[test]
+fn parse_float_1() {
+    parse_test!(
+        "f 1.5 -2.0",
+        expr_app,
+        Ok(Expr::mk_app(
+            Expr::mk_app(
+                Spanned {
+                    pos: 0,
+                    item: Expr::mk_var("f")
+                },
+                Spanned {
+                    pos: 2,
+                    item: Expr::Float(Float(1.5))
+                }
+            ),
+            Spanned {
+                pos: 6,
+                item: Expr::Float(Float(-2.0))
+            }
+        ))
+    )
+}
@@ -7,19 +7,21 @@ use crate::{
     between, choices,
     grammar::{
         expr::expr,
-        pattern::pattern,
+        pattern::pattern_atom,
         r#type::{type_, type_atom},
     },
     indent, indent_scope, keep_left, keep_right, many, many_, map0, optional, sep_by, spanned,
     Parsed, Parser,
 };
-use ipso_syntax::{Declaration, InstanceMember, Keyword, Module, Names, Spanned, Type};
+use ipso_syntax::{
+    DataConstructor, Declaration, InstanceMember, Keyword, Module, Names, Spanned, Type,
+};
 use std::rc::Rc;
 
 /**
 ```text
 definition ::=
-  ident ':' type ident pattern* '=' expr
+  ident ':' type ident pattern_atom* '=' expr
 ```
 */
 pub fn definition(parser: &mut Parser) -> Parsed<Declaration> {
@@ -39,7 +41,7 @@ pub fn definition(parser: &mut Parser) -> Parsed<Declaration> {
                     many!(indent!(
                         parser,
                         Relation::Gt,
-                        spanned!(parser, pattern(parser))
+                        spanned!(parser, pattern_atom(parser))
                     ))
                 )
                 .and_then(|args| {
@@ -62,91 +64,222 @@ pub fn definition(parser: &mut Parser) -> Parsed<Declaration> {
 
 /**
 ```text
-type_alias ::=
-  'type' ctor '=' type
+data_constructor ::=
+  ctor type_atom*
+```
+*/
+pub fn data_constructor(parser: &mut Parser) -> Parsed<DataConstructor> {
+    spanned!(parser, indent!(parser, Relation::Gt, parser.ctor()))
+        .and_then(|name| many!(type_atom(parser)).map(|fields| DataConstructor { name, fields }))
+}
+
+/**
+Interpret a type as a data constructor.
+
+The first constructor of a data declaration without a leading `|` is parsed as a type,
+because it can't be distinguished from a type alias's body until a `|` is reached.
+*/
+fn type_to_data_constructor(ty: Spanned<Type<Rc<str>>>) -> Option<DataConstructor> {
+    let (head, fields) = ty.item.unwrap_app();
+    match head {
+        Type::Name(name) => Some(DataConstructor {
+            name: Spanned {
+                pos: ty.pos,
+                item: name.clone(),
+            },
+            fields: fields.into_iter().cloned().collect(),
+        }),
+        _ => None,
+    }
+}
+
+/**
+```text
+type_declaration ::=
+  'type' ctor ident* '=' '|' data_constructor ('|' data_constructor)*
+  'type' ctor ident* '=' type ('|' data_constructor)*
 ```
+
+A type declaration whose body is a `|`-separated list of constructors declares a new data
+type. A data type with a single constructor is written with a leading `|`, e.g.
+`type Box a = | Box a`. Any other type declaration is a type alias.
 */
-pub fn type_alias(parser: &mut Parser) -> Parsed<Declaration> {
+pub fn type_declaration(parser: &mut Parser) -> Parsed<Declaration> {
     indent_scope!(parser, {
         keep_right!(
             indent!(parser, Relation::Eq, parser.keyword(&Keyword::Type)),
-            indent!(parser, Relation::Gt, parser.ctor_owned()).and_then(|name| many!(
-                parser.ident_owned()
-            )
+            indent!(parser, Relation::Gt, parser.ctor()).and_then(|name| many!(spanned!(
+                parser,
+                indent!(parser, Relation::Gt, parser.ident())
+            ))
             .and_then(|args| keep_right!(
                 indent!(parser, Relation::Gt, parser.token(&token::Data::Equals)),
-                indent!(parser, Relation::Gt, type_(parser)).map(|body| Declaration::TypeAlias {
-                    name,
-                    args,
-                    body
-                })
+                choices!(
+                    keep_right!(
+                        indent!(parser, Relation::Gt, parser.token(&token::Data::Pipe)),
+                        sep_by!(
+                            data_constructor(parser),
+                            indent!(parser, Relation::Gt, parser.token(&token::Data::Pipe))
+                        )
+                    )
+                    .map(|constructors| Declaration::Data {
+                        name: name.clone(),
+                        args: args.clone(),
+                        constructors
+                    }),
+                    spanned!(parser, indent!(parser, Relation::Gt, type_(parser))).and_then(
+                        |body| many!(keep_right!(
+                            indent!(parser, Relation::Gt, parser.token(&token::Data::Pipe)),
+                            data_constructor(parser)
+                        ))
+                        .and_then(|rest| if rest.is_empty() {
+                            Parsed::pure(Declaration::TypeAlias {
+                                name: String::from(name.as_ref()),
+                                args: args
+                                    .iter()
+                                    .map(|arg| String::from(arg.item.as_ref()))
+                                    .collect(),
+                                body: body.item,
+                            })
+                        } else {
+                            let pos = body.pos;
+                            match type_to_data_constructor(body) {
+                                None => Parsed::expected_constructor(pos),
+                                Some(first) => {
+                                    let mut constructors = vec![first];
+                                    constructors.extend(rest);
+                                    Parsed::pure(Declaration::Data {
+                                        name: name.clone(),
+                                        args: args.clone(),
+                                        constructors,
+                                    })
+                                }
+                            }
+                        })
+                    )
+                )
             )))
         )
     })
 }
 
+/**
+```text
+module_name ::=
+  ident ['.' ident]*
+```
+
+The components of a module name are joined with `.`.
+*/
+pub fn module_name(parser: &mut Parser) -> Parsed<String> {
+    indent!(parser, Relation::Gt, parser.ident_owned()).and_then(|first| {
+        many!(keep_right!(
+            indent!(parser, Relation::Gt, parser.token(&token::Data::Dot)),
+            indent!(parser, Relation::Gt, parser.ident_owned())
+        ))
+        .map(|rest| {
+            rest.into_iter().fold(first, |mut name, component| {
+                name.push('.');
+                name.push_str(&component);
+                name
+            })
+        })
+    })
+}
+
 /**
 ```text
 import ::=
-  'import' ident ['as' ident]
+  'import' module_name ['as' ident]
 ```
 */
 pub fn import(parser: &mut Parser) -> Parsed<Declaration> {
     indent_scope!(parser, {
         keep_right!(
             indent!(parser, Relation::Eq, parser.keyword(&Keyword::Import)),
-            spanned!(parser, indent!(parser, Relation::Gt, parser.ident_owned())).and_then(
-                |module| {
-                    optional!(keep_right!(
-                        indent!(parser, Relation::Gt, parser.keyword(&Keyword::As)),
-                        spanned!(parser, indent!(parser, Relation::Gt, parser.ident_owned()))
-                    ))
-                    .map(|as_name| Declaration::Import {
-                        resolved: None,
-                        module,
-                        as_name,
-                    })
-                }
-            )
+            spanned!(parser, module_name(parser)).and_then(|module| {
+                optional!(keep_right!(
+                    indent!(parser, Relation::Gt, parser.keyword(&Keyword::As)),
+                    spanned!(parser, indent!(parser, Relation::Gt, parser.ident_owned()))
+                ))
+                .map(|as_name| Declaration::Import {
+                    resolved: None,
+                    module,
+                    as_name,
+                })
+            })
         )
     })
 }
 
 /**
 ```text
+from_import_name ::=
+  ident
+  ctor
+
 from_import_choices ::=
   '*'
-  ident [',' ident]
+  from_import_name [',' from_import_name]
 
 from_import ::=
-  'from' ident 'import'
+  'from' module_name 'import' from_import_choices
 ```
 */
 pub fn from_import(parser: &mut Parser) -> Parsed<Declaration> {
     indent_scope!(parser, {
         keep_right!(
             parser.keyword(&Keyword::From),
-            spanned!(parser, indent!(parser, Relation::Gt, parser.ident_owned())).and_then(
-                |module| keep_right!(
-                    indent!(parser, Relation::Gt, parser.keyword(&Keyword::Import)),
-                    choices!(
-                        map0!(
-                            Names::All,
-                            indent!(parser, Relation::Gt, parser.token(&token::Data::Asterisk))
+            spanned!(parser, module_name(parser)).and_then(|module| keep_right!(
+                indent!(parser, Relation::Gt, parser.keyword(&Keyword::Import)),
+                choices!(
+                    map0!(
+                        Names::All,
+                        indent!(parser, Relation::Gt, parser.token(&token::Data::Asterisk))
+                    ),
+                    sep_by!(
+                        indent!(
+                            parser,
+                            Relation::Gt,
+                            spanned!(parser, choices!(parser.ident_owned(), parser.ctor_owned()))
                         ),
-                        sep_by!(
-                            indent!(parser, Relation::Gt, spanned!(parser, parser.ident_owned())),
-                            indent!(parser, Relation::Gt, parser.token(&token::Data::Comma))
-                        )
-                        .map(Names::Names)
+                        indent!(parser, Relation::Gt, parser.token(&token::Data::Comma))
                     )
-                    .map(|names| Declaration::FromImport {
-                        resolved: None,
-                        module,
-                        names
-                    })
+                    .map(Names::Names)
                 )
+                .map(|names| Declaration::FromImport {
+                    resolved: None,
+                    module,
+                    names
+                })
+            ))
+        )
+    })
+}
+
+/**
+```text
+export_name ::=
+  ident
+  ctor
+
+export ::=
+  'export' export_name [',' export_name]
+```
+*/
+pub fn export(parser: &mut Parser) -> Parsed<Declaration> {
+    indent_scope!(parser, {
+        keep_right!(
+            indent!(parser, Relation::Eq, parser.keyword(&Keyword::Export)),
+            sep_by!(
+                indent!(
+                    parser,
+                    Relation::Gt,
+                    spanned!(parser, choices!(parser.ident_owned(), parser.ctor_owned()))
+                ),
+                indent!(parser, Relation::Gt, parser.token(&token::Data::Comma))
             )
+            .map(|names| Declaration::Export { names })
         )
     })
 }
@@ -177,18 +310,44 @@ pub fn assumptions(parser: &mut Parser) -> Parsed<Vec<Spanned<Type<Rc<str>>>>> {
     })
 }
 
+/// An item in the body of a class declaration.
+pub enum ClassMember {
+    /// A member's type signature.
+    Signature(String, Spanned<Type<Rc<str>>>),
+    /// A member's default implementation.
+    Default(InstanceMember),
+}
+
 /**
 ```text
 class_member ::=
   ident ':' type
+  ident pattern_atom* '=' expr
 ```
 */
-pub fn class_member(parser: &mut Parser) -> Parsed<(String, Spanned<Type<Rc<str>>>)> {
-    parser.ident_owned().and_then(|name| {
-        keep_right!(
-            indent!(parser, Relation::Gt, parser.token(&token::Data::Colon)),
-            indent!(parser, Relation::Gt, spanned!(parser, type_(parser)))
-                .map(|type_| (name, type_))
+pub fn class_member(parser: &mut Parser) -> Parsed<ClassMember> {
+    spanned!(parser, parser.ident_owned()).and_then(|name| {
+        choices!(
+            keep_right!(
+                indent!(parser, Relation::Gt, parser.token(&token::Data::Colon)),
+                indent!(parser, Relation::Gt, spanned!(parser, type_(parser)))
+                    .map(|type_| ClassMember::Signature(name.item.clone(), type_))
+            ),
+            many!(indent!(
+                parser,
+                Relation::Gt,
+                spanned!(parser, pattern_atom(parser))
+            ))
+            .and_then(|args| {
+                keep_right!(
+                    indent!(parser, Relation::Gt, parser.token(&token::Data::Equals)),
+                    expr(parser).map(|body| ClassMember::Default(InstanceMember {
+                        name,
+                        args,
+                        body
+                    }))
+                )
+            })
         )
     })
 }
@@ -218,12 +377,25 @@ pub fn class(parser: &mut Parser) -> Parsed<Declaration> {
                                 indent_scope!(
                                     parser,
                                     many!(indent!(parser, Relation::Eq, class_member(parser))).map(
-                                        |members| {
+                                        |class_members| {
+                                            let mut members = Vec::new();
+                                            let mut defaults = Vec::new();
+                                            for class_member in class_members {
+                                                match class_member {
+                                                    ClassMember::Signature(name, type_) => {
+                                                        members.push((name, type_))
+                                                    }
+                                                    ClassMember::Default(default) => {
+                                                        defaults.push(default)
+                                                    }
+                                                }
+                                            }
                                             Declaration::Class {
                                                 supers,
                                                 name,
                                                 args,
                                                 members,
+                                                defaults,
                                             }
                                         }
                                     )
@@ -240,7 +412,7 @@ pub fn class(parser: &mut Parser) -> Parsed<Declaration> {
 /*
 ```text
 instance_member ::=
-  ident pattern* '=' expr
+  ident pattern_atom* '=' expr
 ```
 */
 pub fn instance_member(parser: &mut Parser) -> Parsed<InstanceMember> {
@@ -248,7 +420,7 @@ pub fn instance_member(parser: &mut Parser) -> Parsed<InstanceMember> {
         many!(indent!(
             parser,
             Relation::Gt,
-            spanned!(parser, pattern(parser))
+            spanned!(parser, pattern_atom(parser))
         ))
         .and_then(|args| {
             keep_right!(
@@ -307,9 +479,10 @@ pub fn instance(parser: &mut Parser) -> Parsed<Declaration> {
 ```text
 declaration ::=
   definition
-  type_alias
+  type_declaration
   import
   from_import
+  export
   class
   instance
 ```
@@ -319,9 +492,10 @@ pub fn declaration(parser: &mut Parser) -> Parsed<Declaration> {
         many_!(parser.comment()),
         choices!(
             definition(parser),
-            type_alias(parser),
+            type_declaration(parser),
             import(parser),
             from_import(parser),
+            export(parser),
             class(parser),
             instance(parser)
         )
@@ -1,11 +1,13 @@
-use super::{definition, from_import, import, type_alias};
+use super::{class, definition, export, from_import, import, type_declaration};
 use crate::{keep_left, map2, Error, Parser};
 use ipso_diagnostic::Source;
 use ipso_lex::{
     token::{self, Relation},
     Lexer,
 };
-use ipso_syntax::{r#type::Type, Declaration, Expr, Names, Pattern, Spanned};
+use ipso_syntax::{
+    r#type::Type, DataConstructor, Declaration, Expr, InstanceMember, Names, Pattern, Spanned,
+};
 use std::rc::Rc;
 
 macro_rules! parse_test {
@@ -40,6 +42,22 @@ fn parse_import_1() {
     )
 }
 
+#[test]
+fn parse_import_dotted_1() {
+    parse_test!(
+        "import yes.no",
+        import,
+        Ok(Declaration::Import {
+            resolved: None,
+            module: Spanned {
+                pos: 7,
+                item: String::from("yes.no")
+            },
+            as_name: None
+        })
+    )
+}
+
 #[test]
 fn parse_import_as_1() {
     parse_test!(
@@ -231,7 +249,7 @@ fn parse_definition_5() {
 fn parse_type_alias_1() {
     parse_test!(
         "type Hello = ()",
-        type_alias,
+        type_declaration,
         Ok(Declaration::TypeAlias {
             name: String::from("Hello"),
             args: vec![],
@@ -244,7 +262,7 @@ fn parse_type_alias_1() {
 fn parse_type_alias_2() {
     parse_test!(
         "type Ap a b = a b",
-        type_alias,
+        type_declaration,
         Ok(Declaration::TypeAlias {
             name: String::from("Ap"),
             args: vec![String::from("a"), String::from("b")],
@@ -253,6 +271,77 @@ fn parse_type_alias_2() {
     )
 }
 
+#[test]
+fn parse_data_1() {
+    parse_test!(
+        "type Tree a = Leaf | Node (Tree a) a (Tree a)",
+        type_declaration,
+        Ok(Declaration::Data {
+            name: Rc::from("Tree"),
+            args: vec![Spanned {
+                pos: 10,
+                item: Rc::from("a")
+            }],
+            constructors: vec![
+                DataConstructor {
+                    name: Spanned {
+                        pos: 14,
+                        item: Rc::from("Leaf")
+                    },
+                    fields: vec![]
+                },
+                DataConstructor {
+                    name: Spanned {
+                        pos: 21,
+                        item: Rc::from("Node")
+                    },
+                    fields: vec![
+                        Type::mk_app(Type::Name(Rc::from("Tree")), Type::Var(Rc::from("a"))),
+                        Type::Var(Rc::from("a")),
+                        Type::mk_app(Type::Name(Rc::from("Tree")), Type::Var(Rc::from("a")))
+                    ]
+                }
+            ]
+        })
+    )
+}
+
+#[test]
+fn parse_data_2() {
+    parse_test!(
+        "type Box a =\n  | Box a",
+        type_declaration,
+        Ok(Declaration::Data {
+            name: Rc::from("Box"),
+            args: vec![Spanned {
+                pos: 9,
+                item: Rc::from("a")
+            }],
+            constructors: vec![DataConstructor {
+                name: Spanned {
+                    pos: 17,
+                    item: Rc::from("Box")
+                },
+                fields: vec![Type::Var(Rc::from("a"))]
+            }]
+        })
+    )
+}
+
+#[test]
+fn parse_data_3() {
+    parse_test!(
+        "type X = a | B",
+        type_declaration,
+        Err(Error::ExpectedConstructor {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 9
+        })
+    )
+}
+
 #[test]
 fn parse_from_import_1() {
     parse_test!(
@@ -297,3 +386,63 @@ fn parse_from_import_2() {
         })
     )
 }
+
+#[test]
+fn parse_class_1() {
+    parse_test!(
+        "class C a where\n  f : a\n  g x = x",
+        class,
+        Ok(Declaration::Class {
+            supers: Vec::new(),
+            name: Rc::from("C"),
+            args: vec![Spanned {
+                pos: 8,
+                item: Rc::from("a")
+            }],
+            members: vec![(
+                String::from("f"),
+                Spanned {
+                    pos: 22,
+                    item: Type::Var(Rc::from("a"))
+                }
+            )],
+            defaults: vec![InstanceMember {
+                name: Spanned {
+                    pos: 26,
+                    item: String::from("g")
+                },
+                args: vec![Spanned {
+                    pos: 28,
+                    item: Pattern::Name(Spanned {
+                        pos: 28,
+                        item: Rc::from("x")
+                    })
+                }],
+                body: Spanned {
+                    pos: 32,
+                    item: Expr::Var(String::from("x"))
+                }
+            }]
+        })
+    )
+}
+
+#[test]
+fn parse_export_1() {
+    parse_test!(
+        "export a, B",
+        export,
+        Ok(Declaration::Export {
+            names: vec![
+                Spanned {
+                    pos: 7,
+                    item: String::from("a")
+                },
+                Spanned {
+                    pos: 10,
+                    item: String::from("B")
+                }
+            ]
+        })
+    )
+}
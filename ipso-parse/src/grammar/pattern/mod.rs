@@ -4,30 +4,56 @@
 mod test;
 
 use crate::{
-    between, choices, indent, indent_scope, keep_right, map0, optional, spanned, Parsed, Parser,
+    between, choices, indent, indent_scope, keep_right, many, map0, optional, sep_by, spanned,
+    Parsed, Parser,
 };
 use ipso_lex::token;
 use ipso_syntax::{Pattern, Spanned};
 use std::rc::Rc;
 
+/**
+```text
+pattern_record_field ::=
+  ident ['=' pattern]
+```
+*/
+pub fn pattern_record_field(parser: &mut Parser) -> Parsed<(Spanned<Rc<str>>, Spanned<Pattern>)> {
+    spanned!(parser, indent!(parser, Relation::Gte, parser.ident())).and_then(|name| {
+        optional!(keep_right!(
+            indent!(parser, Relation::Gte, parser.token(&token::Data::Equals)),
+            indent!(parser, Relation::Gte, spanned!(parser, pattern(parser)))
+        ))
+        .map(|m_pattern| {
+            let pattern = match m_pattern {
+                None => Spanned {
+                    pos: name.pos,
+                    item: Pattern::Name(name.clone()),
+                },
+                Some(pattern) => pattern,
+            };
+            (name, pattern)
+        })
+    })
+}
+
 /**
 ```text
 pattern_record_fields ::=
-  ident [',' pattern_record_fields]
+  pattern_record_field [',' pattern_record_fields]
   '..' ident
 ```
 */
 pub fn pattern_record_fields(
     parser: &mut Parser,
-    names: &mut Vec<Spanned<Rc<str>>>,
+    fields: &mut Vec<(Spanned<Rc<str>>, Spanned<Pattern>)>,
 ) -> Parsed<Option<Spanned<Rc<str>>>> {
     choices!(
-        // ident [',' pattern_record_fields]
-        spanned!(parser, indent!(parser, Relation::Gte, parser.ident())).and_then(|name| {
-            names.push(name);
+        // pattern_record_field [',' pattern_record_fields]
+        pattern_record_field(parser).and_then(|field| {
+            fields.push(field);
             optional!(keep_right!(
                 indent!(parser, Relation::Gte, parser.token(&token::Data::Comma)),
-                pattern_record_fields(parser, names)
+                pattern_record_fields(parser, fields)
             ))
             .map(|m_rest| match m_rest {
                 None => None,
@@ -54,45 +80,79 @@ pub fn pattern_record(parser: &mut Parser) -> Parsed<Pattern> {
             indent!(parser, Relation::Eq, parser.token(&token::Data::LBrace)),
             indent!(parser, Relation::Gte, parser.token(&token::Data::RBrace)),
             {
-                let mut names = Vec::new();
+                let mut fields = Vec::new();
 
-                pattern_record_fields(parser, &mut names)
-                    .map(|rest| Pattern::Record { names, rest })
+                pattern_record_fields(parser, &mut fields)
+                    .map(|rest| Pattern::Record { fields, rest })
             }
         )
     })
 }
 
+/**
+```text
+pattern_array ::=
+  '[' [pattern (',' pattern)*] ']'
+```
+*/
+pub fn pattern_array(parser: &mut Parser) -> Parsed<Pattern> {
+    indent_scope!(parser, {
+        between!(
+            indent!(parser, Relation::Eq, parser.token(&token::Data::LBracket)),
+            indent!(parser, Relation::Gte, parser.token(&token::Data::RBracket)),
+            sep_by!(
+                indent!(parser, Relation::Gte, spanned!(parser, pattern(parser))),
+                indent!(parser, Relation::Gte, parser.token(&token::Data::Comma))
+            )
+        )
+        .map(Pattern::Array)
+    })
+}
+
 /**
 ```text
 pattern_variant ::=
-  ctor ident
+  ctor pattern_atom*
 ```
 */
 pub fn pattern_variant(parser: &mut Parser) -> Parsed<Pattern> {
     parser.ctor().and_then(|name| {
-        spanned!(parser, indent!(parser, Relation::Gt, parser.ident()))
-            .map(|arg| Pattern::Variant { name, arg })
+        many!(indent!(
+            parser,
+            Relation::Gt,
+            spanned!(parser, pattern_atom(parser))
+        ))
+        .map(|args| Pattern::Variant { name, args })
     })
 }
 
 /**
+A pattern that doesn't need to be parenthesised when it's a constructor's or a function's
+argument.
+
 ```text
-pattern ::=
+pattern_atom ::=
   ident
+  ctor
   pattern_record
-  pattern_variant
+  pattern_array
   char
   int
   '"' string '"'
   '_'
+  '(' pattern ')'
 ```
 */
-pub fn pattern(parser: &mut Parser) -> Parsed<Pattern> {
+pub fn pattern_atom(parser: &mut Parser) -> Parsed<Pattern> {
     choices!(
-        spanned!(parser, parser.ident()).map(Pattern::Name),
+        spanned!(parser, parser.ident())
+            .and_then(|name| optional!(parser.qualified_ctor()).map(|_| Pattern::Name(name))),
+        parser.ctor().map(|name| Pattern::Variant {
+            name,
+            args: Vec::new()
+        }),
         pattern_record(parser),
-        pattern_variant(parser),
+        pattern_array(parser),
         spanned!(parser, parser.char()).map(Pattern::Char),
         spanned!(parser, parser.int()).map(Pattern::Int),
         spanned!(
@@ -107,6 +167,22 @@ pub fn pattern(parser: &mut Parser) -> Parsed<Pattern> {
             pos: s.pos,
             item: Rc::from(s.item)
         })),
-        map0!(Pattern::Wildcard, parser.token(&token::Data::Underscore))
+        map0!(Pattern::Wildcard, parser.token(&token::Data::Underscore)),
+        between!(
+            parser.token(&token::Data::LParen),
+            indent!(parser, Relation::Gt, parser.token(&token::Data::RParen)),
+            indent!(parser, Relation::Gt, pattern(parser))
+        )
     )
 }
+
+/**
+```text
+pattern ::=
+  pattern_variant
+  pattern_atom
+```
+*/
+pub fn pattern(parser: &mut Parser) -> Parsed<Pattern> {
+    choices!(pattern_variant(parser), pattern_atom(parser))
+}
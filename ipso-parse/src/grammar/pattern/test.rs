@@ -37,3 +37,139 @@ fn parse_pattern_1() {
 fn parse_pattern_2() {
     parse_test!("_", pattern, Ok(Pattern::Wildcard))
 }
+
+#[test]
+fn parse_pattern_3() {
+    parse_test!(
+        "Node l x r",
+        pattern,
+        Ok(Pattern::Variant {
+            name: Rc::from("Node"),
+            args: vec![
+                Spanned {
+                    pos: 5,
+                    item: Pattern::Name(Spanned {
+                        pos: 5,
+                        item: Rc::from("l")
+                    })
+                },
+                Spanned {
+                    pos: 7,
+                    item: Pattern::Name(Spanned {
+                        pos: 7,
+                        item: Rc::from("x")
+                    })
+                },
+                Spanned {
+                    pos: 9,
+                    item: Pattern::Name(Spanned {
+                        pos: 9,
+                        item: Rc::from("r")
+                    })
+                }
+            ]
+        })
+    )
+}
+
+#[test]
+fn parse_pattern_4() {
+    parse_test!(
+        "Leaf",
+        pattern,
+        Ok(Pattern::Variant {
+            name: Rc::from("Leaf"),
+            args: vec![]
+        })
+    )
+}
+
+#[test]
+fn parse_pattern_5() {
+    parse_test!(
+        "Some (Ok x)",
+        pattern,
+        Ok(Pattern::Variant {
+            name: Rc::from("Some"),
+            args: vec![Spanned {
+                pos: 5,
+                item: Pattern::Variant {
+                    name: Rc::from("Ok"),
+                    args: vec![Spanned {
+                        pos: 9,
+                        item: Pattern::Name(Spanned {
+                            pos: 9,
+                            item: Rc::from("x")
+                        })
+                    }]
+                }
+            }]
+        })
+    )
+}
+
+#[test]
+fn parse_pattern_6() {
+    parse_test!(
+        "{ value = Ok x, next }",
+        pattern,
+        Ok(Pattern::Record {
+            fields: vec![
+                (
+                    Spanned {
+                        pos: 2,
+                        item: Rc::from("value")
+                    },
+                    Spanned {
+                        pos: 10,
+                        item: Pattern::Variant {
+                            name: Rc::from("Ok"),
+                            args: vec![Spanned {
+                                pos: 13,
+                                item: Pattern::Name(Spanned {
+                                    pos: 13,
+                                    item: Rc::from("x")
+                                })
+                            }]
+                        }
+                    }
+                ),
+                (
+                    Spanned {
+                        pos: 16,
+                        item: Rc::from("next")
+                    },
+                    Spanned {
+                        pos: 16,
+                        item: Pattern::Name(Spanned {
+                            pos: 16,
+                            item: Rc::from("next")
+                        })
+                    }
+                )
+            ],
+            rest: None
+        })
+    )
+}
+
+#[test]
+fn parse_pattern_7() {
+    parse_test!(
+        "[x, 1]",
+        pattern,
+        Ok(Pattern::Array(vec![
+            Spanned {
+                pos: 1,
+                item: Pattern::Name(Spanned {
+                    pos: 1,
+                    item: Rc::from("x")
+                })
+            },
+            Spanned {
+                pos: 4,
+                item: Pattern::Int(Spanned { pos: 4, item: 1 })
+            }
+        ]))
+    )
+}
@@ -128,6 +128,7 @@ pub fn type_variant(parser: &mut Parser) -> Parsed<Type<Rc<str>>> {
 type_atom ::=
   'Bool'
   'Int'
+  'Float'
   'Char'
   'String'
   'Array'
@@ -153,6 +154,10 @@ pub fn type_atom(parser: &mut Parser) -> Parsed<Type<Rc<str>>> {
                 Type::Int,
                 parser.token(&token::Data::Ident(Rc::from("Int")))
             ),
+            map0!(
+                Type::Float,
+                parser.token(&token::Data::Ident(Rc::from("Float")))
+            ),
             map0!(
                 Type::Char,
                 parser.token(&token::Data::Ident(Rc::from("Char")))
@@ -173,7 +178,9 @@ pub fn type_atom(parser: &mut Parser) -> Parsed<Type<Rc<str>>> {
             type_record(parser),
             type_variant(parser),
             parser.ctor().map(Type::Name),
-            parser.ident().map(Type::Var),
+            parser
+                .ident()
+                .and_then(|name| optional!(parser.qualified_ctor()).map(|_| Type::Var(name))),
             between!(
                 indent!(parser, Relation::Gt, parser.token(&token::Data::LParen)),
                 indent!(parser, Relation::Gt, parser.token(&token::Data::RParen)),
@@ -1,5 +1,5 @@
 use super::type_;
-use crate::{keep_left, map2, Parser};
+use crate::{keep_left, map2, Error, Parser};
 use ipso_diagnostic::Source;
 use ipso_lex::Lexer;
 use ipso_syntax::{Spanned, Type};
@@ -141,3 +141,17 @@ fn parse_type_4() {
 fn parse_type_5() {
     parse_test!("IO ()", type_, Ok(Type::mk_app(Type::IO, Type::Unit)))
 }
+
+#[test]
+fn parse_type_6() {
+    parse_test!(
+        "lib.Opt Int",
+        type_,
+        Err(Error::QualifiedConstructor {
+            source: Source::Interactive {
+                label: String::from("(parser)"),
+            },
+            pos: 4
+        })
+    )
+}
@@ -12,9 +12,10 @@ use ipso_lex::{
     token::{self, Relation, Sign, Token},
     Lexer,
 };
-use ipso_syntax::{self as syntax, Binop, Keyword, Module, Spanned};
+use ipso_syntax::{self as syntax, Binop, Float, Keyword, Module, Spanned};
 use std::{
     collections::BTreeSet,
+    convert::TryFrom,
     fs::File,
     io::Read,
     path::{Path, PathBuf},
@@ -36,6 +37,23 @@ pub enum Error {
         pos: usize,
         operator: Binop,
     },
+    IntegerOutOfRange {
+        source: Source,
+        pos: usize,
+    },
+    ExpectedConstructor {
+        source: Source,
+        pos: usize,
+    },
+    QualifiedConstructor {
+        source: Source,
+        pos: usize,
+    },
+    UnsupportedFileDescriptor {
+        source: Source,
+        pos: usize,
+        fd: u64,
+    },
 }
 
 impl Error {
@@ -43,6 +61,10 @@ impl Error {
         match self {
             Error::Unexpected { source, .. } => source.clone(),
             Error::AmbiguousUseOf { source, .. } => source.clone(),
+            Error::IntegerOutOfRange { source, .. } => source.clone(),
+            Error::ExpectedConstructor { source, .. } => source.clone(),
+            Error::QualifiedConstructor { source, .. } => source.clone(),
+            Error::UnsupportedFileDescriptor { source, .. } => source.clone(),
         }
     }
 
@@ -50,6 +72,10 @@ impl Error {
         match self {
             Error::Unexpected { pos, .. } => *pos,
             Error::AmbiguousUseOf { pos, .. } => *pos,
+            Error::IntegerOutOfRange { pos, .. } => *pos,
+            Error::ExpectedConstructor { pos, .. } => *pos,
+            Error::QualifiedConstructor { pos, .. } => *pos,
+            Error::UnsupportedFileDescriptor { pos, .. } => *pos,
         }
     }
 
@@ -75,6 +101,29 @@ impl Error {
                 str.push_str(operator.render());
                 str
             }
+            Error::IntegerOutOfRange { .. } => String::from("integer literal out of range"),
+            Error::ExpectedConstructor { .. } => String::from("expected a data constructor"),
+            Error::QualifiedConstructor { .. } => {
+                String::from("data types and constructors can't be accessed through a module")
+            }
+            Error::UnsupportedFileDescriptor { fd, .. } => {
+                format!("unsupported file descriptor {} in redirection", fd)
+            }
+        }
+    }
+
+    pub fn addendum(&self) -> Option<String> {
+        match self {
+            Error::Unexpected { .. } => None,
+            Error::AmbiguousUseOf { .. } => None,
+            Error::IntegerOutOfRange { .. } => None,
+            Error::ExpectedConstructor { .. } => None,
+            Error::QualifiedConstructor { .. } => Some(String::from(
+                "bring it into scope with `from <module> import <name>` instead",
+            )),
+            Error::UnsupportedFileDescriptor { .. } => Some(String::from(
+                "only stdout (1) and stderr (2) can be redirected",
+            )),
         }
     }
 
@@ -86,7 +135,7 @@ impl Error {
             }),
             Message {
                 content: self.message(),
-                addendum: None,
+                addendum: self.addendum(),
             },
         )
     }
@@ -96,6 +145,10 @@ impl Error {
 pub enum ErrorName {
     Unexpected,
     AmbiguousUseOf(Spanned<Binop>),
+    IntegerOutOfRange { pos: usize },
+    ExpectedConstructor { pos: usize },
+    QualifiedConstructor { pos: usize },
+    UnsupportedFileDescriptor { pos: usize, fd: u64 },
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -146,6 +199,38 @@ impl<A> Parsed<A> {
         }
     }
 
+    /// The integer token was recognised, so this error is reported instead of trying alternatives.
+    fn integer_out_of_range(pos: usize) -> Self {
+        Parsed {
+            consumed: true,
+            result: Err(ErrorName::IntegerOutOfRange { pos }),
+        }
+    }
+
+    /// A data declaration's body was a type that isn't a constructor applied to fields.
+    fn expected_constructor(pos: usize) -> Self {
+        Parsed {
+            consumed: true,
+            result: Err(ErrorName::ExpectedConstructor { pos }),
+        }
+    }
+
+    /// A data type or constructor was prefixed with a module name, e.g. `lib.Some`.
+    fn qualified_constructor(pos: usize) -> Self {
+        Parsed {
+            consumed: true,
+            result: Err(ErrorName::QualifiedConstructor { pos }),
+        }
+    }
+
+    /// A command redirected a file descriptor other than stdout or stderr, e.g. `3> file`.
+    fn unsupported_file_descriptor(pos: usize, fd: u64) -> Self {
+        Parsed {
+            consumed: true,
+            result: Err(ErrorName::UnsupportedFileDescriptor { pos, fd }),
+        }
+    }
+
     fn unexpected(consumed: bool) -> Self {
         Parsed {
             consumed,
@@ -521,6 +606,25 @@ impl<'input> Parser<'input> {
                     pos: operator.pos,
                     operator: operator.item,
                 },
+                ErrorName::IntegerOutOfRange { pos } => Error::IntegerOutOfRange {
+                    source: self.source,
+                    pos,
+                },
+                ErrorName::ExpectedConstructor { pos } => Error::ExpectedConstructor {
+                    source: self.source,
+                    pos,
+                },
+                ErrorName::QualifiedConstructor { pos } => Error::QualifiedConstructor {
+                    source: self.source,
+                    pos,
+                },
+                ErrorName::UnsupportedFileDescriptor { pos, fd } => {
+                    Error::UnsupportedFileDescriptor {
+                        source: self.source,
+                        pos,
+                        fd,
+                    }
+                }
             }),
         }
     }
@@ -632,7 +736,23 @@ impl<'input> Parser<'input> {
         self.ctor().map(|s| String::from(s.as_ref()))
     }
 
-    fn int(&mut self) -> Parsed<i32> {
+    /**
+    Reject a data type or constructor that's accessed through a module, e.g. `lib.Some`.
+
+    `.` can't follow a type variable or a pattern variable, so it isn't recorded as an expected
+    token.
+    */
+    fn qualified_ctor(&mut self) -> Parsed<()> {
+        match &self.current {
+            Some(token) if token.data == token::Data::Dot => self
+                .consume()
+                .and_then(|_| spanned!(self, self.ctor()))
+                .and_then(|ctor| Parsed::qualified_constructor(ctor.pos)),
+            _ => Parsed::unexpected(false),
+        }
+    }
+
+    fn int(&mut self) -> Parsed<i64> {
         self.expecting.insert(token::Name::Int);
         (match &self.current {
             Some(token) => match &token.data {
@@ -640,20 +760,35 @@ impl<'input> Parser<'input> {
                     sign,
                     value,
                     length: _,
-                } => Parsed::pure((*sign, *value)),
+                } => {
+                    let value = match sign {
+                        Sign::Negative => i64::try_from(-i128::from(*value)).ok(),
+                        Sign::None => i64::try_from(*value).ok(),
+                    };
+                    match value {
+                        Some(value) => Parsed::pure(value),
+                        None => Parsed::integer_out_of_range(token.pos),
+                    }
+                }
                 _ => Parsed::unexpected(false),
             },
             None => Parsed::unexpected(false),
         })
-        .and_then(|(sign, value)| {
-            map0!(
-                match sign {
-                    Sign::Negative => -(value as i32),
-                    Sign::None => value as i32,
-                },
-                self.consume()
-            )
-        })
+        .and_then(|value| map0!(value, self.consume()))
+    }
+
+    fn float(&mut self) -> Parsed<Float> {
+        self.expecting.insert(token::Name::Float);
+        match &self.current {
+            Some(token) => match &token.data {
+                token::Data::Float { value, length: _ } => {
+                    let value = *value;
+                    map0!(value, self.consume())
+                }
+                _ => Parsed::unexpected(false),
+            },
+            None => Parsed::unexpected(false),
+        }
     }
 
     /// ```
@@ -1,6 +1,6 @@
 use ipso_core::{CommonKinds, Type};
 use ipso_diagnostic::Source;
-use ipso_eval::{closure_conversion, Env, Interpreter, Object};
+use ipso_eval::{closure_conversion, Env, Interpreter, Object, RuntimeError};
 use ipso_import::{resolve_from_import_all, rewrite_module_accessors_expr, ImportedItemInfo};
 use ipso_syntax::{
     desugar::{self, desugar_expr},
@@ -25,6 +25,7 @@ pub enum Error {
     TypeError(type_inference::Error),
     CheckError(ipso_typecheck::Error),
     DesugarError(desugar::Error),
+    RuntimeError(RuntimeError),
 }
 
 impl From<ipso_typecheck::Error> for Error {
@@ -45,12 +46,23 @@ impl From<desugar::Error> for Error {
     }
 }
 
+impl From<RuntimeError> for Error {
+    fn from(err: RuntimeError) -> Self {
+        Error::RuntimeError(err)
+    }
+}
+
 impl Error {
     pub fn position(&self) -> usize {
         match self {
             Error::TypeError(err) => err.position,
             Error::CheckError(err) => err.position(),
             Error::DesugarError(err) => err.position(),
+            Error::RuntimeError(err) => err
+                .location
+                .as_ref()
+                .and_then(|location| location.offset)
+                .unwrap_or(0),
         }
     }
 
@@ -59,6 +71,7 @@ impl Error {
             Error::TypeError(err) => err.message(),
             Error::CheckError(err) => err.message(),
             Error::DesugarError(err) => err.message(),
+            Error::RuntimeError(err) => err.message(),
         }
     }
 }
@@ -97,12 +110,14 @@ impl Repl {
         let mut type_context = HashMap::new();
         let mut context = HashMap::new();
         let mut class_context = HashMap::new();
+        let mut constructor_context = HashMap::new();
         register_from_import(
             &common_kinds,
             &mut implications,
             &mut type_context,
             &mut context,
             &mut class_context,
+            &mut constructor_context,
             &modules,
             builtins_module_id,
             &Names::All,
@@ -131,9 +146,11 @@ impl Repl {
         let env = type_inference::Env {
             common_kinds: &self.common_kinds,
             modules: &self.module_context,
+            unexported: &Default::default(),
             types: &Default::default(),
             type_variables: &Default::default(),
             type_signatures: &Default::default(),
+            constructors: &Default::default(),
             source: &self.source,
         };
         let mut state = type_inference::State::new();
@@ -156,9 +173,11 @@ impl Repl {
             let env = type_inference::Env {
                 common_kinds: &self.common_kinds,
                 modules: &self.module_context,
+                unexported: &Default::default(),
                 types: &Default::default(),
                 type_variables: &Default::default(),
                 type_signatures: &Default::default(),
+                constructors: &Default::default(),
                 source: &self.source,
             };
 
@@ -169,6 +188,7 @@ impl Repl {
             let (mut expr, show_final_value) = match ty {
                 Type::Bool
                 | Type::Int
+                | Type::Float
                 | Type::Char
                 | Type::String
                 | Type::Bytes
@@ -241,7 +261,13 @@ impl Repl {
                 | Type::RowCons(_, _, _)
                 | Type::HasField(_, _)
                 | Type::DebugRecordFields
-                | Type::DebugVariantCtor => unreachable!(),
+                | Type::DebugVariantCtor
+                | Type::ToJsonRecordFields
+                | Type::FromJsonRecordFields
+                | Type::EqRecordFields
+                | Type::EqVariantCtor
+                | Type::OrdRecordFields
+                | Type::OrdVariantCtor => unreachable!(),
             };
 
             rewrite_module_accessors_expr(&mut Default::default(), &self.imported_items, &mut expr);
@@ -277,23 +303,27 @@ impl Repl {
             stdout,
             &self.common_kinds,
             &self.modules,
+            &self.source,
             &context,
         );
 
-        let value = interpreter.eval(&mut Env::new(), &expr);
+        let value = interpreter.eval(&mut Env::new(), &expr)?;
 
-        match value {
+        match &value {
             ipso_eval::Value::Object(object) => match object.as_ref() {
                 Object::Bytes(_)
                 | Object::Variant(_, _)
                 | Object::Array(_)
                 | Object::Record(_)
+                | Object::Map(_)
+                | Object::Set(_)
+                | Object::Regex(_)
                 | Object::Closure { .. }
                 | Object::StaticClosure { .. }
                 | Object::Cmd(_) => todo!(),
                 Object::String(s) => Ok(Some(String::from(s.as_ref()))),
-                Object::IO { env, body } => {
-                    let result = body.run(&mut interpreter, env.clone());
+                Object::IO { .. } | Object::LocatedIO { .. } => {
+                    let result = value.perform_io(&mut interpreter)?;
                     Ok(if show_final_value {
                         Some(String::from(result.unpack_string()))
                     } else {
@@ -306,6 +336,7 @@ impl Repl {
             ipso_eval::Value::True
             | ipso_eval::Value::False
             | ipso_eval::Value::Int(_)
+            | ipso_eval::Value::Float(_)
             | ipso_eval::Value::Char(_)
             | ipso_eval::Value::Unit => unreachable!(),
         }
@@ -4,6 +4,15 @@ use crate::{
 use ipso_diagnostic::{Diagnostic, Location, Message, Source};
 use std::rc::Rc;
 
+/**
+The builtin that computation expressions are desugared into.
+
+`comp.bind : Monad m => (a -> m b) -> m a -> m b` is `andThen` with its arguments swapped, so
+the rest of a computation expression is checked before the value that it binds. The name isn't a
+valid identifier, so programs can't shadow it.
+*/
+pub const COMP_BIND: &str = "comp.bind";
+
 /// An invalid ending for a computation expression.
 #[derive(PartialEq, Eq, Debug)]
 pub enum CompExprEnd {
@@ -74,13 +83,17 @@ fn desugar_module_mut(source: &Source, module: &mut Module) -> Result<(), Error>
 fn desugar_decl_mut(source: &Source, decl: &mut Declaration) -> Result<(), Error> {
     match decl {
         Declaration::Definition { body, .. } => desugar_expr_mut(source, body),
-        Declaration::Instance { members, .. } => members
+        Declaration::Instance { members, .. }
+        | Declaration::Class {
+            defaults: members, ..
+        } => members
             .iter_mut()
             .try_for_each(|member| desugar_expr_mut(source, &mut member.body)),
-        Declaration::Class { .. }
-        | Declaration::TypeAlias { .. }
+        Declaration::TypeAlias { .. }
+        | Declaration::Data { .. }
         | Declaration::Import { .. }
-        | Declaration::FromImport { .. } => Ok(()),
+        | Declaration::FromImport { .. }
+        | Declaration::Export { .. } => Ok(()),
     }
 }
 
@@ -97,7 +110,7 @@ fn desugar_branch_mut(source: &Source, branch: &mut Branch) -> Result<(), Error>
 
 fn desugar_cmd_part_mut(source: &Source, cmd_part: &mut CmdPart) -> Result<(), Error> {
     match cmd_part {
-        CmdPart::Literal(_) => Ok(()),
+        CmdPart::Literal(_) | CmdPart::Pipe | CmdPart::Redirect(_) => Ok(()),
         CmdPart::Expr(expr) => {
             desugar_expr_mut(source, expr)?;
             *expr = Expr::mk_app(
@@ -264,65 +277,47 @@ fn desugar_expr_mut(source: &Source, expr: &mut Spanned<Expr>) -> Result<(), Err
                                 let comp_line_pos = comp_line.pos;
                                 match comp_line.item {
                                     CompLine::Expr(comp_line_expr) => {
-                                        // io.andThen <comp_line_expr> (\_ -> <expr>)
+                                        // comp.bind (\_ -> <expr>) <comp_line_expr>
                                         Expr::mk_app(
                                             Expr::mk_app(
                                                 Spanned {
                                                     pos: comp_line_pos,
-                                                    item: Expr::mk_project(
-                                                        Spanned {
-                                                            pos: comp_line.pos,
-                                                            item: Expr::mk_var("io"),
-                                                        },
-                                                        Spanned {
-                                                            pos: comp_line.pos,
-                                                            item: String::from("andThen"),
-                                                        },
+                                                    item: Expr::mk_var(COMP_BIND),
+                                                },
+                                                Spanned {
+                                                    pos: expr.pos,
+                                                    item: Expr::mk_lam(
+                                                        vec![Spanned {
+                                                            pos: expr.pos,
+                                                            item: Pattern::Wildcard,
+                                                        }],
+                                                        expr,
                                                     ),
                                                 },
-                                                comp_line_expr,
                                             ),
-                                            Spanned {
-                                                pos: expr.pos,
-                                                item: Expr::mk_lam(
-                                                    vec![Spanned {
-                                                        pos: expr.pos,
-                                                        item: Pattern::Wildcard,
-                                                    }],
-                                                    expr,
-                                                ),
-                                            },
+                                            comp_line_expr,
                                         )
                                     }
                                     CompLine::Bind(name, value) => {
-                                        // io.andThen <value> (\<name> -> <expr>)
+                                        // comp.bind (\<name> -> <expr>) <value>
                                         Expr::mk_app(
                                             Expr::mk_app(
                                                 Spanned {
                                                     pos: comp_line_pos,
-                                                    item: Expr::mk_project(
-                                                        Spanned {
-                                                            pos: comp_line.pos,
-                                                            item: Expr::mk_var("io"),
-                                                        },
-                                                        Spanned {
-                                                            pos: comp_line.pos,
-                                                            item: String::from("andThen"),
-                                                        },
+                                                    item: Expr::mk_var(COMP_BIND),
+                                                },
+                                                Spanned {
+                                                    pos: expr.pos,
+                                                    item: Expr::mk_lam(
+                                                        vec![Spanned {
+                                                            pos: expr.pos,
+                                                            item: Pattern::Name(name),
+                                                        }],
+                                                        expr,
                                                     ),
                                                 },
-                                                value,
                                             ),
-                                            Spanned {
-                                                pos: expr.pos,
-                                                item: Expr::mk_lam(
-                                                    vec![Spanned {
-                                                        pos: expr.pos,
-                                                        item: Pattern::Name(name),
-                                                    }],
-                                                    expr,
-                                                ),
-                                            },
+                                            value,
                                         )
                                     }
                                     CompLine::Let(name, value) => {
@@ -347,6 +342,7 @@ fn desugar_expr_mut(source: &Source, expr: &mut Spanned<Expr>) -> Result<(), Err
         Expr::Variant(_)
         | Expr::Module { .. }
         | Expr::Int(_)
+        | Expr::Float(_)
         | Expr::Char(_)
         | Expr::True
         | Expr::False
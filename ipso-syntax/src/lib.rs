@@ -30,6 +30,41 @@ impl<A> Spanned<A> {
     }
 }
 
+/**
+A floating point number that can appear in syntax trees.
+
+Floats are compared by [`f64::total_cmp`] so that syntax trees can be [`Eq`], [`Ord`] and
+[`Hash`]. Use the wrapped [`f64`] for arithmetic.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Float {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 pub enum Keyword {
     Case,
@@ -50,6 +85,7 @@ pub enum Keyword {
     In,
     Comp,
     Bind,
+    Export,
 }
 
 impl Arbitrary for Keyword {
@@ -73,6 +109,7 @@ impl Arbitrary for Keyword {
             Keyword::In,
             Keyword::Comp,
             Keyword::Bind,
+            Keyword::Export,
         ])
         .unwrap()
     }
@@ -80,7 +117,7 @@ impl Arbitrary for Keyword {
 
 impl Keyword {
     pub fn num_variants() -> usize {
-        18
+        19
     }
 
     pub fn matches(&self, actual: &str) -> bool {
@@ -107,6 +144,7 @@ impl Keyword {
             Keyword::In => "in",
             Keyword::Comp => "comp",
             Keyword::Bind => "bind",
+            Keyword::Export => "export",
         }
     }
 
@@ -130,6 +168,7 @@ impl Keyword {
             "in" => Some(Keyword::In),
             "comp" => Some(Keyword::Comp),
             "bind" => Some(Keyword::Bind),
+            "export" => Some(Keyword::Export),
             _ => None,
         }
     }
@@ -137,7 +176,7 @@ impl Keyword {
 
 const KEYWORDS: &[&str] = &[
     "case", "of", "if", "then", "else", "true", "false", "import", "as", "from", "where", "type",
-    "class", "instance", "let", "in", "comp", "bind",
+    "class", "instance", "let", "in", "comp", "bind", "export",
 ];
 
 pub fn is_keyword(val: &str) -> bool {
@@ -253,92 +292,90 @@ pub enum StringPart {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Pattern {
     Name(Spanned<Rc<str>>),
+    /**
+    A record pattern.
+
+    A field without a pattern (e.g. `{ x }`) binds the field to a variable of the same name,
+    and is represented as `(x, Pattern::Name(x))`.
+    */
     Record {
-        names: Vec<Spanned<Rc<str>>>,
+        fields: Vec<(Spanned<Rc<str>>, Spanned<Pattern>)>,
         rest: Option<Spanned<Rc<str>>>,
     },
+    /**
+    A constructor pattern.
+
+    Variant constructors take exactly one argument. Data constructors take one argument
+    per field.
+    */
     Variant {
         name: Rc<str>,
-        arg: Spanned<Rc<str>>,
+        args: Vec<Spanned<Pattern>>,
     },
+    /// An array pattern, which matches arrays of exactly its length.
+    Array(Vec<Spanned<Pattern>>),
     Char(Spanned<char>),
-    Int(Spanned<i32>),
+    Int(Spanned<i64>),
     String(Spanned<Rc<str>>),
     Wildcard,
 }
 
 pub struct IterNames<'a> {
-    items: Vec<&'a Spanned<Rc<str>>>,
-    pattern: Option<&'a Pattern>,
+    names: std::vec::IntoIter<&'a Spanned<Rc<str>>>,
 }
 
 impl<'a> Iterator for IterNames<'a> {
     type Item = &'a Spanned<Rc<str>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.pattern {
-            None => match self.items.pop() {
-                None => None,
-                Some(item) => Some(item),
-            },
-            Some(pattern) => {
-                self.pattern = None;
-                match pattern {
-                    Pattern::Name(n) => Some(n),
-                    Pattern::Record { names, rest } => {
-                        match rest {
-                            Some(n) => {
-                                self.items.push(n);
-                            }
-                            None => {}
-                        }
-                        self.items.extend(names.iter().rev());
-                        self.next()
-                    }
-                    Pattern::Variant { name: _, arg } => Some(arg),
-                    Pattern::Char(_) => None,
-                    Pattern::Int(_) => None,
-                    Pattern::String(_) => None,
-                    Pattern::Wildcard => None,
-                }
-            }
-        }
+        self.names.next()
     }
 }
 
 impl Pattern {
     pub fn iter_names(&self) -> IterNames {
         IterNames {
-            items: Vec::new(),
-            pattern: Some(self),
+            names: self.get_arg_names().into_iter(),
         }
     }
 
+    /// Get the names bound by a pattern, from left to right.
     pub fn get_arg_names(&self) -> Vec<&Spanned<Rc<str>>> {
-        let mut arg_names = Vec::new();
-        match self {
-            Pattern::Name(n) => {
-                arg_names.push(n);
-            }
-            Pattern::Record { names, rest } => {
-                for name in names {
-                    arg_names.push(name);
+        fn go<'a>(pattern: &'a Pattern, arg_names: &mut Vec<&'a Spanned<Rc<str>>>) {
+            match pattern {
+                Pattern::Name(n) => {
+                    arg_names.push(n);
                 }
-                match rest {
-                    None => {}
-                    Some(n) => {
-                        arg_names.push(n);
+                Pattern::Record { fields, rest } => {
+                    for (_, pattern) in fields {
+                        go(&pattern.item, arg_names);
+                    }
+                    match rest {
+                        None => {}
+                        Some(n) => {
+                            arg_names.push(n);
+                        }
                     }
                 }
+                Pattern::Variant { name: _, args } => {
+                    for arg in args {
+                        go(&arg.item, arg_names);
+                    }
+                }
+                Pattern::Array(items) => {
+                    for item in items {
+                        go(&item.item, arg_names);
+                    }
+                }
+                Pattern::Char(_) => {}
+                Pattern::Int(_) => {}
+                Pattern::String(_) => {}
+                Pattern::Wildcard => {}
             }
-            Pattern::Variant { name: _, arg } => {
-                arg_names.push(arg);
-            }
-            Pattern::Char(_) => {}
-            Pattern::Int(_) => {}
-            Pattern::String(_) => {}
-            Pattern::Wildcard => {}
         }
+
+        let mut arg_names = Vec::new();
+        go(self, &mut arg_names);
         arg_names
     }
 }
@@ -365,10 +402,63 @@ pub enum CompLine {
     Let(Spanned<Rc<str>>, Spanned<Expr>),
 }
 
+/// A redirection of one of a process' standard streams.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Redirect {
+    /// `< path`
+    Stdin,
+    /// `> path`
+    Stdout,
+    /// `>> path`
+    StdoutAppend,
+    /// `2> path`
+    Stderr,
+    /// `2>> path`
+    StderrAppend,
+    /// `2>&1`
+    StderrToStdout,
+    /// `>&2`
+    StdoutToStderr,
+}
+
+impl Redirect {
+    /// Whether the redirection is followed by a path.
+    pub fn has_target(&self) -> bool {
+        match self {
+            Redirect::Stdin
+            | Redirect::Stdout
+            | Redirect::StdoutAppend
+            | Redirect::Stderr
+            | Redirect::StderrAppend => true,
+            Redirect::StderrToStdout | Redirect::StdoutToStderr => false,
+        }
+    }
+
+    pub fn render(&self) -> &'static str {
+        match self {
+            Redirect::Stdin => "<",
+            Redirect::Stdout => ">",
+            Redirect::StdoutAppend => ">>",
+            Redirect::Stderr => "2>",
+            Redirect::StderrAppend => "2>>",
+            Redirect::StderrToStdout => "2>&1",
+            Redirect::StdoutToStderr => ">&2",
+        }
+    }
+}
+
+/**
+A piece of a command literal.
+
+[`CmdPart::Pipe`] separates the processes of a pipeline. A [`CmdPart::Redirect`] for which
+[`Redirect::has_target`] holds is immediately followed by the part that names its target.
+*/
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum CmdPart {
     Literal(Rc<str>),
     Expr(Spanned<Expr>),
+    Pipe,
+    Redirect(Redirect),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -424,7 +514,8 @@ pub enum Expr {
     False,
     IfThenElse(Rc<Spanned<Expr>>, Rc<Spanned<Expr>>, Rc<Spanned<Expr>>),
 
-    Int(i32),
+    Int(i64),
+    Float(Float),
 
     Binop(Spanned<Binop>, Rc<Spanned<Expr>>, Rc<Spanned<Expr>>),
 
@@ -530,6 +621,17 @@ pub struct InstanceMember {
     pub body: Spanned<Expr>,
 }
 
+/**
+A constructor of a [`Declaration::Data`].
+
+Errors in the constructor's fields are reported at the constructor's name.
+*/
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DataConstructor {
+    pub name: Spanned<Rc<str>>,
+    pub fields: Vec<Type<Rc<str>>>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Declaration {
     Definition {
@@ -543,6 +645,7 @@ pub enum Declaration {
         name: Rc<str>,
         args: Vec<Spanned<Rc<str>>>,
         members: Vec<(String, Spanned<Type<Rc<str>>>)>,
+        defaults: Vec<InstanceMember>,
     },
     Instance {
         assumes: Vec<Spanned<Type<Rc<str>>>>,
@@ -555,6 +658,11 @@ pub enum Declaration {
         args: Vec<String>,
         body: Type<Rc<str>>,
     },
+    Data {
+        name: Rc<str>,
+        args: Vec<Spanned<Rc<str>>>,
+        constructors: Vec<DataConstructor>,
+    },
     Import {
         resolved: Option<ModuleId>,
         module: Spanned<String>,
@@ -565,6 +673,9 @@ pub enum Declaration {
         module: Spanned<String>,
         names: Names,
     },
+    Export {
+        names: Vec<Spanned<String>>,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -575,7 +686,15 @@ pub struct Module {
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub enum ModuleKey {
     Path(PathBuf),
+
+    /// A module that isn't defined in a file, such as the builtins.
     Name(String),
+
+    /// A module that was found in the module search path.
+    Library {
+        name: String,
+        path: PathBuf,
+    },
 }
 
 impl From<&Path> for ModuleKey {
@@ -10,6 +10,7 @@ pub enum Type<A> {
     Var(A),
     Bool,
     Int,
+    Float,
     Char,
     String,
     Bytes,
@@ -149,6 +150,7 @@ impl<A> Type<A> {
             Type::Var(x) => Type::Var(f(x)),
             Type::Bool => Type::Bool,
             Type::Int => Type::Int,
+            Type::Float => Type::Float,
             Type::Char => Type::Char,
             Type::String => Type::String,
             Type::Bytes => Type::Bytes,
@@ -181,6 +183,7 @@ impl<A> Type<A> {
             Type::Var(n) => f(n),
             Type::Bool => Type::Bool,
             Type::Int => Type::Int,
+            Type::Float => Type::Float,
             Type::Char => Type::Char,
             Type::String => Type::String,
             Type::Bytes => Type::Bytes,
@@ -431,6 +434,7 @@ impl<A> Type<A> {
             Type::Var(n) => s.push_str(format!("{}", n).as_str()),
             Type::Bool => s.push_str("Bool"),
             Type::Int => s.push_str("Int"),
+            Type::Float => s.push_str("Float"),
             Type::Char => s.push_str("Char"),
             Type::String => s.push_str("String"),
             Type::Bytes => s.push_str("Bytes"),
@@ -572,6 +576,7 @@ impl<'a, A> Iterator for TypeIterMetas<'a, A> {
                 Type::Var(_) => Step::Skip,
                 Type::Bool => Step::Skip,
                 Type::Int => Step::Skip,
+                Type::Float => Step::Skip,
                 Type::Char => Step::Skip,
                 Type::String => Step::Skip,
                 Type::Bytes => Step::Skip,
@@ -650,6 +655,7 @@ impl<'a, A> Iterator for IterVars<'a, A> {
                 Type::Var(n) => Step::Yield(n),
                 Type::Bool => Step::Skip,
                 Type::Int => Step::Skip,
+                Type::Float => Step::Skip,
                 Type::Char => Step::Skip,
                 Type::String => Step::Skip,
                 Type::Bytes => Step::Skip,
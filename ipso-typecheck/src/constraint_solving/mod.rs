@@ -3,7 +3,7 @@ mod test;
 
 use crate::{evidence::Constraint, fill_ty_names, metavariables, type_inference, BoundVars};
 use ipso_core::{
-    self as core, Binop, Branch, CommonKinds, Expr, Pattern, Placeholder, StringPart, Type,
+    self as core, Binop, Branch, Builtin, CommonKinds, Expr, Pattern, Placeholder, StringPart, Type,
 };
 use ipso_diagnostic::Source;
 use ipso_syntax::{self as syntax, kind::Kind};
@@ -510,7 +510,7 @@ pub fn solve_constraint(
                                 vec![Branch {
                                     // { <field_name> = <field_value>, ..rest } ->
                                     pattern: Pattern::Record {
-                                        names: vec![field_index.as_ref().clone()],
+                                        fields: vec![(field_index.as_ref().clone(), Pattern::Name)],
                                         rest: true,
                                     },
                                     // [ { field = <field_name>, value = <debug_dict>.debug <field_value> } ] ++
@@ -579,6 +579,268 @@ pub fn solve_constraint(
                 }
             }
         }
+        Constraint::ToJsonRecordFields(entire_row) => {
+            // toJsonRecordFields : { r } -> Array { key : String, value : Json }
+            match type_inference_state.zonk_type(entire_row.clone()) {
+                Type::RowCons(field_name, field_type, rest) => {
+                    // to_json_dict : { toJson : <field_type> -> Json }
+                    let to_json_dict = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::from_type(&Type::mk_app(
+                            &Type::Name(
+                                Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                                Rc::from("ToJson"),
+                            ),
+                            field_type.as_ref(),
+                        )),
+                    )?;
+
+                    // field_index : Int
+                    let field_index = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::HasField {
+                            field: field_name.clone(),
+                            rest: entire_row.clone(),
+                        },
+                    )?;
+
+                    // rest_evidence : { r } -> Array { key : String, value : Json }
+                    let rest_evidence = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::ToJsonRecordFields(rest.as_ref().clone()),
+                    )?;
+
+                    Ok(Rc::new(
+                        // \record ->
+                        Expr::mk_lam(
+                            true,
+                            // case record of
+                            Expr::mk_case(
+                                Expr::Var(0),
+                                vec![Branch {
+                                    // { <field_name> = <field_value>, ..rest } ->
+                                    pattern: Pattern::Record {
+                                        fields: vec![(field_index.as_ref().clone(), Pattern::Name)],
+                                        rest: true,
+                                    },
+                                    // [ { key = <field_name>, value = <to_json_dict>.toJson <field_value> } ] ++
+                                    // toJsonRecordFields rest
+                                    body: Expr::mk_binop(
+                                        Binop::Append,
+                                        Expr::Array(vec![Expr::mk_record(
+                                            vec![
+                                                (
+                                                    // key =
+                                                    Expr::Int(0),
+                                                    // <field_name>
+                                                    Expr::String(vec![StringPart::from(
+                                                        field_name.as_ref(),
+                                                    )]),
+                                                ),
+                                                (
+                                                    // value =
+                                                    Expr::Int(1),
+                                                    Expr::mk_app(
+                                                        // to_json_dict.toJson
+                                                        Expr::Project(
+                                                            to_json_dict,
+                                                            Rc::new(Expr::Int(0)),
+                                                        ),
+                                                        // <field_value>
+                                                        Expr::Var(1),
+                                                    ),
+                                                ),
+                                            ],
+                                            None,
+                                        )]),
+                                        // toJsonRecordFields rest
+                                        Expr::App(rest_evidence, Rc::new(Expr::Var(0))),
+                                    ),
+                                }],
+                            ),
+                        ),
+                    ))
+                }
+                Type::RowNil => Ok(Rc::new(
+                    // \_ -> []
+                    Expr::mk_lam(true, Expr::Array(vec![])),
+                )),
+                ty => {
+                    let constraint = Constraint::ToJsonRecordFields(ty);
+
+                    let evidence_result = type_inference_state
+                        .evidence
+                        .find(&type_inference_state.type_solutions, &constraint);
+                    match evidence_result {
+                        None => Err(Error::cannot_deduce(
+                            env.source.clone(),
+                            fill_ty_names(
+                                env.type_variables,
+                                type_inference_state
+                                    .zonk_type(constraint.to_type())
+                                    .to_syntax(),
+                            ),
+                        )
+                        .with_position(pos)),
+
+                        Some(evidence) => Ok(evidence),
+                    }
+                }
+            }
+        }
+        Constraint::FromJsonRecordFields(entire_row) => {
+            /*
+            fromJsonRecordFields :
+              Array { key : String, value : Json } ->
+              (| Err : String, Ok : { r } |)
+            */
+            match type_inference_state.zonk_type(entire_row.clone()) {
+                Type::RowCons(field_name, field_type, rest) => {
+                    // from_json_dict : { fromJson : Json -> (| Err : String, Ok : <field_type> |) }
+                    let from_json_dict = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::from_type(&Type::mk_app(
+                            &Type::Name(
+                                Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                                Rc::from("FromJson"),
+                            ),
+                            field_type.as_ref(),
+                        )),
+                    )?;
+
+                    /*
+                    field_index : Int
+
+                    The decoded field is used to extend a record of the rest of the row, so
+                    its index is computed the same way as for a record extension.
+                    */
+                    let field_index = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::HasField {
+                            field: field_name.clone(),
+                            rest: rest.as_ref().clone(),
+                        },
+                    )?;
+
+                    /*
+                    rest_evidence :
+                      Array { key : String, value : Json } ->
+                      (| Err : String, Ok : { r } |)
+                    */
+                    let rest_evidence = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::FromJsonRecordFields(rest.as_ref().clone()),
+                    )?;
+
+                    Ok(Rc::new(
+                        // \fields ->
+                        Expr::mk_lam(
+                            true,
+                            // case decodeJsonField "<field_name>" from_json_dict.fromJson fields of
+                            Expr::mk_case(
+                                Expr::mk_app(
+                                    Expr::mk_app(
+                                        Expr::mk_app(
+                                            Expr::Builtin(Builtin::JsonDecodeField),
+                                            Expr::String(vec![StringPart::from(
+                                                field_name.as_ref(),
+                                            )]),
+                                        ),
+                                        // from_json_dict.fromJson
+                                        Expr::Project(from_json_dict, Rc::new(Expr::Int(0))),
+                                    ),
+                                    Expr::Var(0),
+                                ),
+                                vec![
+                                    Branch {
+                                        // Ok value ->
+                                        pattern: Pattern::Variant {
+                                            tag: Rc::new(Expr::Int(1)),
+                                            arg: Rc::new(Pattern::Name),
+                                        },
+                                        // case fromJsonRecordFields fields of
+                                        body: Expr::mk_case(
+                                            Expr::App(rest_evidence, Rc::new(Expr::Var(1))),
+                                            vec![
+                                                Branch {
+                                                    // Ok record ->
+                                                    pattern: Pattern::Variant {
+                                                        tag: Rc::new(Expr::Int(1)),
+                                                        arg: Rc::new(Pattern::Name),
+                                                    },
+                                                    // Ok { <field_name> = value, ..record }
+                                                    body: Expr::mk_app(
+                                                        Expr::mk_variant(Expr::Int(1)),
+                                                        Expr::Extend(
+                                                            field_index,
+                                                            Rc::new(Expr::Var(1)),
+                                                            Rc::new(Expr::Var(0)),
+                                                        ),
+                                                    ),
+                                                },
+                                                Branch {
+                                                    // error -> error
+                                                    pattern: Pattern::Name,
+                                                    body: Expr::Var(0),
+                                                },
+                                            ],
+                                        ),
+                                    },
+                                    Branch {
+                                        // error -> error
+                                        pattern: Pattern::Name,
+                                        body: Expr::Var(0),
+                                    },
+                                ],
+                            ),
+                        ),
+                    ))
+                }
+                Type::RowNil => Ok(Rc::new(
+                    // \_ -> Ok {}
+                    Expr::mk_lam(
+                        true,
+                        Expr::mk_app(
+                            Expr::mk_variant(Expr::Int(1)),
+                            Expr::mk_record(vec![], None),
+                        ),
+                    ),
+                )),
+                ty => {
+                    let constraint = Constraint::FromJsonRecordFields(ty);
+
+                    let evidence_result = type_inference_state
+                        .evidence
+                        .find(&type_inference_state.type_solutions, &constraint);
+                    match evidence_result {
+                        None => Err(Error::cannot_deduce(
+                            env.source.clone(),
+                            fill_ty_names(
+                                env.type_variables,
+                                type_inference_state
+                                    .zonk_type(constraint.to_type())
+                                    .to_syntax(),
+                            ),
+                        )
+                        .with_position(pos)),
+
+                        Some(evidence) => Ok(evidence),
+                    }
+                }
+            }
+        }
         Constraint::DebugVariantCtor(row) => {
             fn go(
                 env: Env,
@@ -652,7 +914,10 @@ pub fn solve_constraint(
                           { ctor = "<field name>", value = debug_dict.debug value }
                         */
                         case_branches.push(Branch {
-                            pattern: Pattern::Variant { tag: field_offset },
+                            pattern: Pattern::Variant {
+                                tag: field_offset,
+                                arg: Rc::new(Pattern::Name),
+                            },
                             body: Expr::mk_record(
                                 vec![
                                     (
@@ -735,7 +1000,538 @@ pub fn solve_constraint(
                 )),
             )
         }
+        Constraint::EqRecordFields(entire_row) => {
+            // eqRecordFields : { r } -> { r } -> Bool
+            match type_inference_state.zonk_type(entire_row.clone()) {
+                Type::RowCons(field_name, field_type, rest) => {
+                    // eq_dict : { eq : <field_type> -> <field_type> -> Bool }
+                    let eq_dict = solve_class_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        "Eq",
+                        field_type.as_ref(),
+                    )?;
+
+                    // field_index : Int
+                    let field_index = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::HasField {
+                            field: field_name.clone(),
+                            rest: entire_row.clone(),
+                        },
+                    )?;
+
+                    // rest_evidence : { r } -> { r } -> Bool
+                    let rest_evidence = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::EqRecordFields(rest.as_ref().clone()),
+                    )?;
+
+                    Ok(Rc::new(
+                        // \left right ->
+                        Expr::mk_lam(
+                            true,
+                            Expr::mk_lam(
+                                true,
+                                // case left of
+                                Expr::mk_case(
+                                    Expr::Var(1),
+                                    vec![Branch {
+                                        // { <field_name> = left_value, ..left_rest } ->
+                                        pattern: Pattern::Record {
+                                            fields: vec![(
+                                                field_index.as_ref().clone(),
+                                                Pattern::Name,
+                                            )],
+                                            rest: true,
+                                        },
+                                        // case right of
+                                        body: Expr::mk_case(
+                                            Expr::Var(2),
+                                            vec![Branch {
+                                                // { <field_name> = right_value, ..right_rest } ->
+                                                pattern: Pattern::Record {
+                                                    fields: vec![(
+                                                        field_index.as_ref().clone(),
+                                                        Pattern::Name,
+                                                    )],
+                                                    rest: true,
+                                                },
+                                                /*
+                                                eq_dict.eq left_value right_value &&
+                                                eqRecordFields left_rest right_rest
+                                                */
+                                                body: Expr::mk_binop(
+                                                    Binop::And,
+                                                    Expr::mk_app(
+                                                        Expr::mk_app(
+                                                            // eq_dict.eq
+                                                            Expr::Project(
+                                                                eq_dict,
+                                                                Rc::new(Expr::Int(0)),
+                                                            ),
+                                                            Expr::Var(3),
+                                                        ),
+                                                        Expr::Var(1),
+                                                    ),
+                                                    Expr::mk_app(
+                                                        Expr::App(
+                                                            rest_evidence,
+                                                            Rc::new(Expr::Var(2)),
+                                                        ),
+                                                        Expr::Var(0),
+                                                    ),
+                                                ),
+                                            }],
+                                        ),
+                                    }],
+                                ),
+                            ),
+                        ),
+                    ))
+                }
+                Type::RowNil => Ok(Rc::new(
+                    // \_ _ -> true
+                    Expr::mk_lam(true, Expr::mk_lam(true, Expr::True)),
+                )),
+                ty => find_row_evidence(
+                    env,
+                    type_inference_state,
+                    pos,
+                    &Constraint::EqRecordFields(ty),
+                ),
+            }
+        }
+        Constraint::OrdRecordFields(entire_row) => {
+            /*
+            ordRecordFields :
+              { r } ->
+              { r } ->
+              (| Equal : (), Greater : (), Less : () |)
+
+            Records are compared lexicographically, in order of field name.
+            */
+            match type_inference_state.zonk_type(entire_row.clone()) {
+                zonked_row @ Type::RowCons(_, _, _) => {
+                    let row_parts = zonked_row.unwrap_rows();
+                    let mut fields = row_parts.fields;
+                    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    let (field_name, field_type) = fields.remove(0);
+                    let rest = Type::mk_rows(
+                        fields
+                            .into_iter()
+                            .map(|(field, ty)| (field.clone(), ty.clone()))
+                            .collect(),
+                        row_parts.rest.cloned(),
+                    );
+
+                    // ord_dict : { eqDict : ..., compare : <field_type> -> <field_type> -> Ordering }
+                    let ord_dict =
+                        solve_class_constraint(env, type_inference_state, pos, "Ord", field_type)?;
+
+                    // field_index : Int
+                    let field_index = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::HasField {
+                            field: field_name.clone(),
+                            rest: entire_row.clone(),
+                        },
+                    )?;
+
+                    // rest_evidence : { r } -> { r } -> Ordering
+                    let rest_evidence = solve_constraint(
+                        env,
+                        type_inference_state,
+                        pos,
+                        &Constraint::OrdRecordFields(rest),
+                    )?;
+
+                    Ok(Rc::new(
+                        // \left right ->
+                        Expr::mk_lam(
+                            true,
+                            Expr::mk_lam(
+                                true,
+                                // case left of
+                                Expr::mk_case(
+                                    Expr::Var(1),
+                                    vec![Branch {
+                                        // { <field_name> = left_value, ..left_rest } ->
+                                        pattern: Pattern::Record {
+                                            fields: vec![(
+                                                field_index.as_ref().clone(),
+                                                Pattern::Name,
+                                            )],
+                                            rest: true,
+                                        },
+                                        // case right of
+                                        body: Expr::mk_case(
+                                            Expr::Var(2),
+                                            vec![Branch {
+                                                // { <field_name> = right_value, ..right_rest } ->
+                                                pattern: Pattern::Record {
+                                                    fields: vec![(
+                                                        field_index.as_ref().clone(),
+                                                        Pattern::Name,
+                                                    )],
+                                                    rest: true,
+                                                },
+                                                // let ordering = ord_dict.compare left_value right_value in
+                                                body: Expr::mk_let(
+                                                    Expr::mk_app(
+                                                        Expr::mk_app(
+                                                            // ord_dict.compare
+                                                            Expr::Project(
+                                                                ord_dict,
+                                                                Rc::new(Expr::Int(1)),
+                                                            ),
+                                                            Expr::Var(3),
+                                                        ),
+                                                        Expr::Var(1),
+                                                    ),
+                                                    // case ordering of
+                                                    Expr::mk_case(
+                                                        Expr::Var(0),
+                                                        vec![
+                                                            Branch {
+                                                                // Equal _ ->
+                                                                pattern: Pattern::mk_variant(
+                                                                    Expr::Int(ORDERING_EQUAL),
+                                                                    Pattern::Wildcard,
+                                                                ),
+                                                                // ordRecordFields left_rest right_rest
+                                                                body: Expr::mk_app(
+                                                                    Expr::App(
+                                                                        rest_evidence,
+                                                                        Rc::new(Expr::Var(3)),
+                                                                    ),
+                                                                    Expr::Var(1),
+                                                                ),
+                                                            },
+                                                            /*
+                                                            _ -> ordering
+
+                                                            `ordering` is returned instead of a name pattern's
+                                                            binding, because the binding's constructor has been
+                                                            peeled.
+                                                            */
+                                                            Branch {
+                                                                pattern: Pattern::Wildcard,
+                                                                body: Expr::Var(0),
+                                                            },
+                                                        ],
+                                                    ),
+                                                ),
+                                            }],
+                                        ),
+                                    }],
+                                ),
+                            ),
+                        ),
+                    ))
+                }
+                Type::RowNil => Ok(Rc::new(
+                    // \_ _ -> Equal ()
+                    Expr::mk_lam(true, Expr::mk_lam(true, mk_ordering(ORDERING_EQUAL))),
+                )),
+                ty => find_row_evidence(
+                    env,
+                    type_inference_state,
+                    pos,
+                    &Constraint::OrdRecordFields(ty),
+                ),
+            }
+        }
+        Constraint::EqVariantCtor(row) => {
+            // eqVariantCtor : (| r |) -> (| r |) -> Bool
+            solve_variant_comparison(
+                env,
+                type_inference_state,
+                pos,
+                row,
+                VariantComparison {
+                    class_name: "Eq",
+                    member_index: 0,
+                    before: Expr::False,
+                    after: Expr::False,
+                    tail_constraint: Constraint::EqVariantCtor,
+                },
+            )
+        }
+        Constraint::OrdVariantCtor(row) => {
+            /*
+            ordVariantCtor :
+              (| r |) ->
+              (| r |) ->
+              (| Equal : (), Greater : (), Less : () |)
+            */
+            solve_variant_comparison(
+                env,
+                type_inference_state,
+                pos,
+                row,
+                VariantComparison {
+                    class_name: "Ord",
+                    member_index: 1,
+                    before: mk_ordering(ORDERING_GREATER),
+                    after: mk_ordering(ORDERING_LESS),
+                    tail_constraint: Constraint::OrdVariantCtor,
+                },
+            )
+        }
+    }
+}
+
+// The tags of `(| Equal : (), Greater : (), Less : () |)`.
+const ORDERING_EQUAL: i64 = 0;
+const ORDERING_GREATER: i64 = 1;
+const ORDERING_LESS: i64 = 2;
+
+fn mk_ordering(tag: i64) -> Expr {
+    Expr::mk_app(Expr::mk_variant(Expr::Int(tag)), Expr::Unit)
+}
+
+/// Solve `<class_name> <ty>` for a single-parameter builtin class.
+fn solve_class_constraint(
+    env: Env,
+    type_inference_state: &mut type_inference::State,
+    pos: usize,
+    class_name: &str,
+    ty: &Type,
+) -> Result<Rc<core::Expr>, Error> {
+    solve_constraint(
+        env,
+        type_inference_state,
+        pos,
+        &Constraint::from_type(&Type::mk_app(
+            &Type::Name(
+                Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+                Rc::from(class_name),
+            ),
+            ty,
+        )),
+    )
+}
+
+/// Find evidence for a row constraint whose row can't be decomposed any further.
+fn find_row_evidence(
+    env: Env,
+    type_inference_state: &mut type_inference::State,
+    pos: usize,
+    constraint: &Constraint,
+) -> Result<Rc<core::Expr>, Error> {
+    match type_inference_state
+        .evidence
+        .find(&type_inference_state.type_solutions, constraint)
+    {
+        None => Err(Error::cannot_deduce(
+            env.source.clone(),
+            fill_ty_names(
+                env.type_variables,
+                type_inference_state
+                    .zonk_type(constraint.to_type())
+                    .to_syntax(),
+            ),
+        )
+        .with_position(pos)),
+        Some(evidence) => Ok(evidence),
+    }
+}
+
+/// How [`solve_variant_comparison`] compares two variants.
+struct VariantComparison {
+    /// The class used to compare constructor arguments.
+    class_name: &'static str,
+    /// The index of the comparison function in the class dictionary.
+    member_index: i64,
+    /// The result when the left constructor comes after the right constructor.
+    before: Expr,
+    /// The result when the left constructor comes before the right constructor.
+    after: Expr,
+    /// The constraint that compares variants of an unknown row.
+    tail_constraint: fn(Type) -> Constraint,
+}
+
+/**
+Construct a function of type `(| r |) -> (| r |) -> a` that compares two variants.
+
+Constructors are ordered by name. Variants with the same constructor are compared by their
+arguments, using the class named by [`VariantComparison::class_name`]. When the row ends in a
+type variable, the row's known constructors come before the variable's constructors.
+*/
+fn solve_variant_comparison(
+    env: Env,
+    type_inference_state: &mut type_inference::State,
+    pos: usize,
+    row: &Type,
+    comparison: VariantComparison,
+) -> Result<Rc<core::Expr>, Error> {
+    let zonked_row = type_inference_state.zonk_type(row.clone());
+    let row_parts = zonked_row.unwrap_rows();
+    let mut fields = row_parts.fields;
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let tail = match row_parts.rest {
+        None => None,
+        /*
+        If this meta is not mentioned in the type of the expression that generated
+        it, then it's ambiguous and we can default it to the empty row.
+        */
+        Some(tail @ Type::Meta(kind, _)) => {
+            debug_assert!(kind == &Kind::Row);
+
+            type_inference::unification::unify(
+                type_inference::unification::Env {
+                    common_kinds: env.common_kinds,
+                    types: env.types,
+                    type_variables: env.type_variables,
+                },
+                &mut type_inference_state.kind_inference_state,
+                &mut type_inference_state.type_solutions,
+                pos,
+                &core::Type::RowNil,
+                tail,
+            )
+            .map_err(|error| Error::unification_error(env.source.clone(), error))?;
+
+            None
+        }
+        Some(tail) => Some(tail),
+    };
+
+    /*
+    The constructors are matched in order, so each constructor's tag is its position in
+    the rest of the row.
+
+    See [note: peeling constructors when matching on variants].
+    */
+    let mut ctors = Vec::with_capacity(fields.len());
+    for (index, (field_name, field_type)) in fields.iter().enumerate() {
+        let dict = solve_class_constraint(
+            env,
+            type_inference_state,
+            pos,
+            comparison.class_name,
+            field_type,
+        )?;
+        let tag = solve_constraint(
+            env,
+            type_inference_state,
+            pos,
+            &Constraint::HasField {
+                field: (*field_name).clone(),
+                rest: Type::mk_rows(
+                    fields[index + 1..]
+                        .iter()
+                        .map(|(field, ty)| ((*field).clone(), (*ty).clone()))
+                        .collect(),
+                    tail.cloned(),
+                ),
+            },
+        )?;
+        ctors.push((tag, dict));
     }
+
+    // <ctor> _ -> <result>
+    let skip_ctor = |tag: &Rc<Expr>, result: &Expr| Branch {
+        pattern: Pattern::Variant {
+            tag: tag.clone(),
+            arg: Rc::new(Pattern::Wildcard),
+        },
+        body: result.clone(),
+    };
+
+    let mut left_branches: Vec<Branch<Expr>> = ctors
+        .iter()
+        .enumerate()
+        .map(|(index, (tag, dict))| {
+            /*
+            <ctor> left_value ->
+              case right of
+                <earlier ctor> _ -> <before>
+                ...
+                <ctor> right_value -> dict.<member> left_value right_value
+                _ -> <after>
+            */
+            let mut right_branches: Vec<Branch<Expr>> = ctors[..index]
+                .iter()
+                .map(|(tag, _)| skip_ctor(tag, &comparison.before))
+                .collect();
+            right_branches.push(Branch {
+                pattern: Pattern::Variant {
+                    tag: tag.clone(),
+                    arg: Rc::new(Pattern::Name),
+                },
+                body: Expr::mk_app(
+                    Expr::mk_app(
+                        Expr::Project(dict.clone(), Rc::new(Expr::Int(comparison.member_index))),
+                        Expr::Var(1),
+                    ),
+                    Expr::Var(0),
+                ),
+            });
+            right_branches.push(Branch {
+                pattern: Pattern::Wildcard,
+                body: comparison.after.clone(),
+            });
+
+            Branch {
+                pattern: Pattern::Variant {
+                    tag: tag.clone(),
+                    arg: Rc::new(Pattern::Name),
+                },
+                body: Expr::mk_case(Expr::Var(1), right_branches),
+            }
+        })
+        .collect();
+
+    if let Some(tail) = tail {
+        // tail_evidence : (| r |) -> (| r |) -> a
+        let tail_evidence = find_row_evidence(
+            env,
+            type_inference_state,
+            pos,
+            &(comparison.tail_constraint)(tail.clone()),
+        )?;
+
+        /*
+        left_rest ->
+          case right of
+            <ctor> _ -> <before>
+            ...
+            right_rest -> tail_evidence left_rest right_rest
+        */
+        let mut right_branches: Vec<Branch<Expr>> = ctors
+            .iter()
+            .map(|(tag, _)| skip_ctor(tag, &comparison.before))
+            .collect();
+        right_branches.push(Branch {
+            pattern: Pattern::Name,
+            body: Expr::mk_app(
+                Expr::App(tail_evidence, Rc::new(Expr::Var(1))),
+                Expr::Var(0),
+            ),
+        });
+        left_branches.push(Branch {
+            pattern: Pattern::Name,
+            body: Expr::mk_case(Expr::Var(1), right_branches),
+        });
+    }
+
+    Ok(Rc::new(
+        // \left right -> case left of <left_branches>
+        Expr::mk_lam(
+            true,
+            Expr::mk_lam(true, Expr::mk_case(Expr::Var(1), left_branches)),
+        ),
+    ))
 }
 
 pub fn solve_placeholder(
@@ -158,6 +158,7 @@ fn solve_constraint_4() {
             &mut implications,
             &mut context,
             &mut class_context,
+            None,
             &ClassDeclaration {
                 supers: Vec::new(),
                 name: Rc::from("MyEq"),
@@ -174,6 +175,7 @@ fn solve_constraint_4() {
                             ),
                         }
                     },
+                    default: None,
                 }],
             },
         );
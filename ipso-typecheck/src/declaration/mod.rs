@@ -3,7 +3,7 @@
 use crate::{
     check_kind,
     constraint_solving::{self, solve_constraint, solve_placeholder},
-    evidence, fill_ty_names, generalise, infer_kind, kind_inference,
+    evidence, fill_ty_names, generalise, infer_kind, kind_inference, module,
     type_inference::{self, infer_pattern},
     BoundVars, Error, Implication,
 };
@@ -13,7 +13,6 @@ use ipso_syntax::{self as syntax, kind::Kind, ModuleId, Modules, Spanned};
 use std::{
     collections::{HashMap, HashSet},
     rc::Rc,
-    todo,
 };
 
 /// Checked declarations.
@@ -27,8 +26,29 @@ pub enum Checked {
     ResolvedImport {
         module_id: ModuleId,
         module: core::Module,
+
+        /// The data types that the import brings into scope.
+        data_decls: Vec<core::DataDeclaration>,
+
+        /**
+        The constructors that the import brings into scope without their data type, along with
+        the data type that declares them.
+
+        e.g. `from x import Some`
+        */
+        constructors: Vec<(Rc<str>, core::DataDeclaration)>,
+
+        /// The builtin types that the import brings into scope.
+        builtin_types: Vec<(String, Kind)>,
+
+        /// The classes that the import brings into scope.
+        class_decls: Vec<core::ClassDeclaration>,
     },
     Class(core::ClassDeclaration),
+    Data(core::DataDeclaration),
+    Export {
+        names: Vec<Spanned<String>>,
+    },
     Instance {
         evidence_name: Rc<str>,
         evidence_body: Rc<core::Expr>,
@@ -44,8 +64,10 @@ pub struct Env<'a> {
     pub common_kinds: &'a CommonKinds,
     pub modules: &'a Modules<core::Module>,
     pub module_context: &'a HashMap<ModuleId, HashMap<String, core::Signature>>,
+    pub module_unexported: &'a HashMap<ModuleId, HashSet<String>>,
     pub type_context: &'a HashMap<Rc<str>, Kind>,
     pub class_context: &'a HashMap<Rc<str>, core::ClassDeclaration>,
+    pub constructor_context: &'a HashMap<Rc<str>, core::DataDeclaration>,
     pub context: &'a HashMap<String, core::Signature>,
     pub implications: &'a [Implication],
     pub source: &'a Source,
@@ -59,12 +81,17 @@ pub fn check(env: Env, decl: &syntax::Spanned<syntax::Declaration>) -> Result<Ch
             args,
             body,
         } => check_definition(env, name, ty, args, body),
-        syntax::Declaration::TypeAlias { name, args, body } => {
-            todo!("check type alias {:?}", (name, args, body))
-        }
+        syntax::Declaration::TypeAlias { .. } => Err(Error::TypeAliasNotSupported {
+            source: env.source.clone(),
+            pos: decl.pos,
+        }),
+        syntax::Declaration::Data {
+            name,
+            args,
+            constructors,
+        } => check_data(env, name, args, constructors),
 
-        syntax::Declaration::Import { resolved, .. }
-        | syntax::Declaration::FromImport { resolved, .. } => {
+        syntax::Declaration::Import { resolved, .. } => {
             let module_id = resolved.unwrap_or_else(|| panic!("unresolved import"));
 
             let module = env.modules.lookup(module_id);
@@ -72,15 +99,118 @@ pub fn check(env: Env, decl: &syntax::Spanned<syntax::Declaration>) -> Result<Ch
             Ok(Checked::ResolvedImport {
                 module_id,
                 module: module.clone(),
+                data_decls: Vec::new(),
+                constructors: Vec::new(),
+                builtin_types: Vec::new(),
+                class_decls: Vec::new(),
             })
         }
+        syntax::Declaration::FromImport {
+            resolved, names, ..
+        } => {
+            let module_id = resolved.unwrap_or_else(|| panic!("unresolved import"));
+
+            let module = env.modules.lookup(module_id);
+
+            let data_decls = module
+                .decls
+                .iter()
+                .filter(|decl| module.exports(decl))
+                .filter_map(|decl| match decl {
+                    core::Declaration::Data(data_decl) => match names {
+                        syntax::Names::All => Some(data_decl.clone()),
+                        syntax::Names::Names(names) => names
+                            .iter()
+                            .any(|name| name.item == data_decl.name.as_ref())
+                            .then(|| data_decl.clone()),
+                    },
+                    _ => None,
+                })
+                .collect();
+
+            let constructors = match names {
+                syntax::Names::All => Vec::new(),
+                syntax::Names::Names(names) => module
+                    .decls
+                    .iter()
+                    .filter(|decl| module.exports(decl))
+                    .filter_map(|decl| match decl {
+                        core::Declaration::Data(data_decl)
+                            if !names
+                                .iter()
+                                .any(|name| name.item == data_decl.name.as_ref()) =>
+                        {
+                            Some(data_decl)
+                        }
+                        _ => None,
+                    })
+                    .flat_map(|data_decl| {
+                        data_decl
+                            .constructors
+                            .iter()
+                            .filter(|constructor| {
+                                names
+                                    .iter()
+                                    .any(|name| name.item == constructor.name.as_ref())
+                            })
+                            .map(move |constructor| (constructor.name.clone(), data_decl.clone()))
+                    })
+                    .collect(),
+            };
+
+            let builtin_types = module
+                .decls
+                .iter()
+                .filter(|decl| module.exports(decl))
+                .filter_map(|decl| match decl {
+                    core::Declaration::BuiltinType { name, kind } => match names {
+                        syntax::Names::All => Some((name.clone(), kind.clone())),
+                        syntax::Names::Names(names) => names
+                            .iter()
+                            .any(|imported_name| &imported_name.item == name)
+                            .then(|| (name.clone(), kind.clone())),
+                    },
+                    _ => None,
+                })
+                .collect();
+
+            let class_decls = module
+                .decls
+                .iter()
+                .filter(|decl| module.exports(decl))
+                .filter_map(|decl| match decl {
+                    core::Declaration::Class(class_decl) => match names {
+                        syntax::Names::All => Some(class_decl.clone()),
+                        syntax::Names::Names(names) => names
+                            .iter()
+                            .any(|name| name.item == class_decl.name.as_ref())
+                            .then(|| class_decl.clone()),
+                    },
+                    _ => None,
+                })
+                .collect();
+
+            Ok(Checked::ResolvedImport {
+                module_id,
+                module: module.clone(),
+                data_decls,
+                constructors,
+                builtin_types,
+                class_decls,
+            })
+        }
+
+        syntax::Declaration::Export { names } => Ok(Checked::Export {
+            names: names.clone(),
+        }),
 
         syntax::Declaration::Class {
             supers,
             name,
             args,
             members,
-        } => check_class(env, supers, name, args, members),
+            defaults,
+        } => check_class(env, supers, name, args, members, defaults),
         syntax::Declaration::Instance {
             assumes,
             name,
@@ -158,16 +288,18 @@ pub fn check_definition(
                 type_inference::Env {
                     common_kinds: env.common_kinds,
                     modules: env.module_context,
+                    unexported: env.module_unexported,
                     types: env.type_context,
                     type_variables: &type_variables,
                     type_signatures: &type_signatures,
+                    constructors: env.constructor_context,
                     source: env.source,
                 },
                 &mut type_inference_state,
                 arg,
             )
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
     let out_ty = type_inference_state.fresh_type_meta(Kind::Type);
 
     type_inference::unification::unify(
@@ -188,16 +320,18 @@ pub fn check_definition(
 
     let arg_bound_vars = arg_tys
         .iter()
-        .flat_map(|arg_ty| arg_ty.names().into_iter())
+        .flat_map(|arg_ty| arg_ty.arg_names(env.common_kinds).into_iter())
         .collect::<Vec<_>>();
     let body = type_inference_state.with_bound_vars(&arg_bound_vars, |type_inference_state| {
         type_inference::check(
             type_inference::Env {
                 common_kinds: env.common_kinds,
                 modules: env.module_context,
+                unexported: env.module_unexported,
                 types: env.type_context,
                 type_variables: &type_variables,
                 type_signatures: &type_signatures,
+                constructors: env.constructor_context,
                 source: env.source,
             },
             type_inference_state,
@@ -206,21 +340,10 @@ pub fn check_definition(
         )
     })?;
 
-    let body = arg_tys.into_iter().rev().fold(body, |body, arg_ty| {
-        let pattern = arg_ty.pattern();
-        match &pattern {
-            core::Pattern::Char(_)
-            | core::Pattern::Int(_)
-            | core::Pattern::String(_)
-            | core::Pattern::Record { .. }
-            | core::Pattern::Variant { .. } => core::Expr::mk_lam(
-                true,
-                core::Expr::mk_case(core::Expr::Var(0), vec![core::Branch { pattern, body }]),
-            ),
-            core::Pattern::Name => core::Expr::mk_lam(true, body),
-            core::Pattern::Wildcard => core::Expr::mk_lam(false, body),
-        }
-    });
+    let body = arg_tys
+        .into_iter()
+        .rev()
+        .fold(body, |body, arg_ty| arg_ty.mk_lam(body));
 
     let (body, sig) = generalise(
         env.common_kinds,
@@ -243,6 +366,139 @@ pub fn check_definition(
     })
 }
 
+pub fn check_data(
+    env: Env,
+    name: &Rc<str>,
+    args: &[Spanned<Rc<str>>],
+    constructors: &[syntax::DataConstructor],
+) -> Result<Checked, Error> {
+    /*
+    The data type's kind is inferred from its constructors' fields, which may refer to the
+    data type itself. Once the kind is known, the fields are checked again so that they
+    don't contain any kind metavariables.
+    */
+    fn check_constructors(
+        env: &Env,
+        type_context: &HashMap<Rc<str>, Kind>,
+        type_variables: &BoundVars<Kind>,
+        kind_inference_state: &mut kind_inference::State,
+        constructors: &[syntax::DataConstructor],
+    ) -> Result<Vec<core::DataConstructor>, Error> {
+        constructors
+            .iter()
+            .map(|constructor| {
+                let fields = constructor
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        check_kind(
+                            env.common_kinds,
+                            type_context,
+                            type_variables,
+                            kind_inference_state,
+                            env.source,
+                            &Spanned {
+                                pos: constructor.name.pos,
+                                item: field.clone(),
+                            },
+                            &Kind::Type,
+                        )
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(core::DataConstructor {
+                    name: constructor.name.item.clone(),
+                    fields,
+                })
+            })
+            .collect()
+    }
+
+    let mut type_variables = BoundVars::new();
+    let mut kind_inference_state = kind_inference::State::new();
+
+    let args_kinds: Vec<(Rc<str>, Kind)> = {
+        let mut seen_names: HashSet<&str> = HashSet::new();
+        args.iter()
+            .map(|arg| {
+                if seen_names.insert(arg.item.as_ref()) {
+                    Ok((arg.item.clone(), kind_inference_state.fresh_meta()))
+                } else {
+                    Err(Error::DuplicateTypeArgument {
+                        source: env.source.clone(),
+                        pos: arg.pos,
+                    })
+                }
+            })
+            .collect::<Result<_, _>>()
+    }?;
+
+    {
+        // Constructors are also unique among the data types that are already in scope.
+        let mut seen_names: HashSet<&str> = HashSet::new();
+        constructors.iter().try_for_each(|constructor| {
+            if seen_names.insert(constructor.name.item.as_ref())
+                && !env.constructor_context.contains_key(&constructor.name.item)
+            {
+                Ok(())
+            } else {
+                Err(Error::DuplicateConstructor {
+                    source: env.source.clone(),
+                    pos: constructor.name.pos,
+                })
+            }
+        })
+    }?;
+
+    let mut type_context = env.type_context.clone();
+    let mut data_decl = core::DataDeclaration {
+        name: name.clone(),
+        args: args_kinds,
+        constructors: Vec::new(),
+    };
+
+    type_context.insert(name.clone(), data_decl.kind());
+    type_variables.insert(&data_decl.args);
+    check_constructors(
+        &env,
+        &type_context,
+        &type_variables,
+        &mut kind_inference_state,
+        constructors,
+    )?;
+    type_variables.delete(data_decl.args.len());
+
+    data_decl.args = data_decl
+        .args
+        .into_iter()
+        .map(|(name, kind)| (name, kind_inference_state.zonk(true, kind)))
+        .collect();
+
+    type_context.insert(name.clone(), data_decl.kind());
+    type_variables.insert(&data_decl.args);
+    let mut kind_inference_state = kind_inference::State::new();
+    let type_solutions = type_inference::unification::Solutions::new();
+    data_decl.constructors = check_constructors(
+        &env,
+        &type_context,
+        &type_variables,
+        &mut kind_inference_state,
+        constructors,
+    )?
+    .into_iter()
+    .map(|constructor| core::DataConstructor {
+        name: constructor.name,
+        fields: constructor
+            .fields
+            .into_iter()
+            .map(|field| type_solutions.zonk(&kind_inference_state.kind_solutions, field))
+            .collect(),
+    })
+    .collect();
+    type_variables.delete(data_decl.args.len());
+
+    Ok(Checked::Data(data_decl))
+}
+
 pub fn check_class_member(
     env: &Env,
     bound_tyvars: &BoundVars<Kind>,
@@ -294,15 +550,130 @@ pub fn check_class_member(
     Ok(core::ClassMember {
         name: name.to_string(),
         sig,
+        default: None,
     })
 }
 
+/**
+Check a class member's default implementation.
+
+A default is checked against the member's full signature, with the class constraint assumed. The
+class dictionary is then replaced by a delayed dictionary (see [`core::ClassMember::default`]).
+*/
+pub fn check_class_default(
+    env: &Env,
+    class_decl: &core::ClassDeclaration,
+    default: &syntax::InstanceMember,
+) -> Result<Rc<core::Expr>, Error> {
+    let class_member = match class_decl
+        .members
+        .iter()
+        .find(|class_member| class_member.name == default.name.item)
+    {
+        None => Err(Error::NotAMember {
+            source: env.source.clone(),
+            pos: default.name.pos,
+            cls: class_decl.name.clone(),
+        }),
+        Some(class_member) => Ok(class_member),
+    }?;
+
+    // The default may use the class's members, so the class needs to be in scope.
+    let mut type_context = env.type_context.clone();
+    let mut implications = env.implications.to_vec();
+    let mut context = env.context.clone();
+    let mut class_context = env.class_context.clone();
+    module::register_class(
+        env.common_kinds,
+        &mut type_context,
+        &mut implications,
+        &mut context,
+        &mut class_context,
+        None,
+        class_decl,
+    );
+
+    let mut type_variables = BoundVars::new();
+    let mut type_inference_state = type_inference::State::new();
+
+    type_variables.insert(&class_decl.args);
+    type_variables.insert(&class_member.sig.ty_vars);
+
+    let class_evar = {
+        let bindings = class_decl.get_bindings(env.common_kinds);
+        let (sig, _) = &bindings[&class_member.name];
+        let (constraint, _) = sig.body.unwrap_fatarrow().unwrap();
+        type_inference_state.evidence.assume(
+            default.name.pos,
+            evidence::Constraint::from_type(constraint),
+        )
+    };
+
+    let expr = Spanned {
+        pos: default.name.pos,
+        item: syntax::Expr::mk_lam(default.args.clone(), default.body.clone()),
+    };
+    let body = type_inference::check(
+        type_inference::Env {
+            common_kinds: env.common_kinds,
+            modules: env.module_context,
+            unexported: env.module_unexported,
+            types: &type_context,
+            type_variables: &type_variables,
+            type_signatures: &context,
+            constructors: env.constructor_context,
+            source: env.source,
+        },
+        &mut type_inference_state,
+        &expr,
+        &class_member.sig.body,
+    )?;
+    let (mut body, _) = generalise(
+        env.common_kinds,
+        &implications,
+        &type_context,
+        &type_variables,
+        &mut type_inference_state,
+        env.source,
+        class_member.sig.ty_vars.len(),
+        body,
+        class_member.sig.body.clone(),
+    )?;
+    type_variables.delete(class_member.sig.ty_vars.len());
+
+    body.subst_placeholder(&mut |p| -> Result<_, Error> {
+        let (expr, _solved_constraint) = solve_placeholder(
+            constraint_solving::Env {
+                common_kinds: env.common_kinds,
+                types: &type_context,
+                implications: &implications,
+                type_variables: &type_variables,
+                source: env.source,
+            },
+            &mut type_inference_state,
+            *p,
+        )?;
+
+        Ok(expr.as_ref().clone())
+    })?;
+
+    // \dict -> body  ~>  \getDict -> body[dict := getDict ()]
+    Ok(Rc::new(core::Expr::mk_lam(
+        true,
+        core::Expr::mk_app(
+            body.abstract_evar(class_evar),
+            core::Expr::mk_app(core::Expr::Var(0), core::Expr::Unit),
+        ),
+    )))
+}
+
 pub fn check_class(
     env: Env,
     supers: &[Spanned<syntax::Type<Rc<str>>>],
     name: &Rc<str>,
     args: &[Spanned<Rc<str>>],
     members: &[(String, Spanned<syntax::Type<Rc<str>>>)],
+    defaults: &[syntax::InstanceMember],
 ) -> Result<Checked, Error> {
     let mut type_variables = BoundVars::new();
     let mut type_solutions = type_inference::unification::Solutions::new();
@@ -359,7 +730,7 @@ pub fn check_class(
 
     type_variables.delete(args_kinds.len());
 
-    Ok(Checked::Class(core::ClassDeclaration {
+    let mut class_decl = core::ClassDeclaration {
         supers,
         name: name.clone(),
         args: args_kinds
@@ -367,7 +738,40 @@ pub fn check_class(
             .map(|(name, kind)| (name, kind_inference_state.zonk(true, kind)))
             .collect::<Vec<(Rc<str>, Kind)>>(),
         members,
-    }))
+    };
+
+    {
+        let mut seen_names: HashSet<&str> = HashSet::new();
+        defaults.iter().try_for_each(|default| {
+            if seen_names.insert(default.name.item.as_str()) {
+                Ok(())
+            } else {
+                Err(Error::DuplicateDefault {
+                    source: env.source.clone(),
+                    pos: default.name.pos,
+                })
+            }
+        })
+    }?;
+
+    let defaults = defaults
+        .iter()
+        .map(|default| {
+            check_class_default(&env, &class_decl, default)
+                .map(|body| (default.name.item.as_str(), body))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for (name, body) in defaults {
+        if let Some(class_member) = class_decl
+            .members
+            .iter_mut()
+            .find(|class_member| class_member.name == name)
+        {
+            class_member.default = Some(body);
+        }
+    }
+
+    Ok(Checked::Class(class_decl))
 }
 
 pub fn check_instance(
@@ -513,12 +917,34 @@ pub fn check_instance(
         superclass_constructors
     };
 
+    /*
+    A class member's signature binds its own type variables inside the class arguments, so the
+    class arguments are the variables whose indices come after the member's. The instance's
+    arguments are moved under the member's type variables.
+    */
     let instantiated_class_members: Vec<core::ClassMember> = class_decl
         .members
         .iter()
-        .map(|class_member| core::ClassMember {
-            name: class_member.name.clone(),
-            sig: class_member.sig.clone().instantiate_many(&args),
+        .map(|class_member| {
+            let offset = class_member.sig.ty_vars.len();
+            let body = class_member.sig.body.subst(&|&index| {
+                if index < offset {
+                    core::Type::Var(
+                        class_member.sig.ty_vars[offset - 1 - index].1.clone(),
+                        index,
+                    )
+                } else {
+                    args[args.len() - 1 - (index - offset)].subst(&|&arg_index| {
+                        let (_, kind) = type_variables.lookup_index(arg_index).unwrap();
+                        core::Type::Var(kind.clone(), arg_index + offset)
+                    })
+                }
+            });
+            core::ClassMember {
+                name: class_member.name.clone(),
+                sig: core::TypeSig::new(class_member.sig.ty_vars.clone(), body),
+                default: class_member.default.clone(),
+            }
         })
         .collect();
 
@@ -536,20 +962,34 @@ pub fn check_instance(
     )?;
 
     // type check members
-    let mut checked_members = Vec::with_capacity(members.len());
-    for member in members {
-        match instantiated_class_members
+    if let Some(member) = members.iter().find(|member| {
+        !instantiated_class_members
+            .iter()
+            .any(|class_member| class_member.name == member.name.item)
+    }) {
+        return Err(Error::NotAMember {
+            source: env.source.clone(),
+            pos: member.name.pos,
+            cls: name.item.clone(),
+        });
+    }
+
+    /*
+    The dictionary's members are in the same order as the class's members. A member that the
+    instance doesn't define uses the class's default, which is passed the instance's own
+    dictionary.
+
+    Members that have a default are delayed (see `core::ClassMember::default`), because
+    building the dictionary would otherwise run the default, which would build the dictionary
+    again.
+    */
+    let mut checked_members = Vec::with_capacity(instantiated_class_members.len());
+    for member_type in &instantiated_class_members {
+        match members
             .iter()
-            .find(|class_member| class_member.name == member.name.item)
+            .find(|member| member.name.item == member_type.name)
         {
-            None => {
-                return Err(Error::NotAMember {
-                    source: env.source.clone(),
-                    pos: member.name.pos,
-                    cls: name.item.clone(),
-                })
-            }
-            Some(member_type) => {
+            Some(member) => {
                 type_variables.insert(&member_type.sig.ty_vars);
 
                 match {
@@ -563,9 +1003,11 @@ pub fn check_instance(
                         type_inference::Env {
                             common_kinds: env.common_kinds,
                             modules: env.module_context,
+                            unexported: env.module_unexported,
                             types: env.type_context,
                             type_variables: &type_variables,
                             type_signatures: env.context,
+                            constructors: env.constructor_context,
                             source: env.source,
                         },
                         &mut type_inference_state,
@@ -588,10 +1030,37 @@ pub fn check_instance(
                     Err(err) => return Err(err),
                     Ok((member_body, _)) => {
                         type_variables.delete(member_type.sig.ty_vars.len());
-                        checked_members.push(member_body);
+                        checked_members.push(if member_type.default.is_some() {
+                            core::Expr::mk_lam(false, member_body)
+                        } else {
+                            member_body
+                        });
                     }
                 };
             }
+            None => match &member_type.default {
+                None => {
+                    return Err(Error::MissingMember {
+                        source: env.source.clone(),
+                        pos: name.pos,
+                        cls: name.item.clone(),
+                        member: member_type.name.clone(),
+                    })
+                }
+                Some(default) => {
+                    let dictionary = assumes.iter().fold(
+                        core::Expr::Name(core::Name::Evidence(evidence_name.clone())),
+                        |acc, (evar, _)| core::Expr::mk_app(acc, core::Expr::EVar(*evar)),
+                    );
+                    checked_members.push(core::Expr::mk_lam(
+                        false,
+                        core::Expr::mk_app(
+                            default.as_ref().clone(),
+                            core::Expr::mk_lam(false, dictionary),
+                        ),
+                    ));
+                }
+            },
         }
     }
 
@@ -615,7 +1084,7 @@ pub fn check_instance(
             dictionary
                 .into_iter()
                 .enumerate()
-                .map(|(ix, val)| (core::Expr::Int(ix as i32), val))
+                .map(|(ix, val)| (core::Expr::Int(ix as i64), val))
                 .collect(),
             None,
         );
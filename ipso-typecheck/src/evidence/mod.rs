@@ -68,6 +68,12 @@ pub enum Constraint {
     HasField { field: Rc<str>, rest: core::Type },
     DebugRecordFields(core::Type),
     DebugVariantCtor(core::Type),
+    ToJsonRecordFields(core::Type),
+    FromJsonRecordFields(core::Type),
+    EqRecordFields(core::Type),
+    EqVariantCtor(core::Type),
+    OrdRecordFields(core::Type),
+    OrdVariantCtor(core::Type),
     Type(core::Type),
 }
 
@@ -84,6 +90,24 @@ impl Constraint {
             core::Type::App(_, a, b) if a.as_ref() == &core::Type::DebugVariantCtor => {
                 Constraint::DebugVariantCtor(b.as_ref().clone())
             }
+            core::Type::App(_, a, b) if a.as_ref() == &core::Type::ToJsonRecordFields => {
+                Constraint::ToJsonRecordFields(b.as_ref().clone())
+            }
+            core::Type::App(_, a, b) if a.as_ref() == &core::Type::FromJsonRecordFields => {
+                Constraint::FromJsonRecordFields(b.as_ref().clone())
+            }
+            core::Type::App(_, a, b) if a.as_ref() == &core::Type::EqRecordFields => {
+                Constraint::EqRecordFields(b.as_ref().clone())
+            }
+            core::Type::App(_, a, b) if a.as_ref() == &core::Type::EqVariantCtor => {
+                Constraint::EqVariantCtor(b.as_ref().clone())
+            }
+            core::Type::App(_, a, b) if a.as_ref() == &core::Type::OrdRecordFields => {
+                Constraint::OrdRecordFields(b.as_ref().clone())
+            }
+            core::Type::App(_, a, b) if a.as_ref() == &core::Type::OrdVariantCtor => {
+                Constraint::OrdVariantCtor(b.as_ref().clone())
+            }
             _ => Constraint::Type(ty.clone()),
         }
     }
@@ -99,6 +123,22 @@ impl Constraint {
             Constraint::DebugVariantCtor(ty) => {
                 core::Type::app(core::Type::DebugVariantCtor, ty.clone())
             }
+            Constraint::ToJsonRecordFields(ty) => {
+                core::Type::app(core::Type::ToJsonRecordFields, ty.clone())
+            }
+            Constraint::FromJsonRecordFields(ty) => {
+                core::Type::app(core::Type::FromJsonRecordFields, ty.clone())
+            }
+            Constraint::EqRecordFields(ty) => {
+                core::Type::app(core::Type::EqRecordFields, ty.clone())
+            }
+            Constraint::EqVariantCtor(ty) => core::Type::app(core::Type::EqVariantCtor, ty.clone()),
+            Constraint::OrdRecordFields(ty) => {
+                core::Type::app(core::Type::OrdRecordFields, ty.clone())
+            }
+            Constraint::OrdVariantCtor(ty) => {
+                core::Type::app(core::Type::OrdVariantCtor, ty.clone())
+            }
             Constraint::Type(ty) => ty.clone(),
         }
     }
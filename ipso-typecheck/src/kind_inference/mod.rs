@@ -190,6 +190,7 @@ pub fn infer(
         syntax::Type::Unit => Ok((core::Type::Unit, Kind::Type)),
         syntax::Type::Bool => Ok((core::Type::Bool, Kind::Type)),
         syntax::Type::Int => Ok((core::Type::Int, Kind::Type)),
+        syntax::Type::Float => Ok((core::Type::Float, Kind::Type)),
         syntax::Type::Char => Ok((core::Type::Char, Kind::Type)),
         syntax::Type::String => Ok((core::Type::String, Kind::Type)),
         syntax::Type::Bytes => Ok((core::Type::Bytes, Kind::Type)),
@@ -251,7 +252,14 @@ pub fn infer(
             let out_kind = state.fresh_meta();
             let a = check(env, state, pos, a, &Kind::mk_arrow(&in_kind, &out_kind))?;
             let b = check(env, state, pos, b, &in_kind)?;
-            Ok((core::Type::app(a, b), out_kind))
+            /*
+            `a`'s kind may be an unsolved metavariable, so [`core::Type::app`] can't be used to
+            compute the application's kind.
+            */
+            Ok((
+                core::Type::App(state.zonk(false, out_kind.clone()), Rc::new(a), Rc::new(b)),
+                out_kind,
+            ))
         }
 
         syntax::Type::Name(name) => match name.as_ref() {
@@ -263,6 +271,30 @@ pub fn infer(
                 core::Type::DebugVariantCtor,
                 Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
             )),
+            "ToJsonRecordFields" => Ok((
+                core::Type::ToJsonRecordFields,
+                Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            )),
+            "FromJsonRecordFields" => Ok((
+                core::Type::FromJsonRecordFields,
+                Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            )),
+            "EqRecordFields" => Ok((
+                core::Type::EqRecordFields,
+                Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            )),
+            "EqVariantCtor" => Ok((
+                core::Type::EqVariantCtor,
+                Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            )),
+            "OrdRecordFields" => Ok((
+                core::Type::OrdRecordFields,
+                Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            )),
+            "OrdVariantCtor" => Ok((
+                core::Type::OrdVariantCtor,
+                Kind::mk_arrow(&Kind::Row, &Kind::Constraint),
+            )),
             _ => match env.types.get(name) {
                 Some(kind) => Ok((core::Type::Name(kind.clone(), name.clone()), kind.clone())),
                 None => Err(Error::not_in_scope(pos, name.clone())),
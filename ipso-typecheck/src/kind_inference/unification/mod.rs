@@ -122,6 +122,7 @@ impl Solutions {
                     }
                     Solution::Solved(new_kind) => {
                         *kind = new_kind.clone();
+                        zonk_simple(solutions, close_unsolved, kind);
                     }
                 },
                 Kind::Ref(kind_ref) => {
@@ -143,6 +143,42 @@ pub enum Error {
         pos: usize,
         cls: Rc<str>,
     },
+    MissingMember {
+        source: Source,
+        pos: usize,
+        cls: Rc<str>,
+        member: String,
+    },
+    DuplicateDefault {
+        source: Source,
+        pos: usize,
+    },
+    ExportNotDefined {
+        source: Source,
+        pos: usize,
+    },
+    ExportClassMember {
+        source: Source,
+        pos: usize,
+        class: Rc<str>,
+    },
+    ExportConstructor {
+        source: Source,
+        pos: usize,
+        data_type: Rc<str>,
+    },
+    DuplicateTypeArgument {
+        source: Source,
+        pos: usize,
+    },
+    DuplicateConstructor {
+        source: Source,
+        pos: usize,
+    },
+    TypeAliasNotSupported {
+        source: Source,
+        pos: usize,
+    },
 }
 
 impl Error {
@@ -154,6 +190,14 @@ impl Error {
             Error::DuplicateClassArgument { source, .. } => source.clone(),
             Error::NoSuchClass { source, .. } => source.clone(),
             Error::NotAMember { source, .. } => source.clone(),
+            Error::MissingMember { source, .. } => source.clone(),
+            Error::DuplicateDefault { source, .. } => source.clone(),
+            Error::ExportNotDefined { source, .. } => source.clone(),
+            Error::ExportClassMember { source, .. } => source.clone(),
+            Error::ExportConstructor { source, .. } => source.clone(),
+            Error::DuplicateTypeArgument { source, .. } => source.clone(),
+            Error::DuplicateConstructor { source, .. } => source.clone(),
+            Error::TypeAliasNotSupported { source, .. } => source.clone(),
         }
     }
 
@@ -165,6 +209,14 @@ impl Error {
             Error::DuplicateClassArgument { pos, .. } => *pos,
             Error::NoSuchClass { pos, .. } => *pos,
             Error::NotAMember { pos, .. } => *pos,
+            Error::MissingMember { pos, .. } => *pos,
+            Error::DuplicateDefault { pos, .. } => *pos,
+            Error::ExportNotDefined { pos, .. } => *pos,
+            Error::ExportClassMember { pos, .. } => *pos,
+            Error::ExportConstructor { pos, .. } => *pos,
+            Error::DuplicateTypeArgument { pos, .. } => *pos,
+            Error::DuplicateConstructor { pos, .. } => *pos,
+            Error::TypeAliasNotSupported { pos, .. } => *pos,
         }
     }
 
@@ -178,17 +230,50 @@ impl Error {
             Error::NotAMember { cls, .. } => {
                 format!("not a member of the {:?} type class", cls)
             }
+            Error::MissingMember { cls, member, .. } => {
+                format!("missing member {:?} of the {:?} type class", member, cls)
+            }
+            Error::DuplicateDefault { .. } => String::from("duplicate default implementation"),
+            Error::ExportNotDefined { .. } => String::from("not defined in this module"),
+            Error::ExportClassMember { class, .. } => {
+                format!("can't export a member of the {:?} type class", class)
+            }
+            Error::ExportConstructor { data_type, .. } => {
+                format!(
+                    "can't export a constructor of the {:?} data type",
+                    data_type
+                )
+            }
+            Error::DuplicateTypeArgument { .. } => String::from("duplicate type argument"),
+            Error::DuplicateConstructor { .. } => String::from("duplicate constructor"),
+            Error::TypeAliasNotSupported { .. } => String::from("type aliases are not supported"),
         }
     }
 
     pub fn addendum(&self) -> Option<String> {
         match self {
-            Error::TypeError { .. } => None,
+            Error::TypeError { error } => error.addendum(),
             Error::ConstraintError { error } => error.addendum(),
             Error::KindError { error, .. } => error.addendum(),
             Error::DuplicateClassArgument { .. } => None,
             Error::NoSuchClass { .. } => None,
             Error::NotAMember { .. } => None,
+            Error::MissingMember { .. } => None,
+            Error::DuplicateDefault { .. } => None,
+            Error::ExportNotDefined { .. } => None,
+            Error::ExportClassMember { class, .. } => Some(format!(
+                "export {:?} instead, which exports all of its members",
+                class
+            )),
+            Error::ExportConstructor { data_type, .. } => Some(format!(
+                "export {:?} instead, which exports all of its constructors",
+                data_type
+            )),
+            Error::DuplicateTypeArgument { .. } => None,
+            Error::DuplicateConstructor { .. } => None,
+            Error::TypeAliasNotSupported { .. } => Some(String::from(
+                "a data type with a single constructor is declared with a leading `|`, e.g. `type Box a = | Box a`",
+            )),
         }
     }
 
@@ -219,6 +304,8 @@ impl From<constraint_solving::Error> for Error {
 }
 
 /**
+Defaults the types of ambiguous arithmetic (see [`type_inference::default_arithmetic_types`])
+before solving the expression's evidence.
 
 * `range` - the number of most recently bound type variables that
    are relevant to this round of abstraction.
@@ -238,6 +325,16 @@ pub fn abstract_evidence(
     range: usize,
     mut expr: core::Expr,
 ) -> Result<(core::Expr, Vec<core::Type>), Error> {
+    type_inference::default_arithmetic_types(
+        type_inference::unification::Env {
+            common_kinds,
+            types,
+            type_variables,
+        },
+        type_inference_state,
+        source,
+    )?;
+
     expr.subst_placeholder(&mut |p| -> Result<_, Error> {
         let (expr, _solved_constraint) = solve_placeholder(
             constraint_solving::Env {
@@ -427,6 +524,7 @@ pub fn eq_zonked_type(
         },
         core::Type::Bool => matches!(t2, core::Type::Bool),
         core::Type::Int => matches!(t2, core::Type::Int),
+        core::Type::Float => matches!(t2, core::Type::Float),
         core::Type::Char => matches!(t2, core::Type::Char),
         core::Type::String => matches!(t2, core::Type::String),
         core::Type::Bytes => matches!(t2, core::Type::Bytes),
@@ -434,6 +532,12 @@ pub fn eq_zonked_type(
         core::Type::Arrow(_) => matches!(t2, core::Type::Arrow(_)),
         core::Type::DebugRecordFields => matches!(t2, core::Type::DebugRecordFields),
         core::Type::DebugVariantCtor => matches!(t2, core::Type::DebugVariantCtor),
+        core::Type::ToJsonRecordFields => matches!(t2, core::Type::ToJsonRecordFields),
+        core::Type::FromJsonRecordFields => matches!(t2, core::Type::FromJsonRecordFields),
+        core::Type::EqRecordFields => matches!(t2, core::Type::EqRecordFields),
+        core::Type::EqVariantCtor => matches!(t2, core::Type::EqVariantCtor),
+        core::Type::OrdRecordFields => matches!(t2, core::Type::OrdRecordFields),
+        core::Type::OrdVariantCtor => matches!(t2, core::Type::OrdVariantCtor),
         core::Type::FatArrow(_) => matches!(t2, core::Type::FatArrow(_)),
         core::Type::Array(_) => matches!(t2, core::Type::Arrow(_)),
         core::Type::Record(_) => matches!(t2, core::Type::Record(_)),
@@ -500,6 +604,34 @@ pub fn eq_zonked_constraint(
             evidence::Constraint::DebugVariantCtor(ty2) => eq_zonked_type(type_solutions, ty, ty2),
             _ => false,
         },
+        evidence::Constraint::ToJsonRecordFields(ty) => match c2 {
+            evidence::Constraint::ToJsonRecordFields(ty2) => {
+                eq_zonked_type(type_solutions, ty, ty2)
+            }
+            _ => false,
+        },
+        evidence::Constraint::FromJsonRecordFields(ty) => match c2 {
+            evidence::Constraint::FromJsonRecordFields(ty2) => {
+                eq_zonked_type(type_solutions, ty, ty2)
+            }
+            _ => false,
+        },
+        evidence::Constraint::EqRecordFields(ty) => match c2 {
+            evidence::Constraint::EqRecordFields(ty2) => eq_zonked_type(type_solutions, ty, ty2),
+            _ => false,
+        },
+        evidence::Constraint::EqVariantCtor(ty) => match c2 {
+            evidence::Constraint::EqVariantCtor(ty2) => eq_zonked_type(type_solutions, ty, ty2),
+            _ => false,
+        },
+        evidence::Constraint::OrdRecordFields(ty) => match c2 {
+            evidence::Constraint::OrdRecordFields(ty2) => eq_zonked_type(type_solutions, ty, ty2),
+            _ => false,
+        },
+        evidence::Constraint::OrdVariantCtor(ty) => match c2 {
+            evidence::Constraint::OrdVariantCtor(ty2) => eq_zonked_type(type_solutions, ty, ty2),
+            _ => false,
+        },
         evidence::Constraint::Type(ty) => match c2 {
             evidence::Constraint::Type(ty2) => eq_zonked_type(type_solutions, ty, ty2),
             _ => false,
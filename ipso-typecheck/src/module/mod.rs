@@ -4,8 +4,8 @@ use crate::constraint_solving::Sort;
 use crate::{constraint_solving::Implication, declaration, Error};
 use ipso_core::{self as core, CommonKinds};
 use ipso_diagnostic::Source;
-use ipso_syntax::{self as syntax, kind::Kind, ModuleId, ModuleRef, Modules};
-use std::collections::HashMap;
+use ipso_syntax::{self as syntax, kind::Kind, ModuleId, ModuleRef, Modules, Spanned};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 /// Module checking state.
@@ -14,8 +14,11 @@ pub struct State {
     pub type_context: HashMap<Rc<str>, Kind>,
     pub context: HashMap<String, core::Signature>,
     pub class_context: HashMap<Rc<str>, core::ClassDeclaration>,
+    pub constructor_context: HashMap<Rc<str>, core::DataDeclaration>,
     pub module_context: HashMap<ModuleId, HashMap<String, core::Signature>>,
+    pub module_unexported: HashMap<ModuleId, HashSet<String>>,
     pub decls: Vec<core::Declaration>,
+    pub exports: Option<Vec<Spanned<String>>>,
 }
 
 impl State {
@@ -25,8 +28,11 @@ impl State {
             type_context: HashMap::new(),
             context: HashMap::new(),
             class_context: HashMap::new(),
+            constructor_context: HashMap::new(),
             module_context: HashMap::new(),
+            module_unexported: HashMap::new(),
             decls: Vec::new(),
+            exports: None,
         }
     }
 
@@ -43,9 +49,25 @@ impl State {
                     .push(core::Declaration::Definition { name, sig, body })
             }
 
-            declaration::Checked::ResolvedImport { module_id, module } => {
+            declaration::Checked::ResolvedImport {
+                module_id,
+                module,
+                data_decls,
+                constructors,
+                builtin_types,
+                class_decls,
+            } => {
                 self.module_context
                     .insert(module_id, module.get_signatures(common_kinds));
+                self.module_unexported.insert(
+                    module_id,
+                    module
+                        .decls
+                        .iter()
+                        .filter(|decl| !module.exports(decl))
+                        .flat_map(|decl| decl.get_signatures(common_kinds).into_keys())
+                        .collect(),
+                );
                 module.decls.iter().for_each(|decl| {
                     if let core::Declaration::Instance {
                         ty_vars,
@@ -58,6 +80,28 @@ impl State {
                         self.import_instance(module_id, ty_vars, assumes, head, evidence.clone())
                     }
                 });
+                data_decls.iter().for_each(|data_decl| {
+                    register_data(
+                        &mut self.type_context,
+                        &mut self.constructor_context,
+                        data_decl,
+                    )
+                });
+                self.constructor_context.extend(constructors);
+                builtin_types.iter().for_each(|(name, kind)| {
+                    register_builtin_type(&mut self.type_context, name, kind)
+                });
+                class_decls.iter().for_each(|class_decl| {
+                    register_class(
+                        common_kinds,
+                        &mut self.type_context,
+                        &mut self.implications,
+                        &mut self.context,
+                        &mut self.class_context,
+                        Some(module_id),
+                        class_decl,
+                    )
+                });
             }
 
             declaration::Checked::Class(class_decl) => {
@@ -67,11 +111,25 @@ impl State {
                     &mut self.implications,
                     &mut self.context,
                     &mut self.class_context,
+                    None,
                     &class_decl,
                 );
                 self.decls.push(core::Declaration::Class(class_decl))
             }
 
+            declaration::Checked::Export { names } => {
+                self.exports.get_or_insert_with(Vec::new).extend(names)
+            }
+
+            declaration::Checked::Data(data_decl) => {
+                register_data(
+                    &mut self.type_context,
+                    &mut self.constructor_context,
+                    &data_decl,
+                );
+                self.decls.push(core::Declaration::Data(data_decl))
+            }
+
             declaration::Checked::Instance {
                 evidence_name,
                 evidence_body,
@@ -128,7 +186,8 @@ impl State {
             core::Declaration::TypeAlias { name, args, body } => {
                 self.import_type_alias(name, args, body)
             }
-            core::Declaration::Class(decl) => self.import_class(common_kinds, decl),
+            core::Declaration::Class(decl) => self.import_class(common_kinds, module_id, decl),
+            core::Declaration::Data(decl) => self.import_data(decl),
             core::Declaration::Evidence { .. } => {}
             core::Declaration::Instance {
                 ty_vars,
@@ -140,17 +199,27 @@ impl State {
         }
     }
 
-    pub fn import_class(&mut self, common_kinds: &CommonKinds, decl: &core::ClassDeclaration) {
+    pub fn import_class(
+        &mut self,
+        common_kinds: &CommonKinds,
+        module_id: ModuleId,
+        decl: &core::ClassDeclaration,
+    ) {
         register_class(
             common_kinds,
             &mut self.type_context,
             &mut self.implications,
             &mut self.context,
             &mut self.class_context,
+            Some(module_id),
             decl,
         )
     }
 
+    pub fn import_data(&mut self, decl: &core::DataDeclaration) {
+        register_data(&mut self.type_context, &mut self.constructor_context, decl)
+    }
+
     pub fn import_instance(
         &mut self,
         module_id: ModuleId,
@@ -211,8 +280,10 @@ pub fn check(
                 common_kinds,
                 modules,
                 module_context: &state.module_context,
+                module_unexported: &state.module_unexported,
                 type_context: &state.type_context,
                 class_context: &state.class_context,
+                constructor_context: &state.constructor_context,
                 context: &state.context,
                 implications: &state.implications,
                 source,
@@ -222,9 +293,62 @@ pub fn check(
         .map(|checked| state.add_declaration(common_kinds, checked))
     })?;
 
+    let exports = state.exports.take();
     let decls = state.finish();
 
-    Ok(core::Module { decls })
+    /*
+    A module can only export names that it defines. Class members and data constructors are
+    exported along with their class or data type.
+    */
+    if let Some(exports) = &exports {
+        exports.iter().try_for_each(|export| {
+            if decls
+                .iter()
+                .any(|decl| decl.name() == Some(export.item.as_str()))
+            {
+                return Ok(());
+            }
+
+            match decls.iter().find_map(|decl| match decl {
+                core::Declaration::Class(class_decl)
+                    if class_decl
+                        .members
+                        .iter()
+                        .any(|member| member.name == export.item) =>
+                {
+                    Some(Error::ExportClassMember {
+                        source: source.clone(),
+                        pos: export.pos,
+                        class: class_decl.name.clone(),
+                    })
+                }
+                core::Declaration::Data(data_decl)
+                    if data_decl
+                        .constructors
+                        .iter()
+                        .any(|constructor| constructor.name.as_ref() == export.item) =>
+                {
+                    Some(Error::ExportConstructor {
+                        source: source.clone(),
+                        pos: export.pos,
+                        data_type: data_decl.name.clone(),
+                    })
+                }
+                _ => None,
+            }) {
+                Some(error) => Err(error),
+                None => Err(Error::ExportNotDefined {
+                    source: source.clone(),
+                    pos: export.pos,
+                }),
+            }
+        })?;
+    }
+
+    Ok(core::Module {
+        decls,
+        exports: exports.map(|exports| exports.into_iter().map(|export| export.item).collect()),
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -234,6 +358,7 @@ pub fn register_from_import(
     type_context: &mut HashMap<Rc<str>, Kind>,
     context: &mut HashMap<String, core::Signature>,
     class_context: &mut HashMap<Rc<str>, core::ClassDeclaration>,
+    constructor_context: &mut HashMap<Rc<str>, core::DataDeclaration>,
     modules: &Modules<core::Module>,
     module_id: ModuleId,
     names: &syntax::Names,
@@ -277,10 +402,16 @@ pub fn register_from_import(
                         implications,
                         context,
                         class_context,
+                        Some(module_id),
                         class_decl,
                     );
                 }
             }
+            core::Declaration::Data(data_decl) => {
+                if should_import(data_decl.name.as_ref()) {
+                    register_data(type_context, constructor_context, data_decl);
+                }
+            }
             core::Declaration::Evidence { .. } => {}
             core::Declaration::Instance {
                 ty_vars,
@@ -302,12 +433,23 @@ pub fn register_from_import(
     }
 }
 
+/**
+Bring a class into scope.
+
+## Arguments
+
+* `module_id` - Module that contains the class, or [`None`] for the module being checked.
+
+  The class context refers to the class's default implementations instead of containing them,
+  because an instance that uses a default might not be in the same module as the class.
+*/
 pub fn register_class(
     common_kinds: &CommonKinds,
     type_context: &mut HashMap<Rc<str>, Kind>,
     implications: &mut Vec<Implication>,
     context: &mut HashMap<String, core::Signature>,
     class_context: &mut HashMap<Rc<str>, core::ClassDeclaration>,
+    module_id: Option<ModuleId>,
     decl: &core::ClassDeclaration,
 ) {
     let decl_name: Rc<str> = Rc::from(decl.name.as_ref());
@@ -346,7 +488,7 @@ pub fn register_class(
                 consequent: superclass.clone(),
                 evidence: Rc::new(core::Expr::mk_lam(
                     true,
-                    core::Expr::mk_project(core::Expr::Var(0), core::Expr::Int(pos as i32)),
+                    core::Expr::mk_project(core::Expr::Var(0), core::Expr::Int(pos as i64)),
                 )),
             }),
     );
@@ -360,7 +502,43 @@ pub fn register_class(
     );
 
     // update class context
-    class_context.insert(decl_name, decl.clone());
+    let mut decl = decl.clone();
+    let default_names: Vec<Option<Rc<str>>> = decl
+        .members
+        .iter()
+        .map(|member| {
+            member
+                .default
+                .as_ref()
+                .map(|_| decl.default_name(&member.name))
+        })
+        .collect();
+    for (member, default_name) in decl.members.iter_mut().zip(default_names) {
+        member.default = default_name.map(|default_name| {
+            Rc::new(match module_id {
+                Some(module_id) => core::Expr::Module {
+                    id: ModuleRef::from(module_id),
+                    path: vec![],
+                    item: core::Name::Evidence(default_name),
+                },
+                None => core::Expr::Name(core::Name::Evidence(default_name)),
+            })
+        });
+    }
+    class_context.insert(decl_name, decl);
+}
+
+pub fn register_data(
+    type_context: &mut HashMap<Rc<str>, Kind>,
+    constructor_context: &mut HashMap<Rc<str>, core::DataDeclaration>,
+    decl: &core::DataDeclaration,
+) {
+    type_context.insert(decl.name.clone(), decl.kind());
+    constructor_context.extend(
+        decl.constructors
+            .iter()
+            .map(|constructor| (constructor.name.clone(), decl.clone())),
+    );
 }
 
 pub fn register_instance(
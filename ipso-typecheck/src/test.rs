@@ -24,6 +24,24 @@ fn zonk_constraint(
         Constraint::DebugVariantCtor(ty) => {
             Constraint::DebugVariantCtor(type_solutions.zonk(kind_solutions, ty.clone()))
         }
+        Constraint::ToJsonRecordFields(ty) => {
+            Constraint::ToJsonRecordFields(type_solutions.zonk(kind_solutions, ty.clone()))
+        }
+        Constraint::FromJsonRecordFields(ty) => {
+            Constraint::FromJsonRecordFields(type_solutions.zonk(kind_solutions, ty.clone()))
+        }
+        Constraint::EqRecordFields(ty) => {
+            Constraint::EqRecordFields(type_solutions.zonk(kind_solutions, ty.clone()))
+        }
+        Constraint::EqVariantCtor(ty) => {
+            Constraint::EqVariantCtor(type_solutions.zonk(kind_solutions, ty.clone()))
+        }
+        Constraint::OrdRecordFields(ty) => {
+            Constraint::OrdRecordFields(type_solutions.zonk(kind_solutions, ty.clone()))
+        }
+        Constraint::OrdVariantCtor(ty) => {
+            Constraint::OrdVariantCtor(type_solutions.zonk(kind_solutions, ty.clone()))
+        }
         Constraint::Type(ty) => Constraint::Type(type_solutions.zonk(kind_solutions, ty.clone())),
     }
 }
@@ -180,6 +198,7 @@ fn infer_record_1() {
     let type_solutions = Default::default();
     let implications = Default::default();
     let type_signatures = Default::default();
+    let constructors = Default::default();
     let source = Source::Interactive {
         label: String::from("test"),
     };
@@ -240,9 +259,11 @@ fn infer_record_1() {
         type_inference::Env {
             common_kinds: &common_kinds,
             modules: &modules,
+            unexported: &Default::default(),
             types: &types,
             type_variables: &type_variables,
             type_signatures: &type_signatures,
+            constructors: &constructors,
             source: &source,
         },
         &mut type_inference_state,
@@ -265,6 +286,10 @@ fn infer_record_1() {
     let p1 = placeholders.get(&Placeholder(1)).unwrap();
     let p2 = placeholders.get(&Placeholder(2)).unwrap();
 
+    /*
+    A record literal without a rest lays out all its fields at once, so each field's index is
+    its position in the entire record, and its `HasField` constraint mentions every other field.
+    */
     assert_eq!(
         Ok((
             Rc::new(core::Expr::Int(2)),
@@ -305,7 +330,13 @@ fn infer_record_1() {
             Rc::new(core::Expr::Int(1)),
             Constraint::HasField {
                 field: Rc::from("y"),
-                rest: core::Type::mk_rows(vec![(Rc::from("x"), core::Type::Int)], None)
+                rest: core::Type::mk_rows(
+                    vec![
+                        (Rc::from("z"), core::Type::Bool),
+                        (Rc::from("x"), core::Type::Int)
+                    ],
+                    None
+                )
             }
         )),
         solve_placeholder(
@@ -334,7 +365,13 @@ fn infer_record_1() {
             Rc::new(core::Expr::Int(0)),
             Constraint::HasField {
                 field: Rc::from("x"),
-                rest: core::Type::RowNil
+                rest: core::Type::mk_rows(
+                    vec![
+                        (Rc::from("z"), core::Type::Bool),
+                        (Rc::from("y"), core::Type::String)
+                    ],
+                    None
+                )
             }
         )),
         solve_placeholder(
@@ -367,6 +404,7 @@ fn check_declaration(
     let implications = Default::default();
     let context = Default::default();
     let class_context = Default::default();
+    let constructor_context = Default::default();
     let modules = Default::default();
     let module_context = Default::default();
     let source = Source::Interactive {
@@ -377,8 +415,10 @@ fn check_declaration(
             common_kinds: &common_kinds,
             modules: &modules,
             module_context: &module_context,
+            module_unexported: &Default::default(),
             type_context: &types,
             class_context: &class_context,
+            constructor_context: &constructor_context,
             context: &context,
             implications,
             source: &source,
@@ -616,10 +656,19 @@ fn check_definition_4() {
             args: vec![syntax::Spanned {
                 pos: 3,
                 item: syntax::Pattern::Record {
-                    names: vec![syntax::Spanned {
-                        pos: 1,
-                        item: Rc::from("x"),
-                    }],
+                    fields: vec![(
+                        syntax::Spanned {
+                            pos: 1,
+                            item: Rc::from("x"),
+                        },
+                        syntax::Spanned {
+                            pos: 1,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 1,
+                                item: Rc::from("x"),
+                            }),
+                        },
+                    )],
                     rest: Some(syntax::Spanned {
                         pos: 2,
                         item: Rc::from("r"),
@@ -661,7 +710,7 @@ fn check_definition_4() {
                     core::Expr::Var(0),
                     vec![core::Branch {
                         pattern: core::Pattern::Record {
-                            names: vec![core::Expr::Var(1)],
+                            fields: vec![(core::Expr::Var(1), core::Pattern::Name)],
                             rest: true,
                         },
                         body: core::Expr::Var(1),
@@ -695,6 +744,7 @@ fn check_class_1() {
                         core::Type::arrow(&common_kinds, a, core::Type::Bool),
                     ),
                 },
+                default: None,
             }],
         }))
     };
@@ -721,6 +771,7 @@ fn check_class_1() {
                     ),
                 },
             )],
+            defaults: Vec::new(),
         },
     });
     assert_eq!(expected, actual);
@@ -744,6 +795,7 @@ fn check_class_1() {
                         core::Type::arrow(&common_kinds, a, core::Type::Bool),
                     ),
                 },
+                default: None,
             }],
         }
     };
@@ -797,6 +849,7 @@ fn check_class_2() {
                         core::Type::arrow(&common_kinds, b, core::Type::Bool),
                     ),
                 },
+                default: None,
             }],
         }))
     };
@@ -823,6 +876,7 @@ fn check_class_2() {
                     ),
                 },
             )],
+            defaults: Vec::new(),
         },
     });
     assert_eq!(expected, actual);
@@ -847,6 +901,7 @@ fn check_class_2() {
                         core::Type::arrow(&common_kinds, b, core::Type::Bool),
                     ),
                 },
+                default: None,
             }],
         }
     };
@@ -934,6 +989,7 @@ fn check_instance_1() {
                             core::Type::arrow(&common_kinds, a, core::Type::Bool),
                         ),
                     },
+                    default: None,
                 }],
             }),
         )
@@ -947,8 +1003,10 @@ fn check_instance_1() {
             common_kinds: &common_kinds,
             modules: &modules,
             module_context: &module_context,
+            module_unexported: &Default::default(),
             type_context: &state.type_context,
             class_context: &state.class_context,
+            constructor_context: &state.constructor_context,
             context: &state.context,
             implications: &state.implications,
             source: &source,
@@ -996,3 +1054,318 @@ fn check_instance_1() {
     );
     assert_eq!(expected, actual)
 }
+
+#[test]
+fn check_instance_2() {
+    let common_kinds = CommonKinds::default();
+    let modules = Default::default();
+    let module_context = Default::default();
+    let mut state = module::State::new();
+    let source = Source::Interactive {
+        label: String::from("test"),
+    };
+
+    /*
+    class Eq a where
+      eq : a -> a -> Bool
+      neq : a -> a -> Bool
+      neq x y = ...
+    */
+    {
+        let a = core::Type::unsafe_mk_var(0, Kind::Type);
+        let member_sig = TypeSig {
+            ty_vars: vec![],
+            body: core::Type::arrow(
+                &common_kinds,
+                a.clone(),
+                core::Type::arrow(&common_kinds, a, core::Type::Bool),
+            ),
+        };
+        state.add_declaration(
+            &common_kinds,
+            declaration::Checked::Class(core::ClassDeclaration {
+                supers: Vec::new(),
+                name: Rc::from("Eq"),
+                args: vec![(Rc::from("a"), Kind::Type)],
+                members: vec![
+                    ClassMember {
+                        name: String::from("eq"),
+                        sig: member_sig.clone(),
+                        default: None,
+                    },
+                    ClassMember {
+                        name: String::from("neq"),
+                        sig: member_sig,
+                        default: Some(Rc::new(core::Expr::mk_lam(
+                            true,
+                            core::Expr::mk_lam(true, core::Expr::mk_lam(true, core::Expr::False)),
+                        ))),
+                    },
+                ],
+            }),
+        )
+    };
+
+    let check_instance = |member: &str| {
+        crate::declaration::check(
+            crate::declaration::Env {
+                common_kinds: &common_kinds,
+                modules: &modules,
+                module_context: &module_context,
+                module_unexported: &Default::default(),
+                type_context: &state.type_context,
+                class_context: &state.class_context,
+                constructor_context: &state.constructor_context,
+                context: &state.context,
+                implications: &state.implications,
+                source: &source,
+            },
+            &Spanned {
+                pos: 0,
+                item: syntax::Declaration::Instance {
+                    assumes: Vec::new(),
+                    name: Spanned {
+                        pos: 9,
+                        item: Rc::from("Eq"),
+                    },
+                    args: vec![Spanned {
+                        pos: 11,
+                        item: Type::Unit,
+                    }],
+                    members: vec![InstanceMember {
+                        name: Spanned {
+                            pos: 22,
+                            item: String::from(member),
+                        },
+                        args: Vec::new(),
+                        body: Spanned {
+                            pos: 27,
+                            item: syntax::Expr::mk_lam(
+                                vec![
+                                    syntax::Spanned {
+                                        pos: 28,
+                                        item: syntax::Pattern::Wildcard,
+                                    },
+                                    syntax::Spanned {
+                                        pos: 30,
+                                        item: syntax::Pattern::Wildcard,
+                                    },
+                                ],
+                                Spanned {
+                                    pos: 35,
+                                    item: syntax::Expr::True,
+                                },
+                            ),
+                        },
+                    }],
+                },
+            },
+        )
+    };
+
+    /*
+    instance Eq () where
+      eq = \_ _ -> True
+    */
+    let expected = {
+        let eq_ty = core::Type::unsafe_mk_name(
+            Rc::from("Eq"),
+            Kind::mk_arrow(&Kind::Type, &Kind::Constraint),
+        );
+        let evidence_name: Rc<str> = Rc::from("Eq ()");
+        Ok(declaration::Checked::Instance {
+            evidence_name: evidence_name.clone(),
+            evidence_body: Rc::new(core::Expr::mk_record(
+                vec![
+                    (
+                        // eq
+                        core::Expr::Int(0),
+                        // \_ _ -> True
+                        core::Expr::mk_lam(false, core::Expr::mk_lam(false, core::Expr::True)),
+                    ),
+                    (
+                        // neq
+                        core::Expr::Int(1),
+                        // \_ -> neqDefault (\_ -> dict)
+                        core::Expr::mk_lam(
+                            false,
+                            core::Expr::mk_app(
+                                core::Expr::Name(core::Name::evidence("Eq.neq default")),
+                                core::Expr::mk_lam(
+                                    false,
+                                    core::Expr::Name(core::Name::evidence(evidence_name.clone())),
+                                ),
+                            ),
+                        ),
+                    ),
+                ],
+                None,
+            )),
+            instance_ty_vars: Vec::new(),
+            instance_assumes: Vec::new(),
+            instance_head: core::Type::app(eq_ty, core::Type::Unit),
+            instance_evidence: evidence_name,
+        })
+    };
+    assert_eq!(expected, check_instance("eq"));
+
+    /*
+    instance Eq () where
+      neq = \_ _ -> True
+    */
+    assert_eq!(
+        Err(crate::Error::MissingMember {
+            source: source.clone(),
+            pos: 9,
+            cls: Rc::from("Eq"),
+            member: String::from("eq"),
+        }),
+        check_instance("neq")
+    );
+}
+
+#[test]
+fn check_data_1() {
+    let common_kinds = CommonKinds::default();
+    let mut state = module::State::new();
+
+    let expected_decl = core::DataDeclaration {
+        name: Rc::from("Maybe"),
+        args: vec![(Rc::from("a"), Kind::Type)],
+        constructors: vec![
+            core::DataConstructor {
+                name: Rc::from("Nothing"),
+                fields: vec![],
+            },
+            core::DataConstructor {
+                name: Rc::from("Just"),
+                fields: vec![core::Type::unsafe_mk_var(0, Kind::Type)],
+            },
+        ],
+    };
+    /*
+    type Maybe a =
+      | Nothing
+      | Just a
+    */
+    let actual = check_declaration(&Spanned {
+        pos: 0,
+        item: syntax::Declaration::Data {
+            name: Rc::from("Maybe"),
+            args: vec![Spanned {
+                pos: 11,
+                item: Rc::from("a"),
+            }],
+            constructors: vec![
+                syntax::DataConstructor {
+                    name: Spanned {
+                        pos: 19,
+                        item: Rc::from("Nothing"),
+                    },
+                    fields: vec![],
+                },
+                syntax::DataConstructor {
+                    name: Spanned {
+                        pos: 31,
+                        item: Rc::from("Just"),
+                    },
+                    fields: vec![Type::Var(Rc::from("a"))],
+                },
+            ],
+        },
+    });
+    assert_eq!(
+        Ok(declaration::Checked::Data(expected_decl.clone())),
+        actual
+    );
+
+    state.add_declaration(&common_kinds, actual.unwrap());
+
+    assert_eq!(
+        Some(&Kind::mk_arrow(&Kind::Type, &Kind::Type)),
+        state.type_context.get("Maybe")
+    );
+    assert_eq!(
+        Some(&expected_decl),
+        state.constructor_context.get("Nothing")
+    );
+    assert_eq!(Some(&expected_decl), state.constructor_context.get("Just"));
+}
+
+#[test]
+fn check_data_2() {
+    let type_to_type = Kind::mk_arrow(&Kind::Type, &Kind::Type);
+
+    let expected = Ok(declaration::Checked::Data(core::DataDeclaration {
+        name: Rc::from("Wrap"),
+        args: vec![(Rc::from("f"), type_to_type.clone())],
+        constructors: vec![core::DataConstructor {
+            name: Rc::from("Wrap"),
+            fields: vec![core::Type::app(
+                core::Type::unsafe_mk_var(0, type_to_type),
+                core::Type::Int,
+            )],
+        }],
+    }));
+    /*
+    type Wrap f =
+      | Wrap (f Int)
+    */
+    let actual = check_declaration(&Spanned {
+        pos: 0,
+        item: syntax::Declaration::Data {
+            name: Rc::from("Wrap"),
+            args: vec![Spanned {
+                pos: 10,
+                item: Rc::from("f"),
+            }],
+            constructors: vec![syntax::DataConstructor {
+                name: Spanned {
+                    pos: 18,
+                    item: Rc::from("Wrap"),
+                },
+                fields: vec![Type::mk_app(Type::Var(Rc::from("f")), Type::Int)],
+            }],
+        },
+    });
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn check_data_3() {
+    /*
+    type T = | A | A
+    */
+    let actual = check_declaration(&Spanned {
+        pos: 0,
+        item: syntax::Declaration::Data {
+            name: Rc::from("T"),
+            args: vec![],
+            constructors: vec![
+                syntax::DataConstructor {
+                    name: Spanned {
+                        pos: 11,
+                        item: Rc::from("A"),
+                    },
+                    fields: vec![],
+                },
+                syntax::DataConstructor {
+                    name: Spanned {
+                        pos: 15,
+                        item: Rc::from("A"),
+                    },
+                    fields: vec![],
+                },
+            ],
+        },
+    });
+    assert_eq!(
+        Err(crate::Error::DuplicateConstructor {
+            source: Source::Interactive {
+                label: String::from("test"),
+            },
+            pos: 15,
+        }),
+        actual
+    );
+}
@@ -0,0 +1,438 @@
+//! Exhaustiveness checking for `case` expressions.
+
+use super::{unify, Env, Error, State};
+use ipso_core::{RowParts, Type};
+use ipso_syntax as syntax;
+use std::rc::Rc;
+
+/**
+An example of a value that isn't matched by any branch of a `case` expression.
+
+Witnesses are found using the "usefulness" algorithm described in
+[Warnings for pattern matching](http://moscova.inria.fr/~maranget/papers/warn/index.html)
+(Maranget, 2007).
+*/
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Witness {
+    Wildcard,
+    Constructor { name: Rc<str>, args: Vec<Witness> },
+    Record(Vec<(Rc<str>, Witness)>),
+    Array(Vec<Witness>),
+}
+
+impl Witness {
+    /// Render the witness as a pattern.
+    pub fn render(&self) -> String {
+        match self {
+            Witness::Wildcard => String::from("_"),
+            Witness::Constructor { name, args } => {
+                let mut result = String::from(name.as_ref());
+                for arg in args {
+                    result.push(' ');
+                    result.push_str(&arg.render_atom());
+                }
+                result
+            }
+            Witness::Record(fields) => {
+                if fields.is_empty() {
+                    String::from("{}")
+                } else {
+                    format!(
+                        "{{ {} }}",
+                        fields
+                            .iter()
+                            .map(|(name, field)| format!("{} = {}", name, field.render()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
+            }
+            Witness::Array(items) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(Witness::render)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn render_atom(&self) -> String {
+        match self {
+            Witness::Constructor { args, .. } if !args.is_empty() => format!("({})", self.render()),
+            _ => self.render(),
+        }
+    }
+}
+
+/// The constructors of a type, along with the types of their arguments.
+enum Constructor {
+    Variant { name: Rc<str>, arg: Type },
+    Data { name: Rc<str>, fields: Vec<Type> },
+    Record { fields: Vec<(Rc<str>, Type)> },
+    Array { length: usize, item: Type },
+}
+
+impl Constructor {
+    fn arg_types(&self) -> Vec<Type> {
+        match self {
+            Constructor::Variant { arg, .. } => vec![arg.clone()],
+            Constructor::Data { fields, .. } => fields.clone(),
+            Constructor::Record { fields } => fields.iter().map(|(_, ty)| ty.clone()).collect(),
+            Constructor::Array { length, item } => vec![item.clone(); *length],
+        }
+    }
+
+    /**
+    The argument patterns of `pattern` when it matches this constructor.
+
+    Returns [`None`] when `pattern` matches a different constructor.
+    */
+    fn match_pattern<'a>(&self, pattern: &'a syntax::Pattern) -> Option<Vec<&'a syntax::Pattern>> {
+        if is_wildcard(pattern) {
+            return Some(vec![&WILDCARD; self.arg_types().len()]);
+        }
+        match (self, pattern) {
+            (
+                Constructor::Variant { name, .. } | Constructor::Data { name, .. },
+                syntax::Pattern::Variant {
+                    name: pattern_name,
+                    args,
+                },
+            ) => {
+                if name == pattern_name {
+                    Some(args.iter().map(|arg| &arg.item).collect())
+                } else {
+                    None
+                }
+            }
+            (
+                Constructor::Record { fields },
+                syntax::Pattern::Record {
+                    fields: pattern_fields,
+                    ..
+                },
+            ) => Some(
+                fields
+                    .iter()
+                    .map(|(name, _)| {
+                        pattern_fields
+                            .iter()
+                            .find(|(pattern_name, _)| &pattern_name.item == name)
+                            .map_or(&WILDCARD, |(_, field)| &field.item)
+                    })
+                    .collect(),
+            ),
+            (Constructor::Array { length, .. }, syntax::Pattern::Array(items)) => {
+                if *length == items.len() {
+                    Some(items.iter().map(|item| &item.item).collect())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn witness(&self, args: Vec<Witness>) -> Witness {
+        match self {
+            Constructor::Variant { name, .. } | Constructor::Data { name, .. } => {
+                Witness::Constructor {
+                    name: name.clone(),
+                    args,
+                }
+            }
+            Constructor::Record { fields } => Witness::Record(
+                fields
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .zip(args.into_iter())
+                    .collect(),
+            ),
+            Constructor::Array { .. } => Witness::Array(args),
+        }
+    }
+}
+
+/// The constructors that a column of patterns could be matched against.
+enum Signature {
+    /// Every value of the column's type is built from one of these constructors.
+    Complete(Vec<Constructor>),
+
+    /**
+    A variant whose row hasn't been solved.
+
+    The row is closed if the constructors are needed to make the match exhaustive, in the same
+    way that a `case` expression without a catch-all pattern closes its scrutinee's row.
+    */
+    Open {
+        constructors: Vec<Constructor>,
+        rest: Type,
+    },
+
+    /**
+    The column's type has too many values to list, e.g. `Int`.
+
+    Contains the constructors that appear in the column, and examples of the values that the
+    patterns in the column don't match.
+    */
+    Incomplete {
+        constructors: Vec<Constructor>,
+        examples: Vec<Witness>,
+    },
+}
+
+impl Signature {
+    fn incomplete() -> Self {
+        Signature::Incomplete {
+            constructors: Vec::new(),
+            examples: vec![Witness::Wildcard],
+        }
+    }
+}
+
+const WILDCARD: syntax::Pattern = syntax::Pattern::Wildcard;
+
+fn is_wildcard(pattern: &syntax::Pattern) -> bool {
+    matches!(
+        pattern,
+        syntax::Pattern::Name(_) | syntax::Pattern::Wildcard
+    )
+}
+
+/**
+Find the values of type `ty` that aren't matched by any of `patterns`.
+
+Returns an empty [`Vec`] when the patterns are exhaustive.
+*/
+pub fn missing_patterns(
+    env: Env,
+    state: &mut State,
+    position: usize,
+    ty: &Type,
+    patterns: &[&syntax::Pattern],
+) -> Result<Vec<Witness>, Error> {
+    let rows: Vec<Vec<&syntax::Pattern>> = patterns.iter().map(|pattern| vec![*pattern]).collect();
+    Ok(missing(env, state, position, &rows, &[ty.clone()])?
+        .into_iter()
+        .map(|mut witnesses| witnesses.swap_remove(0))
+        .collect())
+}
+
+/**
+Find the rows of values that aren't matched by any of the rows of `patterns`.
+
+Each row of `patterns` has one pattern per type in `tys`.
+*/
+fn missing(
+    env: Env,
+    state: &mut State,
+    position: usize,
+    patterns: &[Vec<&syntax::Pattern>],
+    tys: &[Type],
+) -> Result<Vec<Vec<Witness>>, Error> {
+    let (ty, tys) = match tys.split_first() {
+        None => {
+            return Ok(if patterns.is_empty() {
+                vec![Vec::new()]
+            } else {
+                Vec::new()
+            })
+        }
+        Some(tys) => tys,
+    };
+    let ty = state.zonk_type(ty.clone());
+    let heads: Vec<&syntax::Pattern> = patterns
+        .iter()
+        .map(|row| row[0])
+        .filter(|pattern| !is_wildcard(pattern))
+        .collect();
+
+    let (constructors, default) = match signature(env, &ty, &heads) {
+        Signature::Complete(constructors) => (constructors, Vec::new()),
+        Signature::Open { constructors, rest } => {
+            let default =
+                missing_default(env, state, position, patterns, tys, &[Witness::Wildcard])?;
+            if default.is_empty() {
+                return Ok(default);
+            }
+            unify(env, state, position, &Type::RowNil, &rest)?;
+            (constructors, Vec::new())
+        }
+        Signature::Incomplete {
+            constructors,
+            examples,
+        } => (
+            constructors,
+            missing_default(env, state, position, patterns, tys, &examples)?,
+        ),
+    };
+
+    let mut result = Vec::new();
+    for constructor in constructors {
+        let specialized: Vec<Vec<&syntax::Pattern>> = patterns
+            .iter()
+            .filter_map(|row| {
+                constructor.match_pattern(row[0]).map(|mut args| {
+                    args.extend_from_slice(&row[1..]);
+                    args
+                })
+            })
+            .collect();
+        let mut arg_tys = constructor.arg_types();
+        let arity = arg_tys.len();
+        arg_tys.extend_from_slice(tys);
+
+        for mut witnesses in missing(env, state, position, &specialized, &arg_tys)? {
+            let rest = witnesses.split_off(arity);
+            let mut row = vec![constructor.witness(witnesses)];
+            row.extend(rest);
+            result.push(row);
+        }
+    }
+    result.extend(default);
+    Ok(result)
+}
+
+/// Check the rows whose first pattern is a wildcard.
+fn missing_default(
+    env: Env,
+    state: &mut State,
+    position: usize,
+    patterns: &[Vec<&syntax::Pattern>],
+    tys: &[Type],
+    examples: &[Witness],
+) -> Result<Vec<Vec<Witness>>, Error> {
+    let default: Vec<Vec<&syntax::Pattern>> = patterns
+        .iter()
+        .filter(|row| is_wildcard(row[0]))
+        .map(|row| row[1..].to_vec())
+        .collect();
+    let witnesses = missing(env, state, position, &default, tys)?;
+    Ok(examples
+        .iter()
+        .flat_map(|example| {
+            witnesses.iter().map(move |witnesses| {
+                let mut row = vec![example.clone()];
+                row.extend(witnesses.iter().cloned());
+                row
+            })
+        })
+        .collect())
+}
+
+fn signature(env: Env, ty: &Type, heads: &[&syntax::Pattern]) -> Signature {
+    let head = match heads.first() {
+        None => return Signature::incomplete(),
+        Some(head) => head,
+    };
+    match head {
+        syntax::Pattern::Variant { name, .. } => match env.constructors.get(name) {
+            Some(data_decl) => {
+                let mut ty_args = Vec::new();
+                let mut current = ty;
+                while let Type::App(_, a, b) = current {
+                    ty_args.push(b.as_ref().clone());
+                    current = a;
+                }
+                ty_args.reverse();
+                Signature::Complete(
+                    data_decl
+                        .constructors
+                        .iter()
+                        .map(|constructor| Constructor::Data {
+                            name: constructor.name.clone(),
+                            fields: constructor
+                                .fields
+                                .iter()
+                                .map(|field| field.instantiate_many(&ty_args))
+                                .collect(),
+                        })
+                        .collect(),
+                )
+            }
+            None => match ty.unwrap_variant() {
+                Some(RowParts { fields, rest }) => {
+                    let constructors = fields
+                        .into_iter()
+                        .map(|(name, arg)| Constructor::Variant {
+                            name: name.clone(),
+                            arg: arg.clone(),
+                        })
+                        .collect();
+                    match rest {
+                        None => Signature::Complete(constructors),
+                        Some(rest @ Type::Meta(_, _)) => Signature::Open {
+                            constructors,
+                            rest: rest.clone(),
+                        },
+                        Some(_) => {
+                            let (constructors, missing): (Vec<Constructor>, Vec<Constructor>) =
+                                constructors.into_iter().partition(|constructor| {
+                                    heads
+                                        .iter()
+                                        .any(|head| constructor.match_pattern(head).is_some())
+                                });
+                            let mut examples: Vec<Witness> = missing
+                                .iter()
+                                .map(|constructor| constructor.witness(vec![Witness::Wildcard]))
+                                .collect();
+                            if examples.is_empty() {
+                                examples.push(Witness::Wildcard);
+                            }
+                            Signature::Incomplete {
+                                constructors,
+                                examples,
+                            }
+                        }
+                    }
+                }
+                None => Signature::incomplete(),
+            },
+        },
+        syntax::Pattern::Record { .. } => match ty {
+            Type::App(_, a, row) if matches!(a.as_ref(), Type::Record(_)) => {
+                Signature::Complete(vec![Constructor::Record {
+                    fields: row
+                        .unwrap_rows()
+                        .fields
+                        .into_iter()
+                        .map(|(name, ty)| (name.clone(), ty.clone()))
+                        .collect(),
+                }])
+            }
+            _ => Signature::incomplete(),
+        },
+        syntax::Pattern::Array(_) => match ty {
+            Type::App(_, a, item) if matches!(a.as_ref(), Type::Array(_)) => {
+                let mut lengths: Vec<usize> = heads
+                    .iter()
+                    .filter_map(|head| match head {
+                        syntax::Pattern::Array(items) => Some(items.len()),
+                        _ => None,
+                    })
+                    .collect();
+                lengths.sort_unstable();
+                lengths.dedup();
+
+                // At most `lengths.len()` lengths are matched by the column.
+                let shortest_missing = (0..=lengths.len())
+                    .find(|length| !lengths.contains(length))
+                    .unwrap_or(lengths.len());
+                Signature::Incomplete {
+                    constructors: lengths
+                        .into_iter()
+                        .map(|length| Constructor::Array {
+                            length,
+                            item: item.as_ref().clone(),
+                        })
+                        .collect(),
+                    examples: vec![Witness::Array(vec![Witness::Wildcard; shortest_missing])],
+                }
+            }
+            _ => Signature::incomplete(),
+        },
+        _ => Signature::incomplete(),
+    }
+}
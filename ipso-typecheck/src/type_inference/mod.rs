@@ -3,16 +3,17 @@
 #[cfg(test)]
 mod test;
 
+pub mod exhaustiveness;
 pub mod unification;
 
 use crate::{
     evidence::{self, Evidence},
     kind_inference, BoundVars,
 };
-use fnv::{FnvHashMap, FnvHashSet};
+use fnv::FnvHashSet;
 use ipso_core::{
-    Binop, Branch, CmdPart, CommonKinds, Expr, Name, Pattern, RowParts, Signature, StringPart,
-    Type, TypeSig,
+    Binop, Branch, CmdPart, CommonKinds, DataDeclaration, Expr, Name, Pattern, RowParts, Signature,
+    StringPart, Type, TypeSig,
 };
 use ipso_diagnostic::Source;
 use ipso_syntax::{self as syntax, Spanned};
@@ -26,12 +27,28 @@ use syntax::{kind::Kind, ModuleId};
 /// Type inference error information.
 #[derive(PartialEq, Eq, Debug)]
 pub enum ErrorInfo {
-    UnificationError { error: unification::Error },
-    NotInScope { name: String },
-    NotAValue { name: String },
+    UnificationError {
+        error: unification::Error,
+    },
+    NotInScope {
+        name: String,
+    },
+    NotAValue {
+        name: String,
+    },
     NotAModule,
-    DuplicateArgument { name: Rc<str> },
+    NotExported,
+    DuplicateArgument {
+        name: Rc<str>,
+    },
     RedundantPattern,
+    NonExhaustive {
+        missing: Vec<exhaustiveness::Witness>,
+    },
+    ConstructorArity {
+        expected: usize,
+        actual: usize,
+    },
 }
 
 /// A type inference error.
@@ -74,6 +91,15 @@ impl Error {
         }
     }
 
+    /// Construct an [`ErrorInfo::NotExported`].
+    pub fn not_exported(source: &Source, position: usize) -> Self {
+        Error {
+            source: source.clone(),
+            position,
+            info: ErrorInfo::NotExported,
+        }
+    }
+
     /// Construct an [`ErrorInfo::DuplicateArgument`].
     pub fn duplicate_argument(source: &Source, position: usize, name: Rc<str>) -> Self {
         Error {
@@ -92,6 +118,33 @@ impl Error {
         }
     }
 
+    /// Construct an [`ErrorInfo::NonExhaustive`].
+    pub fn non_exhaustive(
+        source: &Source,
+        position: usize,
+        missing: Vec<exhaustiveness::Witness>,
+    ) -> Self {
+        Error {
+            source: source.clone(),
+            position,
+            info: ErrorInfo::NonExhaustive { missing },
+        }
+    }
+
+    /// Construct an [`ErrorInfo::ConstructorArity`].
+    pub fn constructor_arity(
+        source: &Source,
+        position: usize,
+        expected: usize,
+        actual: usize,
+    ) -> Self {
+        Error {
+            source: source.clone(),
+            position,
+            info: ErrorInfo::ConstructorArity { expected, actual },
+        }
+    }
+
     /// Lift a [`unification::Error`].
     pub fn unification_error(source: &Source, position: usize, error: unification::Error) -> Self {
         Error {
@@ -107,8 +160,30 @@ impl Error {
             ErrorInfo::NotInScope { .. } => String::from("variable not in scope"),
             ErrorInfo::DuplicateArgument { .. } => String::from("duplicate argument"),
             ErrorInfo::RedundantPattern => String::from("redundant pattern"),
+            ErrorInfo::NonExhaustive { .. } => String::from("patterns are not exhaustive"),
             ErrorInfo::NotAValue { .. } => String::from("not a value"),
             ErrorInfo::NotAModule => String::from("not a module"),
+            ErrorInfo::NotExported => String::from("not exported by module"),
+            ErrorInfo::ConstructorArity { expected, actual } => format!(
+                "constructor expects {} argument{}, but got {}",
+                expected,
+                if *expected == 1 { "" } else { "s" },
+                actual
+            ),
+        }
+    }
+
+    pub fn addendum(&self) -> Option<String> {
+        match &self.info {
+            ErrorInfo::NonExhaustive { missing } => Some(missing.iter().fold(
+                String::from("Missing patterns:"),
+                |mut acc, witness| {
+                    acc.push_str("\n  ");
+                    acc.push_str(&witness.render());
+                    acc
+                },
+            )),
+            _ => None,
         }
     }
 }
@@ -127,8 +202,11 @@ pub enum InferredPattern {
         /// The variant's constructor name.
         ctor: Rc<str>,
 
-        /// The variant's argument name.
-        arg_name: Rc<str>,
+        /// The pattern that matches the variant's argument.
+        arg: Pattern<Expr>,
+
+        /// The variables bound by the variant's argument.
+        names: Vec<(Rc<str>, Type)>,
 
         /// The variant's argument type.
         arg_ty: Type,
@@ -136,6 +214,24 @@ pub enum InferredPattern {
         /// The row corresponding to the all the other constructors of the variant.
         rest: Type,
     },
+    Data {
+        /// The constructor's position in its data declaration.
+        tag: usize,
+
+        /**
+        The pattern that matches the constructor's fields.
+
+        A data constructor's argument is a record of its fields (see
+        [`DataDeclaration::constructor_body`]).
+        */
+        fields: Pattern<Expr>,
+
+        /// The variables bound by the constructor's fields.
+        names: Vec<(Rc<str>, Type)>,
+
+        /// The data type.
+        ty: Type,
+    },
 }
 
 impl InferredPattern {
@@ -149,22 +245,62 @@ impl InferredPattern {
                 vec![(ctor.clone(), arg_ty.clone())],
                 Some(rest.clone()),
             ),
+            InferredPattern::Data { ty, .. } => ty.clone(),
         }
     }
 
     pub fn pattern(&self) -> Pattern<Expr> {
         match self {
             InferredPattern::Any { pattern, .. } => pattern.clone(),
-            InferredPattern::Variant { tag, .. } => Pattern::Variant { tag: tag.clone() },
+            InferredPattern::Variant { tag, arg, .. } => Pattern::Variant {
+                tag: tag.clone(),
+                arg: Rc::new(arg.clone()),
+            },
+            InferredPattern::Data { tag, fields, .. } => {
+                Pattern::mk_variant(Expr::Int(*tag as i64), fields.clone())
+            }
         }
     }
 
     pub fn names(&self) -> Vec<(Rc<str>, Type)> {
         match self {
-            InferredPattern::Any { names, .. } => names.clone(),
-            InferredPattern::Variant {
-                arg_name, arg_ty, ..
-            } => vec![(arg_name.clone(), arg_ty.clone())],
+            InferredPattern::Any { names, .. }
+            | InferredPattern::Variant { names, .. }
+            | InferredPattern::Data { names, .. } => names.clone(),
+        }
+    }
+
+    /**
+    The variables that are in scope when this pattern is a function's argument.
+
+    See [`InferredPattern::mk_lam`].
+    */
+    pub fn arg_names(&self, common_kinds: &CommonKinds) -> Vec<(Rc<str>, Type)> {
+        match self.pattern() {
+            Pattern::Name | Pattern::Wildcard => self.names(),
+            _ => {
+                let mut names = vec![(Rc::from(ANONYMOUS_VARIABLE), self.ty(common_kinds))];
+                names.extend(self.names());
+                names
+            }
+        }
+    }
+
+    /**
+    Build a function whose argument is matched by this pattern.
+
+    An argument that isn't a name or a wildcard is bound to an anonymous variable, which is
+    then matched against the pattern. The function's body is in the scope of
+    [`InferredPattern::arg_names`].
+    */
+    pub fn mk_lam(&self, body: Expr) -> Expr {
+        match self.pattern() {
+            Pattern::Name => Expr::mk_lam(true, body),
+            Pattern::Wildcard => Expr::mk_lam(false, body),
+            pattern => Expr::mk_lam(
+                true,
+                Expr::mk_case(Expr::Var(0), vec![Branch { pattern, body }]),
+            ),
         }
     }
 }
@@ -178,35 +314,72 @@ pub enum CheckedPattern {
         /// Evidence for the variant's tag.
         tag: Rc<Expr>,
 
-        /// The variant's argument name.
-        arg_name: Rc<str>,
+        /// The pattern that matches the variant's argument.
+        arg: Pattern<Expr>,
 
-        /// The variant's argument type.
-        arg_ty: Type,
+        /// The variables bound by the variant's argument.
+        names: Vec<(Rc<str>, Type)>,
 
         /// The row corresponding to the all the other constructors of the variant.
         rest: Type,
     },
+    Data {
+        /// The constructor's position in its data declaration.
+        tag: usize,
+
+        /// The pattern that matches the constructor's fields.
+        fields: Pattern<Expr>,
+
+        /// The variables bound by the constructor's fields.
+        names: Vec<(Rc<str>, Type)>,
+    },
 }
 
 impl CheckedPattern {
     fn pattern(&self) -> Pattern<Expr> {
         match self {
             CheckedPattern::Any { pattern, .. } => pattern.clone(),
-            CheckedPattern::Variant { tag, .. } => Pattern::Variant { tag: tag.clone() },
+            CheckedPattern::Variant { tag, arg, .. } => Pattern::Variant {
+                tag: tag.clone(),
+                arg: Rc::new(arg.clone()),
+            },
+            CheckedPattern::Data { tag, fields, .. } => {
+                Pattern::mk_variant(Expr::Int(*tag as i64), fields.clone())
+            }
         }
     }
 
     fn names(&self) -> Vec<(Rc<str>, Type)> {
         match self {
-            CheckedPattern::Any { names, .. } => names.clone(),
-            CheckedPattern::Variant {
-                arg_name, arg_ty, ..
-            } => vec![(arg_name.clone(), arg_ty.clone())],
+            CheckedPattern::Any { names, .. }
+            | CheckedPattern::Variant { names, .. }
+            | CheckedPattern::Data { names, .. } => names.clone(),
+        }
+    }
+
+    /**
+    Whether matching this pattern removes its constructor from the type of the values that
+    are matched by later branches.
+
+    See [note: peeling constructors when matching on variants].
+    */
+    fn peels_constructor(&self) -> bool {
+        match self {
+            CheckedPattern::Any { .. } => false,
+            CheckedPattern::Variant { arg, .. } => arg.is_irrefutable(),
+            CheckedPattern::Data { fields, .. } => fields.is_irrefutable(),
         }
     }
 }
 
+/**
+The name given to variables that are introduced during type checking, such as a function
+argument that's matched against a pattern.
+
+It isn't a valid identifier, so it can't be referred to by a program.
+*/
+const ANONYMOUS_VARIABLE: &str = "<anonymous>";
+
 /**
 Type inference environment.
 
@@ -217,9 +390,15 @@ struct bundles all those arguments for convenience.
 pub struct Env<'a> {
     pub common_kinds: &'a CommonKinds,
     pub modules: &'a HashMap<ModuleId, HashMap<String, Signature>>,
+
+    /// The definitions that each module in [`Env::modules`] has but doesn't export.
+    pub unexported: &'a HashMap<ModuleId, HashSet<String>>,
     pub types: &'a HashMap<Rc<str>, Kind>,
     pub type_variables: &'a BoundVars<Kind>,
     pub type_signatures: &'a HashMap<String, Signature>,
+
+    /// The data declarations of the data constructors in scope, indexed by constructor name.
+    pub constructors: &'a HashMap<Rc<str>, DataDeclaration>,
     pub source: &'a Source,
 }
 
@@ -231,6 +410,14 @@ pub struct State {
     pub type_solutions: unification::Solutions,
     variables: BoundVars<Type>,
     pub evidence: Evidence,
+
+    /**
+    The operand types of arithmetic operations that weren't known when the operations were
+    inferred, along with the operations' positions.
+
+    See [`default_arithmetic_types`].
+    */
+    arithmetic_types: Vec<(usize, Type)>,
 }
 
 impl State {
@@ -240,6 +427,7 @@ impl State {
             type_solutions: unification::Solutions::new(),
             variables: BoundVars::new(),
             evidence: Evidence::new(),
+            arithmetic_types: Vec::new(),
         }
     }
 
@@ -372,7 +560,7 @@ fn infer_string_pattern(s: &Spanned<Rc<str>>) -> InferredPattern {
     }
 }
 
-fn infer_int_pattern(n: &Spanned<i32>) -> InferredPattern {
+fn infer_int_pattern(n: &Spanned<i64>) -> InferredPattern {
     InferredPattern::Any {
         pattern: Pattern::Int(n.item),
         names: Vec::new(),
@@ -389,90 +577,172 @@ fn infer_char_pattern(c: &Spanned<char>) -> InferredPattern {
 }
 
 fn infer_record_pattern(
-    common_kinds: &CommonKinds,
-    type_solutions: &mut unification::Solutions,
-    evidence: &mut Evidence,
-    names: &[Spanned<Rc<str>>],
+    env: Env,
+    state: &mut State,
+    fields: &[(Spanned<Rc<str>>, Spanned<syntax::Pattern>)],
     rest: Option<&Spanned<Rc<str>>>,
-) -> InferredPattern {
-    let mut names_to_positions: FnvHashMap<&str, usize> =
-        FnvHashMap::with_capacity_and_hasher(names.len(), Default::default());
-    let entire_row = {
-        let fields = names
+) -> Result<InferredPattern, Error> {
+    let fields: Vec<(&Spanned<Rc<str>>, InferredPattern)> = fields
+        .iter()
+        .map(|(name, pattern)| Ok((name, infer_pattern(env, state, pattern)?)))
+        .collect::<Result<_, _>>()?;
+    let field_tys: Vec<(Rc<str>, Type)> = fields
+        .iter()
+        .map(|(name, pattern)| (name.item.clone(), pattern.ty(env.common_kinds)))
+        .collect();
+    let rest_row = rest.map(|_| state.fresh_type_meta(Kind::Row));
+
+    let mut pattern_fields: Vec<(Expr, Pattern<Expr>)> = Vec::with_capacity(fields.len());
+    let mut names: Vec<(Rc<str>, Type)> = Vec::new();
+    for (index, (name, pattern)) in fields.iter().enumerate() {
+        /*
+        Each field's index is its position in the entire record, so its evidence is
+        found using all the other fields.
+        */
+        let other_fields = field_tys
             .iter()
-            .map(|name| {
-                let name_item_ref = name.item.as_ref();
-                names_to_positions.insert(name_item_ref, name.pos);
-                (
-                    Rc::from(name_item_ref),
-                    fresh_type_meta(type_solutions, Kind::Type),
-                )
-            })
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .map(|(_, field)| field.clone())
             .collect();
-        let rest = rest.map(|_| fresh_type_meta(type_solutions, Kind::Row));
-        Type::mk_rows(fields, rest)
-    };
-
-    let (names, names_tys): (Vec<Expr>, Vec<(Rc<str>, Type)>) = {
-        let mut names: Vec<Expr> = Vec::with_capacity(names.len());
-        let mut names_tys: Vec<(Rc<str>, Type)> = Vec::with_capacity(names.len());
-
-        let mut row: &Type = &entire_row;
-        while let Type::RowCons(field, ty, rest) = row {
-            names.push(Expr::Placeholder(evidence.placeholder(
-                names_to_positions.get(field.as_ref()).copied().unwrap_or(0),
+        pattern_fields.push((
+            Expr::Placeholder(state.evidence.placeholder(
+                name.pos,
                 evidence::Constraint::HasField {
-                    field: field.clone(),
-                    rest: (**rest).clone(),
+                    field: name.item.clone(),
+                    rest: Type::mk_rows(other_fields, rest_row.clone()),
                 },
-            )));
-            names_tys.push((field.clone(), (**ty).clone()));
-            row = rest.as_ref();
-        }
-        if let Some(rest) = rest {
-            names_tys.push((
-                Rc::from(rest.item.as_ref()),
-                Type::app(Type::mk_record_ctor(common_kinds), row.clone()),
-            ));
-        }
-
-        (names, names_tys)
-    };
+            )),
+            pattern.pattern(),
+        ));
+        names.extend(pattern.names());
+    }
+    if let Some(rest) = rest {
+        names.push((
+            Rc::from(rest.item.as_ref()),
+            Type::app(
+                Type::mk_record_ctor(env.common_kinds),
+                rest_row.clone().unwrap_or(Type::RowNil),
+            ),
+        ));
+    }
 
-    InferredPattern::Any {
+    let entire_row = Type::mk_rows(field_tys, rest_row);
+    Ok(InferredPattern::Any {
         pattern: Pattern::Record {
-            names,
+            fields: pattern_fields,
             rest: rest.is_some(),
         },
-        names: names_tys,
-        ty: Type::app(Type::mk_record_ctor(common_kinds), entire_row),
+        names,
+        ty: Type::app(Type::mk_record_ctor(env.common_kinds), entire_row),
+    })
+}
+
+fn infer_array_pattern(
+    env: Env,
+    state: &mut State,
+    items: &[Spanned<syntax::Pattern>],
+) -> Result<InferredPattern, Error> {
+    let item_ty = state.fresh_type_meta(Kind::Type);
+
+    let mut patterns: Vec<Pattern<Expr>> = Vec::with_capacity(items.len());
+    let mut names: Vec<(Rc<str>, Type)> = Vec::new();
+    for item in items {
+        let result = infer_pattern(env, state, item)?;
+        unify(env, state, item.pos, &item_ty, &result.ty(env.common_kinds))?;
+        patterns.push(result.pattern());
+        names.extend(result.names());
     }
+
+    Ok(InferredPattern::Any {
+        pattern: Pattern::Array(patterns),
+        names,
+        ty: Type::app(Type::mk_array(env.common_kinds), item_ty),
+    })
 }
 
 fn infer_variant_pattern(
-    type_solutions: &mut unification::Solutions,
-    evidence: &mut Evidence,
+    env: Env,
+    state: &mut State,
     pos: usize,
     ctor: &str,
-    arg: &Spanned<Rc<str>>,
-) -> InferredPattern {
+    args: &[Spanned<syntax::Pattern>],
+) -> Result<InferredPattern, Error> {
+    let arg = match args {
+        [arg] => arg,
+        _ => return Err(Error::constructor_arity(env.source, pos, 1, args.len())),
+    };
+    let arg = infer_pattern(env, state, arg)?;
     let ctor: Rc<str> = Rc::from(ctor);
-    let arg_ty = fresh_type_meta(type_solutions, Kind::Type);
-    let rest_row = fresh_type_meta(type_solutions, Kind::Row);
-    let tag = Expr::Placeholder(evidence.placeholder(
+    let rest_row = state.fresh_type_meta(Kind::Row);
+    let tag = Expr::Placeholder(state.evidence.placeholder(
         pos,
         evidence::Constraint::HasField {
             field: ctor.clone(),
             rest: rest_row.clone(),
         },
     ));
-    InferredPattern::Variant {
+    Ok(InferredPattern::Variant {
         tag: Rc::new(tag),
         ctor,
-        arg_name: Rc::from(arg.item.as_ref()),
-        arg_ty,
+        arg: arg.pattern(),
+        names: arg.names(),
+        arg_ty: arg.ty(env.common_kinds),
         rest: rest_row,
+    })
+}
+
+fn infer_data_pattern(
+    env: Env,
+    state: &mut State,
+    pos: usize,
+    data_decl: &DataDeclaration,
+    ctor: &str,
+    args: &[Spanned<syntax::Pattern>],
+) -> Result<InferredPattern, Error> {
+    let (tag, constructor) = data_decl
+        .get_constructor(ctor)
+        .unwrap_or_else(|| panic!("{:?} is not a constructor of {:?}", ctor, data_decl.name));
+
+    if args.len() != constructor.fields.len() {
+        return Err(Error::constructor_arity(
+            env.source,
+            pos,
+            constructor.fields.len(),
+            args.len(),
+        ));
     }
+
+    let metas: Vec<Type> = data_decl
+        .args
+        .iter()
+        .map(|(_, kind)| state.fresh_type_meta(kind.clone()))
+        .collect();
+
+    let mut fields: Vec<(Expr, Pattern<Expr>)> = Vec::with_capacity(args.len());
+    let mut names: Vec<(Rc<str>, Type)> = Vec::new();
+    for (index, (arg, field)) in args.iter().zip(constructor.fields.iter()).enumerate() {
+        let result = infer_pattern(env, state, arg)?;
+        unify(
+            env,
+            state,
+            arg.pos,
+            &field.instantiate_many(&metas),
+            &result.ty(env.common_kinds),
+        )?;
+        fields.push((Expr::Int(index as i64), result.pattern()));
+        names.extend(result.names());
+    }
+
+    Ok(InferredPattern::Data {
+        tag,
+        fields: Pattern::Record {
+            fields,
+            rest: false,
+        },
+        names,
+        ty: data_decl.ty().instantiate_many(&metas),
+    })
 }
 
 fn infer_wildcard_pattern(type_solutions: &mut unification::Solutions) -> InferredPattern {
@@ -488,28 +758,22 @@ pub fn infer_pattern(
     env: Env,
     state: &mut State,
     pattern: &Spanned<syntax::Pattern>,
-) -> InferredPattern {
-    match &pattern.item {
+) -> Result<InferredPattern, Error> {
+    Ok(match &pattern.item {
         syntax::Pattern::Name(name) => infer_name_pattern(&mut state.type_solutions, name),
-        syntax::Pattern::Record { names, rest } => infer_record_pattern(
-            env.common_kinds,
-            &mut state.type_solutions,
-            &mut state.evidence,
-            names,
-            rest.as_ref(),
-        ),
-        syntax::Pattern::Variant { name, arg } => infer_variant_pattern(
-            &mut state.type_solutions,
-            &mut state.evidence,
-            pattern.pos,
-            name,
-            arg,
-        ),
+        syntax::Pattern::Record { fields, rest } => {
+            infer_record_pattern(env, state, fields, rest.as_ref())?
+        }
+        syntax::Pattern::Variant { name, args } => match env.constructors.get(name) {
+            Some(data_decl) => infer_data_pattern(env, state, pattern.pos, data_decl, name, args)?,
+            None => infer_variant_pattern(env, state, pattern.pos, name, args)?,
+        },
+        syntax::Pattern::Array(items) => infer_array_pattern(env, state, items)?,
         syntax::Pattern::Char(c) => infer_char_pattern(c),
         syntax::Pattern::Int(n) => infer_int_pattern(n),
         syntax::Pattern::String(s) => infer_string_pattern(s),
         syntax::Pattern::Wildcard => infer_wildcard_pattern(&mut state.type_solutions),
-    }
+    })
 }
 
 pub fn check_pattern(
@@ -518,36 +782,27 @@ pub fn check_pattern(
     pattern: &Spanned<syntax::Pattern>,
     expected: &Type,
 ) -> Result<CheckedPattern, Error> {
-    let result = infer_pattern(env, state, pattern);
+    let result = infer_pattern(env, state, pattern)?;
 
     let actual = result.ty(env.common_kinds);
-    unification::unify(
-        unification::Env {
-            common_kinds: env.common_kinds,
-            types: env.types,
-            type_variables: env.type_variables,
-        },
-        &mut state.kind_inference_state,
-        &mut state.type_solutions,
-        pattern.pos,
-        expected,
-        &actual,
-    )
-    .map_err(|error| Error::unification_error(env.source, pattern.pos, error))?;
+    unify(env, state, pattern.pos, expected, &actual)?;
     Ok(match result {
         InferredPattern::Any { pattern, names, .. } => CheckedPattern::Any { pattern, names },
         InferredPattern::Variant {
             tag,
-            arg_name,
-            arg_ty,
+            arg,
+            names,
             rest,
             ..
         } => CheckedPattern::Variant {
             tag,
-            arg_name,
-            arg_ty,
+            arg,
+            names,
             rest,
         },
+        InferredPattern::Data {
+            tag, fields, names, ..
+        } => CheckedPattern::Data { tag, fields, names },
     })
 }
 
@@ -613,7 +868,18 @@ pub fn infer(
             let definitions = lookup_path(env.source, definitions, path)?;
 
             match definitions.get(&item.item) {
-                None => Err(Error::not_in_scope(env.source, item.pos, &item.item)),
+                None => match id {
+                    syntax::ModuleRef::Id(id)
+                        if path.is_empty()
+                            && env
+                                .unexported
+                                .get(id)
+                                .map_or(false, |names| names.contains(&item.item)) =>
+                    {
+                        Err(Error::not_exported(env.source, item.pos))
+                    }
+                    _ => Err(Error::not_in_scope(env.source, item.pos, &item.item)),
+                },
                 Some(signature) => match signature {
                     Signature::TypeSig(type_signature) => Ok(state.instantiate(
                         expr.pos,
@@ -644,6 +910,7 @@ pub fn infer(
         }
 
         syntax::Expr::Int(n) => Ok((Expr::Int(*n), Type::Int)),
+        syntax::Expr::Float(n) => Ok((Expr::Float(*n), Type::Float)),
         syntax::Expr::Char(c) => Ok((Expr::Char(*c), Type::Char)),
         syntax::Expr::Unit => Ok((Expr::Unit, Type::Unit)),
         syntax::Expr::Cmd(cmd_parts) => {
@@ -651,6 +918,8 @@ pub fn infer(
                 .iter()
                 .map(|cmd_part| match cmd_part {
                     syntax::CmdPart::Literal(value) => Ok(CmdPart::Literal(value.clone())),
+                    syntax::CmdPart::Pipe => Ok(CmdPart::Pipe),
+                    syntax::CmdPart::Redirect(redirect) => Ok(CmdPart::Redirect(*redirect)),
                     syntax::CmdPart::Expr(expr) => check(
                         env,
                         state,
@@ -690,25 +959,15 @@ pub fn infer(
         }
 
         syntax::Expr::Binop(op, left, right) => match op.item {
-            syntax::Binop::Add => {
-                let left = check(env, state, left, &Type::Int)?;
-                let right = check(env, state, right, &Type::Int)?;
-                Ok((Expr::mk_binop(Binop::Add, left, right), Type::Int))
-            }
+            syntax::Binop::Add => infer_arithmetic(env, state, op.pos, Binop::Add, left, right),
             syntax::Binop::Multiply => {
-                let left = check(env, state, left, &Type::Int)?;
-                let right = check(env, state, right, &Type::Int)?;
-                Ok((Expr::mk_binop(Binop::Multiply, left, right), Type::Int))
+                infer_arithmetic(env, state, op.pos, Binop::Multiply, left, right)
             }
             syntax::Binop::Subtract => {
-                let left = check(env, state, left, &Type::Int)?;
-                let right = check(env, state, right, &Type::Int)?;
-                Ok((Expr::mk_binop(Binop::Subtract, left, right), Type::Int))
+                infer_arithmetic(env, state, op.pos, Binop::Subtract, left, right)
             }
             syntax::Binop::Divide => {
-                let left = check(env, state, left, &Type::Int)?;
-                let right = check(env, state, right, &Type::Int)?;
-                Ok((Expr::mk_binop(Binop::Divide, left, right), Type::Int))
+                infer_arithmetic(env, state, op.pos, Binop::Divide, left, right)
             }
             syntax::Binop::Append => {
                 let item_ty = fresh_type_meta(&mut state.type_solutions, Kind::Type);
@@ -757,7 +1016,8 @@ pub fn infer(
             | syntax::Binop::Gte
             | syntax::Binop::Lt
             | syntax::Binop::Lte => panic!("overloaded binop not desugared: {:?}", op.item),
-        },
+        }
+        .map(|(expr, ty)| (Expr::mk_located(op.pos, expr), ty)),
 
         syntax::Expr::App(fun, arg) => {
             let in_ty = fresh_type_meta(&mut state.type_solutions, Kind::Type);
@@ -769,19 +1029,18 @@ pub fn infer(
                 &Type::mk_arrow(env.common_kinds, &in_ty, &out_ty),
             )?;
             let arg = check(env, state, arg, &in_ty)?;
-            Ok((Expr::mk_app(fun, arg), out_ty))
+            Ok((Expr::mk_located(expr.pos, Expr::mk_app(fun, arg)), out_ty))
         }
         syntax::Expr::Lam { args, body } => {
             check_duplicate_args(env.source, args)?;
 
-            let mut inferred_args: Vec<(Pattern<Expr>, Type)> = Vec::with_capacity(args.len());
-            let bound_variables: Vec<(Rc<str>, Type)> = args
+            let inferred_args: Vec<InferredPattern> = args
                 .iter()
-                .flat_map(|arg| {
-                    let result = infer_pattern(env, state, arg);
-                    inferred_args.push((result.pattern(), result.ty(env.common_kinds)));
-                    result.names().into_iter()
-                })
+                .map(|arg| infer_pattern(env, state, arg))
+                .collect::<Result<_, _>>()?;
+            let bound_variables: Vec<(Rc<str>, Type)> = inferred_args
+                .iter()
+                .flat_map(|arg| arg.arg_names(env.common_kinds).into_iter())
                 .collect();
 
             state.variables.insert(&bound_variables);
@@ -790,25 +1049,9 @@ pub fn infer(
 
             let (expr, ty) = inferred_args.into_iter().rev().fold(
                 (body, body_ty),
-                |(body, body_ty), (arg_pattern, arg_ty)| {
-                    let body = match arg_pattern {
-                        Pattern::Name => Expr::mk_lam(true, body),
-                        Pattern::Char(_)
-                        | Pattern::Int(_)
-                        | Pattern::String(_)
-                        | Pattern::Record { .. }
-                        | Pattern::Variant { .. } => Expr::mk_lam(
-                            true,
-                            Expr::mk_case(
-                                Expr::Var(0),
-                                vec![Branch {
-                                    pattern: arg_pattern,
-                                    body,
-                                }],
-                            ),
-                        ),
-                        Pattern::Wildcard => Expr::mk_lam(false, body),
-                    };
+                |(body, body_ty), inferred_arg| {
+                    let body = inferred_arg.mk_lam(body);
+                    let arg_ty = inferred_arg.ty(env.common_kinds);
                     let body_ty = Type::arrow(env.common_kinds, arg_ty, body_ty);
 
                     (body, body_ty)
@@ -863,13 +1106,24 @@ pub fn infer(
             let mut expr_fields: Vec<(Expr, Expr)> = Vec::with_capacity(fields.len());
             let mut ty_fields: Vec<(Rc<str>, Type)> = Vec::with_capacity(fields.len());
 
+            let extends_rest = rest.is_some();
             let mut row = &entire_row;
             while let Type::RowCons(field, ty, rest) = row {
+                /*
+                A record with a rest is built by extending the rest with each field in turn, so
+                a field's index is relative to the fields that follow it. Otherwise the fields are
+                laid out all at once, and each field's index is its position in the entire record.
+                */
+                let other_fields = if extends_rest {
+                    (**rest).clone()
+                } else {
+                    Type::mk_rows(ty_fields.clone(), Some((**rest).clone()))
+                };
                 let field_index = Expr::Placeholder(state.evidence.placeholder(
                     field_to_pos.get(field.as_ref()).copied().unwrap_or(0),
                     evidence::Constraint::HasField {
                         field: field.clone(),
-                        rest: (**rest).clone(),
+                        rest: other_fields,
                     },
                 ));
                 let field_expr = field_to_expr.remove(field.as_ref()).unwrap();
@@ -948,6 +1202,20 @@ pub fn infer(
             let pos = constructor.pos;
             let constructor: Rc<str> = Rc::from(constructor.item.as_str());
 
+            if let Some(data_decl) = env.constructors.get(&constructor) {
+                let (tag, _) = data_decl.get_constructor(&constructor).unwrap_or_else(|| {
+                    panic!(
+                        "{:?} is not a constructor of {:?}",
+                        constructor, data_decl.name
+                    )
+                });
+                return Ok(state.instantiate(
+                    pos,
+                    data_decl.constructor_body(tag),
+                    &data_decl.constructor_signature(env.common_kinds, tag),
+                ));
+            }
+
             let rest_row = fresh_type_meta(&mut state.type_solutions, Kind::Row);
             let placeholder = Expr::Placeholder(state.evidence.placeholder(
                 pos,
@@ -999,7 +1267,8 @@ pub fn infer(
                 ),
             ))
         }
-        syntax::Expr::Case(expr, branches) => infer_case(env, state, expr, branches),
+        syntax::Expr::Case(scrutinee, branches) => infer_case(env, state, scrutinee, branches)
+            .map(|(case, ty)| (Expr::mk_located(expr.pos, case), ty)),
 
         syntax::Expr::Comp(_) => {
             panic!("computation expression was not desugared")
@@ -1015,6 +1284,9 @@ fn infer_case(
 ) -> Result<(Expr, Type), Error> {
     let position = expr.pos;
     let (expr, mut expr_ty) = infer(env, state, expr)?;
+    let scrutinee_ty = expr_ty.clone();
+    let patterns: Vec<&syntax::Pattern> =
+        branches.iter().map(|branch| &branch.pattern.item).collect();
 
     /*
     [note: peeling constructors when matching on variants]
@@ -1087,24 +1359,74 @@ fn infer_case(
     The interpreter needs to account for this when checking pattern matches.
     */
 
+    /*
+    [note: matching on data types]
+
+    Data constructor tags are positions in the data declaration, and they're peeled the same
+    way as variant tags. Given `type Tree a = Leaf | Node (Tree a) a (Tree a)`,
+
+    ```
+    case tree of
+      # Node's tag is 1
+      Node l x r -> ...
+
+      # Leaf's tag is 0
+      Leaf -> ...
+    ```
+
+    but
+
+    ```
+    case tree of
+      # Leaf's tag is 0
+      Leaf -> ...
+
+      # Node's tag is 0 (because `Leaf` has been peeled off)
+      Node l x r -> ...
+    ```
+
+    The interpreter rebuilds a peeled variant when it's bound by a catch-all name pattern,
+    which would give the data value the wrong tag. When a case on a data type has a
+    catch-all name pattern, the scrutinee is bound to an anonymous variable and the name
+    pattern refers to that instead.
+    */
+    let data_decl: Option<&DataDeclaration> =
+        branches
+            .iter()
+            .find_map(|branch| match &branch.pattern.item {
+                syntax::Pattern::Variant { name, .. } => env.constructors.get(name),
+                _ => None,
+            });
+    let binds_scrutinee = data_decl.is_some()
+        && branches
+            .iter()
+            .any(|branch| matches!(branch.pattern.item, syntax::Pattern::Name(_)));
+    let scrutinee_variable = [(Rc::from(ANONYMOUS_VARIABLE), expr_ty.clone())];
+    if binds_scrutinee {
+        state.variables.insert(&scrutinee_variable);
+    }
+
     let out_ty = fresh_type_meta(&mut state.type_solutions, Kind::Type);
     let mut seen_ctors = FnvHashSet::default();
+    let mut seen_data_tags: Vec<usize> = Vec::new();
     let mut saw_catchall = false;
-    let branches: Vec<Branch<Expr>> = branches
+    let branches: Result<Vec<Branch<Expr>>, Error> = branches
         .iter()
         .map(|branch| {
             if pattern_is_redundant(&seen_ctors, saw_catchall, &branch.pattern.item) {
                 return Err(Error::redundant_pattern(env.source, branch.pattern.pos));
             }
 
-            if let syntax::Pattern::Variant { name, .. } = &branch.pattern.item {
-                seen_ctors.insert(name.as_ref());
-            }
-
             let result = check_pattern(env, state, &branch.pattern, &expr_ty)?;
 
-            if let CheckedPattern::Variant { rest, .. } = &result {
-                expr_ty = Type::app(Type::mk_variant_ctor(env.common_kinds), rest.clone())
+            let peels_constructor = result.peels_constructor();
+            if peels_constructor {
+                if let syntax::Pattern::Variant { name, .. } = &branch.pattern.item {
+                    seen_ctors.insert(name.as_ref());
+                }
+                if let CheckedPattern::Variant { rest, .. } = &result {
+                    expr_ty = Type::app(Type::mk_variant_ctor(env.common_kinds), rest.clone())
+                }
             }
 
             let names = result.names();
@@ -1112,14 +1434,36 @@ fn infer_case(
             let body = check(env, state, &branch.body, &out_ty)?;
             state.variables.delete(names.len());
 
-            let pattern = result.pattern();
-            if let Pattern::Wildcard | Pattern::Name = pattern {
+            let (pattern, body) = match result {
+                CheckedPattern::Data { tag, fields, .. } => {
+                    let peeled_tag =
+                        tag - seen_data_tags.iter().filter(|seen| **seen < tag).count();
+                    if peels_constructor {
+                        seen_data_tags.push(tag);
+                    }
+                    (
+                        Pattern::mk_variant(Expr::Int(peeled_tag as i64), fields),
+                        body,
+                    )
+                }
+                CheckedPattern::Any {
+                    pattern: Pattern::Name,
+                    ..
+                } if binds_scrutinee => (Pattern::Wildcard, Expr::mk_let(Expr::Var(0), body)),
+                result => (result.pattern(), body),
+            };
+            if pattern.is_irrefutable() {
                 saw_catchall = true;
             }
 
             Ok(Branch { pattern, body })
         })
-        .collect::<Result<_, _>>()?;
+        .collect();
+
+    if binds_scrutinee {
+        state.variables.delete(scrutinee_variable.len());
+    }
+    let branches = branches?;
 
     state.zonk_type_mut(&mut expr_ty);
     match expr_ty.unwrap_variant() {
@@ -1146,20 +1490,92 @@ fn infer_case(
         _ => Ok(()),
     }?;
 
-    Ok((Expr::mk_case(expr, branches), out_ty))
+    let missing = exhaustiveness::missing_patterns(env, state, position, &scrutinee_ty, &patterns)?;
+    if !missing.is_empty() {
+        return Err(Error::non_exhaustive(env.source, position, missing));
+    }
+
+    Ok((
+        if binds_scrutinee {
+            Expr::mk_let(expr, Expr::mk_case(Expr::Var(0), branches))
+        } else {
+            Expr::mk_case(expr, branches)
+        },
+        out_ty,
+    ))
 }
 
-/// Check an expression's type.
-pub fn check(
+/**
+Infer the type of an arithmetic operation.
+
+Arithmetic works on `Int`s and `Float`s. Both operands are `Float`s when either operand is
+known to be a `Float`, and `Int`s when either is known to be anything else. When neither
+operand's type is known yet, the decision is left to [`default_arithmetic_types`].
+*/
+fn infer_arithmetic(
     env: Env,
     state: &mut State,
-    expr: &Spanned<syntax::Expr>,
-    expected: &Type,
-) -> Result<Expr, Error> {
-    let position = expr.pos;
-    let (expr, expr_ty) = infer(env, state, expr)?;
+    position: usize,
+    op: Binop,
+    left: &Spanned<syntax::Expr>,
+    right: &Spanned<syntax::Expr>,
+) -> Result<(Expr, Type), Error> {
+    let (left_expr, left_ty) = infer(env, state, left)?;
+    let (right_expr, right_ty) = infer(env, state, right)?;
+
+    let ty = match (
+        state.zonk_type(left_ty.clone()),
+        state.zonk_type(right_ty.clone()),
+    ) {
+        (Type::Float, _) | (_, Type::Float) => Type::Float,
+        (Type::Meta(_, _), Type::Meta(_, _)) => {
+            state.arithmetic_types.push((position, left_ty.clone()));
+            left_ty.clone()
+        }
+        _ => Type::Int,
+    };
 
-    let actual = expr_ty;
+    unify(env, state, left.pos, &ty, &left_ty)?;
+    unify(env, state, right.pos, &ty, &right_ty)?;
+    Ok((Expr::mk_binop(op, left_expr, right_expr), ty))
+}
+
+/**
+Decide the operand types of the arithmetic operations whose operand types weren't known
+during inference.
+
+Operands that are still of unknown type default to `Int`. This should be called once
+everything about an expression's type is known, before its evidence is solved.
+*/
+pub fn default_arithmetic_types(
+    env: unification::Env,
+    state: &mut State,
+    source: &Source,
+) -> Result<(), Error> {
+    for (position, ty) in std::mem::take(&mut state.arithmetic_types) {
+        if !matches!(state.zonk_type(ty.clone()), Type::Float) {
+            unification::unify(
+                env,
+                &mut state.kind_inference_state,
+                &mut state.type_solutions,
+                position,
+                &Type::Int,
+                &ty,
+            )
+            .map_err(|error| Error::unification_error(source, position, error))?;
+        }
+    }
+    Ok(())
+}
+
+/// Unify an expression's type with the type it was expected to have.
+fn unify(
+    env: Env,
+    state: &mut State,
+    position: usize,
+    expected: &Type,
+    actual: &Type,
+) -> Result<(), Error> {
     unification::unify(
         unification::Env {
             common_kinds: env.common_kinds,
@@ -1170,9 +1586,20 @@ pub fn check(
         &mut state.type_solutions,
         position,
         expected,
-        &actual,
+        actual,
     )
-    .map_err(|error| Error::unification_error(env.source, position, error))?;
+    .map_err(|error| Error::unification_error(env.source, position, error))
+}
 
+/// Check an expression's type.
+pub fn check(
+    env: Env,
+    state: &mut State,
+    expr: &Spanned<syntax::Expr>,
+    expected: &Type,
+) -> Result<Expr, Error> {
+    let position = expr.pos;
+    let (expr, expr_ty) = infer(env, state, expr)?;
+    unify(env, state, position, expected, &expr_ty)?;
     Ok(expr)
 }
@@ -1,9 +1,9 @@
-use super::{Env, Error, InferredPattern, State};
+use super::{exhaustiveness::Witness, Env, Error, InferredPattern, State};
 use crate::{
-    type_inference::{infer, infer_pattern, unification},
+    type_inference::{default_arithmetic_types, infer, infer_pattern, unification},
     BoundVars,
 };
-use ipso_core::{Branch, CommonKinds, Expr, Pattern, Type};
+use ipso_core::{Binop, Branch, CommonKinds, Expr, Float, Pattern, Type};
 use ipso_diagnostic::Source;
 use ipso_syntax::{self as syntax, kind::Kind, Spanned};
 use std::{collections::HashMap, rc::Rc};
@@ -21,12 +21,15 @@ fn with_type_variables_env_and_state<A, F: FnOnce(Env, &mut State) -> A>(
     let modules = HashMap::new();
     let types = HashMap::new();
     let type_signatures = HashMap::new();
+    let constructors = HashMap::new();
     let env = Env {
         common_kinds: &common_kinds,
         modules: &modules,
+        unexported: &Default::default(),
         types: &types,
         type_variables: &type_variables,
         type_signatures: &type_signatures,
+        constructors: &constructors,
         source: &source,
     };
     let mut state = State::new();
@@ -87,11 +90,11 @@ fn infer_pattern_1() {
         };
         assert_eq!(
             infer_pattern(env, state, &pattern),
-            InferredPattern::Any {
+            Ok(InferredPattern::Any {
                 pattern: Pattern::Name,
                 names: vec![(Rc::from("x"), Type::Meta(Kind::Type, 0))],
                 ty: Type::Meta(Kind::Type, 0),
-            }
+            })
         )
     })
 }
@@ -102,29 +105,56 @@ fn infer_pattern_2() {
         let pat = syntax::Spanned {
             pos: 0,
             item: syntax::Pattern::Record {
-                names: vec![
-                    syntax::Spanned {
-                        pos: 0,
-                        item: Rc::from("x"),
-                    },
-                    syntax::Spanned {
-                        pos: 2,
-                        item: Rc::from("y"),
-                    },
-                    syntax::Spanned {
-                        pos: 4,
-                        item: Rc::from("z"),
-                    },
+                fields: vec![
+                    (
+                        syntax::Spanned {
+                            pos: 0,
+                            item: Rc::from("x"),
+                        },
+                        syntax::Spanned {
+                            pos: 0,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 0,
+                                item: Rc::from("x"),
+                            }),
+                        },
+                    ),
+                    (
+                        syntax::Spanned {
+                            pos: 2,
+                            item: Rc::from("y"),
+                        },
+                        syntax::Spanned {
+                            pos: 2,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 2,
+                                item: Rc::from("y"),
+                            }),
+                        },
+                    ),
+                    (
+                        syntax::Spanned {
+                            pos: 4,
+                            item: Rc::from("z"),
+                        },
+                        syntax::Spanned {
+                            pos: 4,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 4,
+                                item: Rc::from("z"),
+                            }),
+                        },
+                    ),
                 ],
                 rest: None,
             },
         };
         let expected = InferredPattern::Any {
             pattern: Pattern::Record {
-                names: vec![
-                    Expr::mk_placeholder(0),
-                    Expr::mk_placeholder(1),
-                    Expr::mk_placeholder(2),
+                fields: vec![
+                    (Expr::mk_placeholder(0), Pattern::Name),
+                    (Expr::mk_placeholder(1), Pattern::Name),
+                    (Expr::mk_placeholder(2), Pattern::Name),
                 ],
                 rest: false,
             },
@@ -143,7 +173,7 @@ fn infer_pattern_2() {
                 (Rc::from("z"), Type::Meta(Kind::Type, 2)),
             ],
         };
-        let actual = infer_pattern(env, state, &pat);
+        let actual = infer_pattern(env, state, &pat).unwrap();
         assert_eq!(expected, actual)
     })
 }
@@ -154,19 +184,46 @@ fn infer_pattern_3() {
         let pat = syntax::Spanned {
             pos: 0,
             item: syntax::Pattern::Record {
-                names: vec![
-                    syntax::Spanned {
-                        pos: 0,
-                        item: Rc::from("x"),
-                    },
-                    syntax::Spanned {
-                        pos: 2,
-                        item: Rc::from("y"),
-                    },
-                    syntax::Spanned {
-                        pos: 4,
-                        item: Rc::from("z"),
-                    },
+                fields: vec![
+                    (
+                        syntax::Spanned {
+                            pos: 0,
+                            item: Rc::from("x"),
+                        },
+                        syntax::Spanned {
+                            pos: 0,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 0,
+                                item: Rc::from("x"),
+                            }),
+                        },
+                    ),
+                    (
+                        syntax::Spanned {
+                            pos: 2,
+                            item: Rc::from("y"),
+                        },
+                        syntax::Spanned {
+                            pos: 2,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 2,
+                                item: Rc::from("y"),
+                            }),
+                        },
+                    ),
+                    (
+                        syntax::Spanned {
+                            pos: 4,
+                            item: Rc::from("z"),
+                        },
+                        syntax::Spanned {
+                            pos: 4,
+                            item: syntax::Pattern::Name(syntax::Spanned {
+                                pos: 4,
+                                item: Rc::from("z"),
+                            }),
+                        },
+                    ),
                 ],
                 rest: Some(syntax::Spanned {
                     pos: 6,
@@ -176,10 +233,10 @@ fn infer_pattern_3() {
         };
         let expected = InferredPattern::Any {
             pattern: Pattern::Record {
-                names: vec![
-                    Expr::mk_placeholder(0),
-                    Expr::mk_placeholder(1),
-                    Expr::mk_placeholder(2),
+                fields: vec![
+                    (Expr::mk_placeholder(0), Pattern::Name),
+                    (Expr::mk_placeholder(1), Pattern::Name),
+                    (Expr::mk_placeholder(2), Pattern::Name),
                 ],
                 rest: true,
             },
@@ -202,7 +259,7 @@ fn infer_pattern_3() {
                 ),
             ],
         };
-        let actual = infer_pattern(env, state, &pat);
+        let actual = infer_pattern(env, state, &pat).unwrap();
         assert_eq!(expected, actual)
     })
 }
@@ -214,20 +271,24 @@ fn infer_pattern_4() {
             pos: 0,
             item: syntax::Pattern::Variant {
                 name: Rc::from("just"),
-                arg: syntax::Spanned {
+                args: vec![syntax::Spanned {
                     pos: 5,
-                    item: Rc::from("x"),
-                },
+                    item: syntax::Pattern::Name(syntax::Spanned {
+                        pos: 5,
+                        item: Rc::from("x"),
+                    }),
+                }],
             },
         };
         let expected = InferredPattern::Variant {
             tag: Rc::new(Expr::mk_placeholder(0)),
             ctor: Rc::from("just"),
-            arg_name: Rc::from("x"),
+            arg: Pattern::Name,
+            names: vec![(Rc::from("x"), Type::Meta(Kind::Type, 0))],
             arg_ty: Type::Meta(Kind::Type, 0),
             rest: Type::Meta(Kind::Row, 1),
         };
-        let actual = infer_pattern(env, state, &pat);
+        let actual = infer_pattern(env, state, &pat).unwrap();
         assert_eq!(expected, actual)
     })
 }
@@ -275,15 +336,33 @@ fn infer_lam_2() {
                 vec![syntax::Spanned {
                     pos: 1,
                     item: syntax::Pattern::Record {
-                        names: vec![
-                            syntax::Spanned {
-                                pos: 2,
-                                item: Rc::from("x"),
-                            },
-                            syntax::Spanned {
-                                pos: 5,
-                                item: Rc::from("y"),
-                            },
+                        fields: vec![
+                            (
+                                syntax::Spanned {
+                                    pos: 2,
+                                    item: Rc::from("x"),
+                                },
+                                syntax::Spanned {
+                                    pos: 2,
+                                    item: syntax::Pattern::Name(syntax::Spanned {
+                                        pos: 2,
+                                        item: Rc::from("x"),
+                                    }),
+                                },
+                            ),
+                            (
+                                syntax::Spanned {
+                                    pos: 5,
+                                    item: Rc::from("y"),
+                                },
+                                syntax::Spanned {
+                                    pos: 5,
+                                    item: syntax::Pattern::Name(syntax::Spanned {
+                                        pos: 5,
+                                        item: Rc::from("y"),
+                                    }),
+                                },
+                            ),
                         ],
                         rest: None,
                     },
@@ -302,7 +381,10 @@ fn infer_lam_2() {
                     Expr::Var(0),
                     vec![Branch {
                         pattern: Pattern::Record {
-                            names: vec![Expr::mk_placeholder(0), Expr::mk_placeholder(1)],
+                            fields: vec![
+                                (Expr::mk_placeholder(0), Pattern::Name),
+                                (Expr::mk_placeholder(1), Pattern::Name),
+                            ],
                             rest: false,
                         },
                         body: Expr::Var(1),
@@ -336,15 +418,33 @@ fn infer_lam_3() {
                 vec![syntax::Spanned {
                     pos: 1,
                     item: syntax::Pattern::Record {
-                        names: vec![
-                            syntax::Spanned {
-                                pos: 2,
-                                item: Rc::from("x"),
-                            },
-                            syntax::Spanned {
-                                pos: 5,
-                                item: Rc::from("y"),
-                            },
+                        fields: vec![
+                            (
+                                syntax::Spanned {
+                                    pos: 2,
+                                    item: Rc::from("x"),
+                                },
+                                syntax::Spanned {
+                                    pos: 2,
+                                    item: syntax::Pattern::Name(syntax::Spanned {
+                                        pos: 2,
+                                        item: Rc::from("x"),
+                                    }),
+                                },
+                            ),
+                            (
+                                syntax::Spanned {
+                                    pos: 5,
+                                    item: Rc::from("y"),
+                                },
+                                syntax::Spanned {
+                                    pos: 5,
+                                    item: syntax::Pattern::Name(syntax::Spanned {
+                                        pos: 5,
+                                        item: Rc::from("y"),
+                                    }),
+                                },
+                            ),
                         ],
                         rest: None,
                     },
@@ -363,7 +463,10 @@ fn infer_lam_3() {
                     Expr::Var(0),
                     vec![Branch {
                         pattern: Pattern::Record {
-                            names: vec![Expr::mk_placeholder(0), Expr::mk_placeholder(1)],
+                            fields: vec![
+                                (Expr::mk_placeholder(0), Pattern::Name),
+                                (Expr::mk_placeholder(1), Pattern::Name),
+                            ],
                             rest: false,
                         },
                         body: Expr::Var(0),
@@ -397,15 +500,33 @@ fn infer_lam_4() {
                 vec![syntax::Spanned {
                     pos: 1,
                     item: syntax::Pattern::Record {
-                        names: vec![
-                            syntax::Spanned {
-                                pos: 2,
-                                item: Rc::from("x"),
-                            },
-                            syntax::Spanned {
-                                pos: 5,
-                                item: Rc::from("y"),
-                            },
+                        fields: vec![
+                            (
+                                syntax::Spanned {
+                                    pos: 2,
+                                    item: Rc::from("x"),
+                                },
+                                syntax::Spanned {
+                                    pos: 2,
+                                    item: syntax::Pattern::Name(syntax::Spanned {
+                                        pos: 2,
+                                        item: Rc::from("x"),
+                                    }),
+                                },
+                            ),
+                            (
+                                syntax::Spanned {
+                                    pos: 5,
+                                    item: Rc::from("y"),
+                                },
+                                syntax::Spanned {
+                                    pos: 5,
+                                    item: syntax::Pattern::Name(syntax::Spanned {
+                                        pos: 5,
+                                        item: Rc::from("y"),
+                                    }),
+                                },
+                            ),
                         ],
                         rest: Some(syntax::Spanned {
                             pos: 11,
@@ -426,7 +547,10 @@ fn infer_lam_4() {
                     Expr::Var(0),
                     vec![Branch {
                         pattern: Pattern::Record {
-                            names: vec![Expr::mk_placeholder(0), Expr::mk_placeholder(1)],
+                            fields: vec![
+                                (Expr::mk_placeholder(0), Pattern::Name),
+                                (Expr::mk_placeholder(1), Pattern::Name),
+                            ],
                             rest: true,
                         },
                         body: Expr::Var(0),
@@ -490,7 +614,10 @@ fn infer_lam_5() {
         let expected = Ok((
             Expr::mk_lam(
                 true,
-                Expr::mk_lam(true, Expr::mk_app(Expr::Var(1), Expr::Var(0))),
+                Expr::mk_lam(
+                    true,
+                    Expr::mk_located(8, Expr::mk_app(Expr::Var(1), Expr::Var(0))),
+                ),
             ),
             Type::arrow(
                 env.common_kinds,
@@ -968,10 +1095,13 @@ fn infer_case_1() {
                                 pos: 18,
                                 item: syntax::Pattern::Variant {
                                     name: Rc::from("X"),
-                                    arg: syntax::Spanned {
+                                    args: vec![syntax::Spanned {
                                         pos: 20,
-                                        item: Rc::from("a"),
-                                    },
+                                        item: syntax::Pattern::Name(syntax::Spanned {
+                                            pos: 20,
+                                            item: Rc::from("a"),
+                                        }),
+                                    }],
                                 },
                             },
                             body: syntax::Spanned {
@@ -986,12 +1116,15 @@ fn infer_case_1() {
         let expected = Ok((
             Expr::mk_lam(
                 true,
-                Expr::mk_case(
-                    Expr::Var(0),
-                    vec![Branch {
-                        pattern: Pattern::mk_variant(Expr::mk_placeholder(0)),
-                        body: Expr::Var(0),
-                    }],
+                Expr::mk_located(
+                    6,
+                    Expr::mk_case(
+                        Expr::Var(0),
+                        vec![Branch {
+                            pattern: Pattern::mk_variant(Expr::mk_placeholder(0), Pattern::Name),
+                            body: Expr::Var(0),
+                        }],
+                    ),
                 ),
             ),
             Type::mk_arrow(
@@ -1040,10 +1173,13 @@ fn infer_case_2() {
                                     pos: 18,
                                     item: syntax::Pattern::Variant {
                                         name: Rc::from("Left"),
-                                        arg: syntax::Spanned {
+                                        args: vec![syntax::Spanned {
                                             pos: 23,
-                                            item: Rc::from("a"),
-                                        },
+                                            item: syntax::Pattern::Name(syntax::Spanned {
+                                                pos: 23,
+                                                item: Rc::from("a"),
+                                            }),
+                                        }],
                                     },
                                 },
                                 body: syntax::Spanned {
@@ -1056,10 +1192,13 @@ fn infer_case_2() {
                                     pos: 32,
                                     item: syntax::Pattern::Variant {
                                         name: Rc::from("Right"),
-                                        arg: syntax::Spanned {
+                                        args: vec![syntax::Spanned {
                                             pos: 34,
-                                            item: Rc::from("b"),
-                                        },
+                                            item: syntax::Pattern::Name(syntax::Spanned {
+                                                pos: 34,
+                                                item: Rc::from("b"),
+                                            }),
+                                        }],
                                     },
                                 },
                                 body: syntax::Spanned {
@@ -1075,18 +1214,27 @@ fn infer_case_2() {
         let expected = Ok((
             Expr::mk_lam(
                 true,
-                Expr::mk_case(
-                    Expr::Var(0),
-                    vec![
-                        Branch {
-                            pattern: Pattern::mk_variant(Expr::mk_placeholder(0)),
-                            body: Expr::Var(0),
-                        },
-                        Branch {
-                            pattern: Pattern::mk_variant(Expr::mk_placeholder(1)),
-                            body: Expr::Var(0),
-                        },
-                    ],
+                Expr::mk_located(
+                    6,
+                    Expr::mk_case(
+                        Expr::Var(0),
+                        vec![
+                            Branch {
+                                pattern: Pattern::mk_variant(
+                                    Expr::mk_placeholder(0),
+                                    Pattern::Name,
+                                ),
+                                body: Expr::Var(0),
+                            },
+                            Branch {
+                                pattern: Pattern::mk_variant(
+                                    Expr::mk_placeholder(1),
+                                    Pattern::Name,
+                                ),
+                                body: Expr::Var(0),
+                            },
+                        ],
+                    ),
                 ),
             ),
             Type::mk_arrow(
@@ -1139,10 +1287,13 @@ fn infer_case_3() {
                                     pos: 18,
                                     item: syntax::Pattern::Variant {
                                         name: Rc::from("Left"),
-                                        arg: syntax::Spanned {
+                                        args: vec![syntax::Spanned {
                                             pos: 23,
-                                            item: Rc::from("a"),
-                                        },
+                                            item: syntax::Pattern::Name(syntax::Spanned {
+                                                pos: 23,
+                                                item: Rc::from("a"),
+                                            }),
+                                        }],
                                     },
                                 },
                                 body: syntax::Spanned {
@@ -1155,10 +1306,13 @@ fn infer_case_3() {
                                     pos: 32,
                                     item: syntax::Pattern::Variant {
                                         name: Rc::from("Right"),
-                                        arg: syntax::Spanned {
+                                        args: vec![syntax::Spanned {
                                             pos: 34,
-                                            item: Rc::from("b"),
-                                        },
+                                            item: syntax::Pattern::Name(syntax::Spanned {
+                                                pos: 34,
+                                                item: Rc::from("b"),
+                                            }),
+                                        }],
                                     },
                                 },
                                 body: syntax::Spanned {
@@ -1184,22 +1338,31 @@ fn infer_case_3() {
         let expected = Ok((
             Expr::mk_lam(
                 true,
-                Expr::mk_case(
-                    Expr::Var(0),
-                    vec![
-                        Branch {
-                            pattern: Pattern::mk_variant(Expr::mk_placeholder(0)),
-                            body: Expr::Var(0),
-                        },
-                        Branch {
-                            pattern: Pattern::mk_variant(Expr::mk_placeholder(1)),
-                            body: Expr::Var(0),
-                        },
-                        Branch {
-                            pattern: Pattern::Wildcard,
-                            body: Expr::Int(1),
-                        },
-                    ],
+                Expr::mk_located(
+                    6,
+                    Expr::mk_case(
+                        Expr::Var(0),
+                        vec![
+                            Branch {
+                                pattern: Pattern::mk_variant(
+                                    Expr::mk_placeholder(0),
+                                    Pattern::Name,
+                                ),
+                                body: Expr::Var(0),
+                            },
+                            Branch {
+                                pattern: Pattern::mk_variant(
+                                    Expr::mk_placeholder(1),
+                                    Pattern::Name,
+                                ),
+                                body: Expr::Var(0),
+                            },
+                            Branch {
+                                pattern: Pattern::Wildcard,
+                                body: Expr::Int(1),
+                            },
+                        ],
+                    ),
                 ),
             ),
             Type::mk_arrow(
@@ -1252,10 +1415,13 @@ fn infer_case_4() {
                                     pos: 18,
                                     item: syntax::Pattern::Variant {
                                         name: Rc::from("Left"),
-                                        arg: syntax::Spanned {
+                                        args: vec![syntax::Spanned {
                                             pos: 23,
-                                            item: Rc::from("a"),
-                                        },
+                                            item: syntax::Pattern::Name(syntax::Spanned {
+                                                pos: 23,
+                                                item: Rc::from("a"),
+                                            }),
+                                        }],
                                     },
                                 },
                                 body: syntax::Spanned {
@@ -1268,10 +1434,13 @@ fn infer_case_4() {
                                     pos: 32,
                                     item: syntax::Pattern::Variant {
                                         name: Rc::from("Left"),
-                                        arg: syntax::Spanned {
+                                        args: vec![syntax::Spanned {
                                             pos: 34,
-                                            item: Rc::from("b"),
-                                        },
+                                            item: syntax::Pattern::Name(syntax::Spanned {
+                                                pos: 34,
+                                                item: Rc::from("b"),
+                                            }),
+                                        }],
                                     },
                                 },
                                 body: syntax::Spanned {
@@ -1305,6 +1474,68 @@ fn infer_case_4() {
     })
 }
 
+#[test]
+fn infer_case_5() {
+    with_empty_env_and_state(|env, state| {
+        /*
+        \x -> case x of
+          Some 0 -> 1
+        */
+        let term = syntax::Spanned {
+            pos: 0,
+            item: syntax::Expr::mk_lam(
+                vec![syntax::Spanned {
+                    pos: 1,
+                    item: syntax::Pattern::Name(syntax::Spanned {
+                        pos: 1,
+                        item: Rc::from("x"),
+                    }),
+                }],
+                syntax::Spanned {
+                    pos: 6,
+                    item: syntax::Expr::mk_case(
+                        syntax::Spanned {
+                            pos: 11,
+                            item: syntax::Expr::Var(String::from("x")),
+                        },
+                        vec![syntax::Branch {
+                            pattern: syntax::Spanned {
+                                pos: 16,
+                                item: syntax::Pattern::Variant {
+                                    name: Rc::from("Some"),
+                                    args: vec![syntax::Spanned {
+                                        pos: 21,
+                                        item: syntax::Pattern::Int(syntax::Spanned {
+                                            pos: 21,
+                                            item: 0,
+                                        }),
+                                    }],
+                                },
+                            },
+                            body: syntax::Spanned {
+                                pos: 26,
+                                item: syntax::Expr::Int(1),
+                            },
+                        }],
+                    ),
+                },
+            ),
+        };
+        let expected = Err(Error::non_exhaustive(
+            &Source::Interactive {
+                label: String::from(SOURCE_LABEL),
+            },
+            11,
+            vec![Witness::Constructor {
+                name: Rc::from("Some"),
+                args: vec![Witness::Wildcard],
+            }],
+        ));
+        let actual = infer(env, state, &term).map(|(expr, ty)| (expr, state.zonk_type(ty)));
+        assert_eq!(expected, actual)
+    })
+}
+
 #[test]
 fn unify_variant_1() {
     let type_variables = {
@@ -1364,3 +1595,105 @@ fn unify_variant_1() {
         assert_eq!(expected, actual);
     })
 }
+
+#[test]
+fn infer_arithmetic_1() {
+    with_empty_env_and_state(|env, state| {
+        // \x -> x + 1.5
+        let term = syntax::Spanned {
+            pos: 0,
+            item: syntax::Expr::mk_lam(
+                vec![syntax::Spanned {
+                    pos: 1,
+                    item: syntax::Pattern::Name(syntax::Spanned {
+                        pos: 1,
+                        item: Rc::from("x"),
+                    }),
+                }],
+                syntax::Expr::mk_binop(
+                    syntax::Spanned {
+                        pos: 8,
+                        item: syntax::Binop::Add,
+                    },
+                    syntax::Spanned {
+                        pos: 6,
+                        item: syntax::Expr::Var(String::from("x")),
+                    },
+                    syntax::Spanned {
+                        pos: 10,
+                        item: syntax::Expr::Float(syntax::Float(1.5)),
+                    },
+                ),
+            ),
+        };
+        let expected = Ok((
+            Expr::mk_lam(
+                true,
+                Expr::mk_located(
+                    8,
+                    Expr::mk_binop(Binop::Add, Expr::Var(0), Expr::Float(Float(1.5))),
+                ),
+            ),
+            Type::arrow(env.common_kinds, Type::Float, Type::Float),
+        ));
+        let actual = infer(env, state, &term).map(|(expr, ty)| (expr, state.zonk_type(ty)));
+        assert_eq!(expected, actual)
+    })
+}
+
+#[test]
+fn infer_arithmetic_2() {
+    with_empty_env_and_state(|env, state| {
+        // \x -> x + x
+        let term = syntax::Spanned {
+            pos: 0,
+            item: syntax::Expr::mk_lam(
+                vec![syntax::Spanned {
+                    pos: 1,
+                    item: syntax::Pattern::Name(syntax::Spanned {
+                        pos: 1,
+                        item: Rc::from("x"),
+                    }),
+                }],
+                syntax::Expr::mk_binop(
+                    syntax::Spanned {
+                        pos: 8,
+                        item: syntax::Binop::Add,
+                    },
+                    syntax::Spanned {
+                        pos: 6,
+                        item: syntax::Expr::Var(String::from("x")),
+                    },
+                    syntax::Spanned {
+                        pos: 10,
+                        item: syntax::Expr::Var(String::from("x")),
+                    },
+                ),
+            ),
+        };
+        let (_, ty) = infer(env, state, &term).unwrap();
+        assert_eq!(
+            Type::arrow(
+                env.common_kinds,
+                Type::Meta(Kind::Type, 0),
+                Type::Meta(Kind::Type, 0)
+            ),
+            state.zonk_type(ty.clone())
+        );
+
+        default_arithmetic_types(
+            unification::Env {
+                common_kinds: env.common_kinds,
+                types: env.types,
+                type_variables: env.type_variables,
+            },
+            state,
+            env.source,
+        )
+        .unwrap();
+        assert_eq!(
+            Type::arrow(env.common_kinds, Type::Int, Type::Int),
+            state.zonk_type(ty)
+        );
+    })
+}
@@ -57,6 +57,7 @@ impl Solutions {
         match ty {
             Type::Bool
             | Type::Int
+            | Type::Float
             | Type::Char
             | Type::String
             | Type::Bytes
@@ -65,6 +66,12 @@ impl Solutions {
             | Type::Cmd
             | Type::DebugRecordFields
             | Type::DebugVariantCtor
+            | Type::ToJsonRecordFields
+            | Type::FromJsonRecordFields
+            | Type::EqRecordFields
+            | Type::EqVariantCtor
+            | Type::OrdRecordFields
+            | Type::OrdVariantCtor
             | Type::Arrow(_)
             | Type::FatArrow(_)
             | Type::Array(_)
@@ -117,6 +124,7 @@ impl Solutions {
         match ty {
             Type::Bool
             | Type::Int
+            | Type::Float
             | Type::Char
             | Type::String
             | Type::Bytes
@@ -124,7 +132,13 @@ impl Solutions {
             | Type::Unit
             | Type::Cmd
             | Type::DebugRecordFields
-            | Type::DebugVariantCtor => {}
+            | Type::DebugVariantCtor
+            | Type::ToJsonRecordFields
+            | Type::FromJsonRecordFields
+            | Type::EqRecordFields
+            | Type::EqVariantCtor
+            | Type::OrdRecordFields
+            | Type::OrdVariantCtor => {}
             Type::Constraints(constraints) => constraints.iter_mut().for_each(|constraint| {
                 self.zonk_mut(kind_solutions, constraint);
             }),
@@ -542,6 +556,114 @@ fn unify_inner(
                 actual.clone(),
             )),
         },
+        Type::ToJsonRecordFields => match actual {
+            Type::ToJsonRecordFields => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
+        Type::FromJsonRecordFields => match actual {
+            Type::FromJsonRecordFields => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
+        Type::EqRecordFields => match actual {
+            Type::EqRecordFields => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
+        Type::EqVariantCtor => match actual {
+            Type::EqVariantCtor => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
+        Type::OrdRecordFields => match actual {
+            Type::OrdRecordFields => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
+        Type::OrdVariantCtor => match actual {
+            Type::OrdVariantCtor => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
         Type::Bool => match actual {
             Type::Bool => Ok(()),
             Type::Meta(_, meta) => unify_meta_right(
@@ -578,6 +700,24 @@ fn unify_inner(
                 actual.clone(),
             )),
         },
+        Type::Float => match actual {
+            Type::Float => Ok(()),
+            Type::Meta(_, meta) => unify_meta_right(
+                env,
+                kind_inference_state,
+                type_solutions,
+                pos,
+                expected,
+                meta,
+            ),
+            _ => Err(ErrorInfo::mismatch(
+                &kind_inference_state.kind_solutions,
+                type_solutions,
+                env.type_variables,
+                expected.clone(),
+                actual.clone(),
+            )),
+        },
         Type::Char => match actual {
             Type::Char => Ok(()),
             Type::Meta(_, meta) => unify_meta_right(